@@ -111,9 +111,11 @@ impl DeriveInput {
                     fn load(manager: &geng::asset::Manager, path: &std::path::Path, options: &Self::Options) -> geng::asset::Future<Self> {
                         let manager = manager.clone();
                         let path = path.to_owned();
-                        async move {
-                            Ok(manager.load_serde(path).await?)
-                        }.boxed_local()
+                        let format = geng::asset::SerdeFormat::detect(&path)
+                            .or(geng::asset::SerdeFormat::from_extension(#ext));
+                        Box::pin(async move {
+                            manager.load_serde_with::<Self>(path, format).await
+                        })
                     }
                     const DEFAULT_EXT: Option<&'static str> = Some(#ext);
                 }
@@ -150,9 +152,14 @@ impl DeriveInput {
                     Some(path) => quote! {
                         manager.load_serde(base_path.join(#path))
                     },
-                    None => quote! {
-                        manager.load_serde(base_path.join(stringify!(#ident)), #ext)
-                    },
+                    None => quote! {{
+                        let path = base_path.join(stringify!(#ident));
+                        let path = match #ext {
+                            Some(ext) => path.with_extension(ext),
+                            None => path,
+                        };
+                        manager.load_serde(path)
+                    }},
                 };
             }
             let list = match (&field.listed_in, &field.list) {
@@ -168,9 +175,7 @@ impl DeriveInput {
                         },
                     };
                     quote! {
-                        manager.load_serde::<Vec<String>>(
-                            #base_path.join(#listed_in)
-                        ).await?.into_iter()
+                        manager.load_list(#base_path.join(#listed_in)).await?.into_iter()
                     }
                 }),
                 (Some(_), Some(_)) => panic!("Can't specify both list and listed_in"),
@@ -263,7 +268,14 @@ impl DeriveInput {
                         Ok::<#ty, anyhow::Error>(value)
                     }
                 }
-            });
+            })
+            .collect::<Vec<_>>();
+        let field_indices = (0..field_names.len()).collect::<Vec<usize>>();
+        let field_tys = data
+            .fields
+            .iter()
+            .map(|field| &field.ty)
+            .collect::<Vec<_>>();
         let load_fields = if sequential {
             quote! {
                 #(
@@ -288,8 +300,7 @@ impl DeriveInput {
         };
         quote! {
             #[allow(clippy::needless_question_mark)]
-            impl geng::asset::Load for #ident
-                /* where #(#field_constraints),* */ {
+            impl #impl_generics geng::asset::Load for #ident #ty_generics #where_clause {
                 type Options = ();
                 fn load(manager: &geng::asset::Manager, base_path: &std::path::Path, options: &Self::Options) -> geng::asset::Future<Self> {
                     let manager = manager.clone();
@@ -303,6 +314,57 @@ impl DeriveInput {
                 }
                 const DEFAULT_EXT: Option<&'static str> = None;
             }
+
+            #[allow(clippy::needless_question_mark)]
+            impl #impl_generics geng::asset::Reload for #ident #ty_generics #where_clause {
+                const FIELDS: &'static [&'static str] = &[#(stringify!(#field_names),)*];
+                fn load_field(
+                    manager: &geng::asset::Manager,
+                    base_path: &std::path::Path,
+                    field: usize,
+                ) -> geng::asset::Future<Box<dyn std::any::Any>> {
+                    let manager = manager.clone();
+                    let base_path = base_path.to_owned();
+                    Box::pin(async move {
+                        Ok(match field {
+                            #(
+                                #field_indices => Box::new(anyhow::Context::context(
+                                    #field_loaders.await,
+                                    concat!("Failed to load ", stringify!(#field_names)),
+                                )?) as Box<dyn std::any::Any>,
+                            )*
+                            _ => anyhow::bail!("{} has no field #{field}", stringify!(#ident)),
+                        })
+                    })
+                }
+                fn from_fields(fields: Vec<Box<dyn std::any::Any>>) -> anyhow::Result<Self> {
+                    if fields.len() != Self::FIELDS.len() {
+                        anyhow::bail!(
+                            "{} has {} fields, got {}",
+                            stringify!(#ident),
+                            Self::FIELDS.len(),
+                            fields.len(),
+                        );
+                    }
+                    let mut fields = fields.into_iter();
+                    Ok(Self {
+                        #(#field_names: *fields.next().unwrap().downcast::<#field_tys>().map_err(|_| {
+                            anyhow::anyhow!(concat!("Wrong type for ", stringify!(#field_names)))
+                        })?,)*
+                    })
+                }
+                fn set_field(&mut self, field: usize, value: Box<dyn std::any::Any>) -> anyhow::Result<()> {
+                    match field {
+                        #(#field_indices => {
+                            self.#field_names = *value.downcast::<#field_tys>().map_err(|_| {
+                                anyhow::anyhow!(concat!("Wrong type for ", stringify!(#field_names)))
+                            })?;
+                        })*
+                        _ => anyhow::bail!("{} has no field #{field}", stringify!(#ident)),
+                    }
+                    Ok(())
+                }
+            }
         }
     }
 }
@@ -0,0 +1,26 @@
+//! Generate the manifest of an asset directory for [geng_asset::Manager::load_manifest]
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (dir, output): (std::path::PathBuf, std::path::PathBuf) = match args.as_slice() {
+        [dir] => (
+            dir.into(),
+            std::path::Path::new(dir).join(geng_asset::MANIFEST_FILE),
+        ),
+        [dir, output] => (dir.into(), output.into()),
+        _ => anyhow::bail!("Usage: geng-manifest <assets dir> [output file]"),
+    };
+    let manifest = geng_asset::Manifest::generate(&dir)?;
+    manifest.write(&output)?;
+    println!(
+        "{} files, version {}, written to {}",
+        manifest.files.len(),
+        manifest.version(),
+        output.display(),
+    );
+    Ok(())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn main() {}
@@ -0,0 +1,13 @@
+//! Pack a directory into a single file for [geng_asset::Manager::mount_pack]
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [dir, output] = args.as_slice() else {
+        anyhow::bail!("Usage: geng-pack <assets dir> <output file>");
+    };
+    geng_asset::pack::pack_dir(dir, output)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn main() {}
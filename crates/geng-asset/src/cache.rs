@@ -0,0 +1,333 @@
+use super::*;
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::rc::Weak;
+
+/// Shared asset loaded through the [Manager] cache, see [Manager::load_cached].
+///
+/// Loading the same path with the same options gives the same asset
+/// while any handle to it is alive, it is evicted together with the last handle.
+/// Loading `Rc<T>` shares assets the same way
+pub struct Handle<T>(Rc<T>);
+
+impl<T> Handle<T> {
+    pub fn ptr_eq(a: &Self, b: &Self) -> bool {
+        Rc::ptr_eq(&a.0, &b.0)
+    }
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> std::ops::Deref for Handle<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: Load> Load for Handle<T>
+where
+    T::Options: PartialEq,
+{
+    type Options = T::Options;
+    fn load(manager: &Manager, path: &Path, options: &Self::Options) -> Future<Self> {
+        manager.load_cached(path, options)
+    }
+    const DEFAULT_EXT: Option<&'static str> = T::DEFAULT_EXT;
+}
+
+impl<T: Load> Load for Rc<T>
+where
+    T::Options: PartialEq,
+{
+    type Options = T::Options;
+    fn load(manager: &Manager, path: &Path, options: &Self::Options) -> Future<Self> {
+        let handle = manager.load_cached::<T>(path, options);
+        async move { Ok(handle.await?.0) }.boxed_local()
+    }
+    const DEFAULT_EXT: Option<&'static str> = T::DEFAULT_EXT;
+    fn memory_usage(&self) -> usize {
+        (**self).memory_usage()
+    }
+}
+
+/// Cached assets of a single type, see [Manager::cache_stats]
+#[derive(Debug, Clone)]
+pub struct CacheStats {
+    pub type_name: &'static str,
+    /// Number of assets currently alive
+    pub count: usize,
+    /// Sum of [Load::memory_usage] of alive assets, in bytes
+    pub memory: usize,
+}
+
+type SharedResult<T> = Result<(Handle<T>, Rc<[PathBuf]>), Rc<anyhow::Error>>;
+type SharedLoad<T> = future::Shared<Pin<Box<dyn StdFuture<Output = SharedResult<T>>>>>;
+
+enum Entry<T> {
+    Loading(SharedLoad<T>),
+    Loaded {
+        asset: Weak<T>,
+        /// Paths read while loading, marked again on every cache hit
+        paths: Rc<[PathBuf]>,
+    },
+}
+
+impl<T> Entry<T> {
+    fn is_alive(&self) -> bool {
+        match self {
+            Self::Loading(_) => true,
+            Self::Loaded { asset, .. } => asset.strong_count() != 0,
+        }
+    }
+}
+
+/// Assets loaded with different options, compared with `==`
+type Variants<T> = Vec<(<T as Load>::Options, Entry<T>)>;
+
+struct TypedCache<T: Load> {
+    entries: HashMap<PathBuf, Variants<T>>,
+}
+
+impl<T: Load> TypedCache<T>
+where
+    T::Options: PartialEq,
+{
+    fn get(&mut self, path: &Path, options: &T::Options) -> Option<&mut Entry<T>> {
+        self.entries
+            .get_mut(path)?
+            .iter_mut()
+            .find(|(entry_options, _)| entry_options == options)
+            .map(|(_, entry)| entry)
+    }
+    fn insert(&mut self, path: PathBuf, options: T::Options, entry: Entry<T>) {
+        match self.get(&path, &options) {
+            Some(existing) => *existing = entry,
+            None => self.entries.entry(path).or_default().push((options, entry)),
+        }
+    }
+    fn remove(&mut self, path: &Path, options: &T::Options) {
+        if let Some(variants) = self.entries.get_mut(path) {
+            variants.retain(|(entry_options, _)| entry_options != options);
+            if variants.is_empty() {
+                self.entries.remove(path);
+            }
+        }
+    }
+    /// Remove entries matching `f`
+    fn remove_if(&mut self, mut f: impl FnMut(&Path, &Entry<T>) -> bool) {
+        self.entries.retain(|path, variants| {
+            variants.retain(|(_, entry)| !f(path, entry));
+            !variants.is_empty()
+        });
+    }
+}
+
+trait AnyCache {
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn stats(&self) -> CacheStats;
+    fn evict(&mut self, path: &Path);
+    fn clear(&mut self);
+}
+
+impl<T: Load> AnyCache for TypedCache<T>
+where
+    T::Options: PartialEq,
+{
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    fn stats(&self) -> CacheStats {
+        let mut stats = CacheStats {
+            type_name: std::any::type_name::<T>(),
+            count: 0,
+            memory: 0,
+        };
+        for (_, entry) in self.entries.values().flatten() {
+            if let Entry::Loaded { asset, .. } = entry {
+                if let Some(asset) = asset.upgrade() {
+                    stats.count += 1;
+                    stats.memory += asset.memory_usage();
+                }
+            }
+        }
+        stats
+    }
+    fn evict(&mut self, path: &Path) {
+        let path = hot::canonicalize(path);
+        self.remove_if(|asset_path, entry| match entry {
+            Entry::Loading(_) => false,
+            Entry::Loaded { paths, .. } => std::iter::once(asset_path)
+                .chain(paths.iter().map(PathBuf::as_path))
+                .any(|read| hot::canonicalize(read) == path),
+        });
+    }
+    fn clear(&mut self) {
+        self.remove_if(|_, entry| matches!(entry, Entry::Loaded { .. }));
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Cache {
+    types: HashMap<TypeId, Box<dyn AnyCache>>,
+}
+
+impl Cache {
+    fn typed<T: Load>(&mut self) -> &mut TypedCache<T>
+    where
+        T::Options: PartialEq,
+    {
+        self.types
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| {
+                Box::new(TypedCache::<T> {
+                    entries: HashMap::new(),
+                })
+            })
+            .as_any_mut()
+            .downcast_mut()
+            .unwrap()
+    }
+}
+
+impl Manager {
+    /// Load asset through the cache, sharing it with every other load
+    /// of the same type, path and options, including the ones still in progress.
+    ///
+    /// Cached assets are dropped when the last [Handle] is, use [Manager::evict]
+    /// to force reloading assets that are still in use
+    pub fn load_cached<T: Load>(
+        &self,
+        path: impl AsRef<Path>,
+        options: &T::Options,
+    ) -> Future<Handle<T>>
+    where
+        T::Options: PartialEq,
+    {
+        let key = (self.resolve(path), options.clone());
+        let cached = match self
+            .inner
+            .cache
+            .borrow_mut()
+            .typed::<T>()
+            .get(&key.0, &key.1)
+        {
+            Some(Entry::Loaded { asset, paths }) => asset
+                .upgrade()
+                .map(|asset| Ok((Handle(asset), paths.clone()))),
+            Some(Entry::Loading(shared)) => Some(Err(shared.clone())),
+            None => None,
+        };
+        let shared = match cached {
+            Some(Ok((asset, paths))) => {
+                for path in paths.iter() {
+                    self.mark_tracked(path);
+                }
+                return future::ready(Ok(asset)).boxed_local();
+            }
+            Some(Err(shared)) => shared,
+            None => {
+                // Not holding the cache borrow here, loading can start nested loads right away
+                let shared = self.load_shared::<T>(key.clone());
+                let mut cache = self.inner.cache.borrow_mut();
+                let cache = cache.typed::<T>();
+                cache.remove_if(|_, entry| !entry.is_alive());
+                cache.insert(key.0, key.1, Entry::Loading(shared.clone()));
+                shared
+            }
+        };
+        let manager = self.clone();
+        async move {
+            let (asset, paths) = shared.await.map_err(|e| anyhow::anyhow!("{e:#}"))?;
+            for path in paths.iter() {
+                manager.mark_tracked(path);
+            }
+            Ok(asset)
+        }
+        .boxed_local()
+    }
+
+    fn load_shared<T: Load>(&self, key: (PathBuf, T::Options)) -> SharedLoad<T>
+    where
+        T::Options: PartialEq,
+    {
+        // Paths are marked for every requester separately
+        let manager = Manager {
+            trackers: Vec::new(),
+            ..self.clone()
+        };
+        let load = manager.track(|manager| T::load(manager, &key.0, &key.1));
+        let future: Pin<Box<dyn StdFuture<Output = SharedResult<T>>>> = async move {
+            let result = load.await;
+            let mut cache = manager.inner.cache.borrow_mut();
+            let cache = cache.typed::<T>();
+            let (path, options) = key;
+            // Entry could have been evicted while loading
+            let loading = matches!(cache.get(&path, &options), Some(Entry::Loading(_)));
+            match result {
+                Ok((asset, paths)) => {
+                    let asset = Handle(Rc::new(asset));
+                    let paths: Rc<[PathBuf]> = paths.into();
+                    if loading {
+                        cache.insert(
+                            path,
+                            options,
+                            Entry::Loaded {
+                                asset: Rc::downgrade(&asset.0),
+                                paths: paths.clone(),
+                            },
+                        );
+                    }
+                    Ok((asset, paths))
+                }
+                Err(e) => {
+                    if loading {
+                        cache.remove(&path, &options);
+                    }
+                    Err(Rc::new(e))
+                }
+            }
+        }
+        .boxed_local();
+        future.shared()
+    }
+
+    /// Remove every cached asset that has read `path` so that it is loaded again next time.
+    ///
+    /// Existing handles stay valid
+    pub fn evict(&self, path: impl AsRef<Path>) {
+        for cache in self.inner.cache.borrow_mut().types.values_mut() {
+            cache.evict(path.as_ref());
+        }
+    }
+
+    /// Remove all cached assets, existing handles stay valid
+    pub fn clear_cache(&self) {
+        for cache in self.inner.cache.borrow_mut().types.values_mut() {
+            cache.clear();
+        }
+    }
+
+    /// Number and memory usage of alive cached assets per type
+    pub fn cache_stats(&self) -> Vec<CacheStats> {
+        self.inner
+            .cache
+            .borrow()
+            .types
+            .values()
+            .map(|cache| cache.stats())
+            .filter(|stats| stats.count != 0)
+            .collect()
+    }
+}
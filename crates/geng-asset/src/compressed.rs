@@ -0,0 +1,497 @@
+//! KTX2, DDS and Basis texture containers.
+//!
+//! Block compressed data is uploaded as stored without flipping,
+//! so images should be exported bottom row first
+//! (`toktx --lower_left_maps_to_s0t0`, `texconv -vflip`).
+//! Basis data is transcoded to the best format the device supports,
+//! which needs the `basis` feature and is not available on the web
+use super::*;
+
+use batbox_la::*;
+use ugli::CompressedFormat;
+
+/// Extensions loaded with [CompressedImage] by `Load for ugli::Texture`
+pub const COMPRESSED_EXTENSIONS: &[&str] = &["ktx2", "dds", "basis"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Compressed(CompressedFormat),
+    /// Uncompressed 8-bit RGBA
+    Rgba8,
+    /// Basis UASTC, has to be [transcoded](CompressedImage::transcode)
+    Uastc {
+        has_alpha: bool,
+    },
+}
+
+/// Texture data read from a container file, before uploading
+#[derive(Debug, Clone)]
+pub struct CompressedImage {
+    pub size: vec2<usize>,
+    pub format: ImageFormat,
+    /// Mip levels starting from the full size
+    pub levels: Vec<Vec<u8>>,
+}
+
+fn u32_at(data: &[u8], offset: usize) -> anyhow::Result<u32> {
+    let bytes = data
+        .get(offset..offset + 4)
+        .ok_or_else(|| anyhow::anyhow!("File is truncated"))?;
+    Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn u64_at(data: &[u8], offset: usize) -> anyhow::Result<u64> {
+    let bytes = data
+        .get(offset..offset + 8)
+        .ok_or_else(|| anyhow::anyhow!("File is truncated"))?;
+    Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn slice(data: &[u8], offset: usize, len: usize) -> anyhow::Result<&[u8]> {
+    data.get(offset..offset.saturating_add(len))
+        .ok_or_else(|| anyhow::anyhow!("File is truncated"))
+}
+
+/// Larger than any device supports, bounds allocations for corrupt files
+const MAX_SIZE: usize = 1 << 16;
+
+/// Check the size read from a file, returning the length of the full mip chain
+fn check_size(size: vec2<usize>) -> anyhow::Result<usize> {
+    if size.x == 0 || size.y == 0 {
+        anyhow::bail!("Texture size must not be zero");
+    }
+    if size.x > MAX_SIZE || size.y > MAX_SIZE {
+        anyhow::bail!("Texture size {}x{} is too large", size.x, size.y);
+    }
+    Ok((usize::BITS - size.x.max(size.y).leading_zeros()) as usize)
+}
+
+fn check_level_count(level_count: usize, max: usize) -> anyhow::Result<()> {
+    if level_count > max {
+        anyhow::bail!("File has {level_count} mip levels, the texture can only have {max}");
+    }
+    Ok(())
+}
+
+impl ImageFormat {
+    fn data_size(&self, size: vec2<usize>) -> anyhow::Result<usize> {
+        match self {
+            Self::Compressed(format) => format.data_size(size),
+            Self::Rgba8 => size.x.checked_mul(size.y).and_then(|x| x.checked_mul(4)),
+            // 16 bytes per 4x4 block
+            Self::Uastc { .. } => size
+                .x
+                .div_ceil(4)
+                .checked_mul(size.y.div_ceil(4))
+                .and_then(|x| x.checked_mul(16)),
+        }
+        .ok_or_else(|| anyhow::anyhow!("Texture size {}x{} is too large", size.x, size.y))
+    }
+}
+
+impl CompressedImage {
+    pub fn parse_ktx2(data: &[u8]) -> anyhow::Result<Self> {
+        const IDENTIFIER: &[u8; 12] = b"\xABKTX 20\xBB\r\n\x1A\n";
+        if !data.starts_with(IDENTIFIER) {
+            anyhow::bail!("Not a KTX2 file");
+        }
+        let vk_format = u32_at(data, 12)?;
+        let size = vec2(u32_at(data, 20)? as usize, u32_at(data, 24)? as usize);
+        let depth = u32_at(data, 28)?;
+        let layers = u32_at(data, 32)?;
+        let faces = u32_at(data, 36)?;
+        let level_count = u32_at(data, 40)?.max(1) as usize;
+        let supercompression = u32_at(data, 44)?;
+        if depth > 1 || layers > 1 || faces > 1 {
+            anyhow::bail!("Only 2D textures are supported, not arrays, cube maps or 3D textures");
+        }
+        check_level_count(level_count, check_size(size)?)?;
+        let format = match vk_format {
+            131 | 132 => ImageFormat::Compressed(CompressedFormat::Bc1),
+            133 | 134 => ImageFormat::Compressed(CompressedFormat::Bc1Alpha),
+            135 | 136 => ImageFormat::Compressed(CompressedFormat::Bc2),
+            137 | 138 => ImageFormat::Compressed(CompressedFormat::Bc3),
+            139 => ImageFormat::Compressed(CompressedFormat::Bc4),
+            141 => ImageFormat::Compressed(CompressedFormat::Bc5),
+            145 | 146 => ImageFormat::Compressed(CompressedFormat::Bc7),
+            147 | 148 => ImageFormat::Compressed(CompressedFormat::Etc2),
+            151 | 152 => ImageFormat::Compressed(CompressedFormat::Etc2Alpha),
+            157 | 158 => ImageFormat::Compressed(CompressedFormat::Astc4x4),
+            37 | 43 => ImageFormat::Rgba8,
+            0 => {
+                const BASIS_LZ: u32 = 1;
+                const MODEL_UASTC: u8 = 166;
+                if supercompression == BASIS_LZ {
+                    anyhow::bail!(
+                        "ETC1S (BasisLZ) KTX2 files are not supported, use UASTC or .basis files"
+                    );
+                }
+                let dfd_offset = u32_at(data, 48)? as usize;
+                // Color model of the first descriptor block, after total size and block header
+                let model = slice(data, dfd_offset.saturating_add(12), 1)?[0];
+                if model != MODEL_UASTC {
+                    anyhow::bail!("Unknown KTX2 color model {model}");
+                }
+                // Channel id of the first sample is RGB, RGBA, RRR or RRRG
+                let channel = slice(data, dfd_offset.saturating_add(31), 1)?[0] & 0xf;
+                ImageFormat::Uastc {
+                    has_alpha: channel == 3 || channel == 5,
+                }
+            }
+            _ => anyhow::bail!("Unsupported KTX2 format {vk_format}"),
+        };
+        let mut levels = Vec::with_capacity(level_count);
+        let mut level_size = size;
+        for level in 0..level_count {
+            let index = 80 + level * 24;
+            let offset = usize::try_from(u64_at(data, index)?)?;
+            let length = usize::try_from(u64_at(data, index + 8)?)?;
+            let level_data = slice(data, offset, length)?;
+            let expected = format.data_size(level_size)?;
+            let level_data = match supercompression {
+                0 => level_data.to_vec(),
+                2 => {
+                    use std::io::Read;
+                    let mut result = Vec::new();
+                    // One byte more than expected is enough to detect a wrong size
+                    ruzstd::StreamingDecoder::new(level_data)
+                        .map_err(|e| anyhow::anyhow!("{e}"))?
+                        .take(expected as u64 + 1)
+                        .read_to_end(&mut result)?;
+                    result
+                }
+                scheme => anyhow::bail!("Unsupported KTX2 supercompression scheme {scheme}"),
+            };
+            if level_data.len() != expected {
+                anyhow::bail!(
+                    "Mip level {level} has {} bytes, expected {expected}",
+                    level_data.len(),
+                );
+            }
+            levels.push(level_data);
+            level_size = level_size.map(|x| (x / 2).max(1));
+        }
+        Ok(Self {
+            size,
+            format,
+            levels,
+        })
+    }
+
+    pub fn parse_dds(data: &[u8]) -> anyhow::Result<Self> {
+        if !data.starts_with(b"DDS ") || u32_at(data, 4)? != 124 {
+            anyhow::bail!("Not a DDS file");
+        }
+        const MIPMAP_COUNT: u32 = 0x20000;
+        const DEPTH: u32 = 0x800000;
+        const FOURCC: u32 = 0x4;
+        const RGB: u32 = 0x40;
+        const CUBEMAP: u32 = 0x200;
+        let flags = u32_at(data, 8)?;
+        let size = vec2(u32_at(data, 16)? as usize, u32_at(data, 12)? as usize);
+        let level_count = match flags & MIPMAP_COUNT {
+            0 => 1,
+            _ => u32_at(data, 28)?.max(1) as usize,
+        };
+        if flags & DEPTH != 0 || u32_at(data, 112)? & CUBEMAP != 0 {
+            anyhow::bail!("Only 2D textures are supported, not cube maps or 3D textures");
+        }
+        check_level_count(level_count, check_size(size)?)?;
+        let pixel_flags = u32_at(data, 80)?;
+        let four_cc = slice(data, 84, 4)?;
+        let mut offset = 128;
+        let format = if pixel_flags & FOURCC != 0 {
+            let compressed = match four_cc {
+                b"DXT1" => CompressedFormat::Bc1Alpha,
+                b"DXT2" | b"DXT3" => CompressedFormat::Bc2,
+                b"DXT4" | b"DXT5" => CompressedFormat::Bc3,
+                b"ATI1" | b"BC4U" => CompressedFormat::Bc4,
+                b"ATI2" | b"BC5U" => CompressedFormat::Bc5,
+                b"DX10" => {
+                    offset += 20;
+                    if u32_at(data, 140)? > 1 {
+                        anyhow::bail!("Texture arrays are not supported");
+                    }
+                    match u32_at(data, 128)? {
+                        71 | 72 => CompressedFormat::Bc1Alpha,
+                        74 | 75 => CompressedFormat::Bc2,
+                        77 | 78 => CompressedFormat::Bc3,
+                        80 => CompressedFormat::Bc4,
+                        83 => CompressedFormat::Bc5,
+                        98 | 99 => CompressedFormat::Bc7,
+                        format => anyhow::bail!("Unsupported DXGI format {format}"),
+                    }
+                }
+                _ => anyhow::bail!(
+                    "Unsupported DDS format {:?}",
+                    String::from_utf8_lossy(four_cc)
+                ),
+            };
+            ImageFormat::Compressed(compressed)
+        } else if pixel_flags & RGB != 0
+            && u32_at(data, 88)? == 32
+            && (u32_at(data, 92)?, u32_at(data, 96)?, u32_at(data, 100)?)
+                == (0xff, 0xff00, 0xff0000)
+        {
+            ImageFormat::Rgba8
+        } else {
+            anyhow::bail!("Unsupported uncompressed DDS pixel format");
+        };
+        let mut levels = Vec::with_capacity(level_count);
+        let mut level_size = size;
+        for _ in 0..level_count {
+            let length = format.data_size(level_size)?;
+            levels.push(slice(data, offset, length)?.to_vec());
+            offset += length;
+            level_size = level_size.map(|x| (x / 2).max(1));
+        }
+        Ok(Self {
+            size,
+            format,
+            levels,
+        })
+    }
+
+    /// Transcode Basis data to the first suitable format in `supported`,
+    /// other formats are returned as is
+    pub fn transcode(self, supported: &[CompressedFormat]) -> anyhow::Result<Self> {
+        match self.format {
+            ImageFormat::Uastc { has_alpha } => basis::transcode_uastc(self, has_alpha, supported),
+            _ => Ok(self),
+        }
+    }
+
+    pub fn upload(&self, ugli: &Ugli) -> anyhow::Result<ugli::Texture> {
+        match self.format {
+            ImageFormat::Compressed(format) => Ok(ugli::Texture::from_compressed(
+                ugli,
+                format,
+                self.size,
+                &self.levels,
+            )?),
+            ImageFormat::Rgba8 => {
+                let mut image = image::RgbaImage::from_raw(
+                    self.size.x as u32,
+                    self.size.y as u32,
+                    self.levels[0].clone(),
+                )
+                .unwrap();
+                // Cancel out the flip in from_image_image, same as compressed data
+                image::imageops::flip_vertical_in_place(&mut image);
+                Ok(ugli::Texture::from_image_image(ugli, image))
+            }
+            ImageFormat::Uastc { .. } => anyhow::bail!("Basis data has to be transcoded first"),
+        }
+    }
+}
+
+/// Transcode targets in order of preference, the ones without alpha are only used for opaque images
+#[cfg_attr(
+    not(all(feature = "basis", not(target_arch = "wasm32"))),
+    allow(dead_code)
+)]
+const TRANSCODE_TARGETS: [(CompressedFormat, bool); 6] = [
+    (CompressedFormat::Astc4x4, true),
+    (CompressedFormat::Bc7, true),
+    (CompressedFormat::Etc2Alpha, true),
+    (CompressedFormat::Bc3, true),
+    (CompressedFormat::Etc1, false),
+    (CompressedFormat::Bc1, false),
+];
+
+#[cfg(all(feature = "basis", not(target_arch = "wasm32")))]
+mod basis {
+    use super::*;
+    use basis_universal::{
+        DecodeFlags, LowLevelUastcTranscoder, SliceParametersUastc, TranscodeParameters,
+        Transcoder, TranscoderBlockFormat, TranscoderTextureFormat,
+    };
+
+    fn target(
+        supported: &[CompressedFormat],
+        has_alpha: bool,
+    ) -> Option<(
+        CompressedFormat,
+        TranscoderBlockFormat,
+        TranscoderTextureFormat,
+    )> {
+        let (format, _) = TRANSCODE_TARGETS
+            .into_iter()
+            .find(|&(format, alpha)| (alpha || !has_alpha) && supported.contains(&format))?;
+        Some(match format {
+            CompressedFormat::Astc4x4 => (
+                format,
+                TranscoderBlockFormat::ASTC_4x4,
+                TranscoderTextureFormat::ASTC_4x4_RGBA,
+            ),
+            CompressedFormat::Bc7 => (
+                format,
+                TranscoderBlockFormat::BC7,
+                TranscoderTextureFormat::BC7_RGBA,
+            ),
+            CompressedFormat::Etc2Alpha => (
+                format,
+                TranscoderBlockFormat::ETC2_RGBA,
+                TranscoderTextureFormat::ETC2_RGBA,
+            ),
+            CompressedFormat::Bc3 => (
+                format,
+                TranscoderBlockFormat::BC3,
+                TranscoderTextureFormat::BC3_RGBA,
+            ),
+            CompressedFormat::Etc1 => (
+                format,
+                TranscoderBlockFormat::ETC1,
+                TranscoderTextureFormat::ETC1_RGB,
+            ),
+            CompressedFormat::Bc1 => (
+                format,
+                TranscoderBlockFormat::BC1,
+                TranscoderTextureFormat::BC1_RGB,
+            ),
+            _ => unreachable!(),
+        })
+    }
+
+    pub fn transcode_uastc(
+        image: CompressedImage,
+        has_alpha: bool,
+        supported: &[CompressedFormat],
+    ) -> anyhow::Result<CompressedImage> {
+        basis_universal::transcoder_init();
+        let (format, block_format) = match target(supported, has_alpha) {
+            Some((format, block_format, _)) => (ImageFormat::Compressed(format), block_format),
+            None => (ImageFormat::Rgba8, TranscoderBlockFormat::RGBA32),
+        };
+        let transcoder = LowLevelUastcTranscoder::new();
+        let mut levels = Vec::with_capacity(image.levels.len());
+        let mut level_size = image.size;
+        for data in &image.levels {
+            let blocks = level_size.map(|x| x.div_ceil(4) as u32);
+            levels.push(
+                transcoder
+                    .transcode_slice(
+                        data,
+                        SliceParametersUastc {
+                            num_blocks_x: blocks.x,
+                            num_blocks_y: blocks.y,
+                            has_alpha,
+                            original_width: level_size.x as u32,
+                            original_height: level_size.y as u32,
+                        },
+                        DecodeFlags::HIGH_QUALITY,
+                        block_format,
+                    )
+                    .map_err(|e| anyhow::anyhow!("Failed to transcode UASTC: {e:?}"))?,
+            );
+            level_size = level_size.map(|x| (x / 2).max(1));
+        }
+        if format == ImageFormat::Rgba8 {
+            levels.truncate(1);
+        }
+        Ok(CompressedImage {
+            size: image.size,
+            format,
+            levels,
+        })
+    }
+
+    pub fn transcode_file(
+        data: &[u8],
+        supported: &[CompressedFormat],
+    ) -> anyhow::Result<CompressedImage> {
+        basis_universal::transcoder_init();
+        let mut transcoder = Transcoder::new();
+        transcoder
+            .prepare_transcoding(data)
+            .map_err(|_| anyhow::anyhow!("Not a valid basis file"))?;
+        let (format, texture_format) = match target(supported, true) {
+            Some((format, _, texture_format)) => (ImageFormat::Compressed(format), texture_format),
+            None => (ImageFormat::Rgba8, TranscoderTextureFormat::RGBA32),
+        };
+        let level_count = match format {
+            ImageFormat::Rgba8 => 1,
+            _ => transcoder.image_level_count(data, 0),
+        };
+        let description = transcoder
+            .image_level_description(data, 0, 0)
+            .ok_or_else(|| anyhow::anyhow!("Basis file has no images"))?;
+        let mut levels = Vec::new();
+        for level in 0..level_count {
+            levels.push(
+                transcoder
+                    .transcode_image_level(
+                        data,
+                        texture_format,
+                        TranscodeParameters {
+                            image_index: 0,
+                            level_index: level,
+                            decode_flags: None,
+                            output_row_pitch_in_blocks_or_pixels: None,
+                            output_rows_in_pixels: None,
+                        },
+                    )
+                    .map_err(|e| anyhow::anyhow!("Failed to transcode basis file: {e:?}"))?,
+            );
+        }
+        transcoder.end_transcoding();
+        Ok(CompressedImage {
+            size: vec2(
+                description.original_width as usize,
+                description.original_height as usize,
+            ),
+            format,
+            levels,
+        })
+    }
+}
+
+// TODO: transcode on the web with the basis_universal JS/wasm transcoder
+#[cfg(not(all(feature = "basis", not(target_arch = "wasm32"))))]
+mod basis {
+    use super::*;
+
+    const UNAVAILABLE: &str = if cfg!(target_arch = "wasm32") {
+        "Basis transcoding is not available on the web"
+    } else {
+        "Basis transcoding requires the `basis` feature of geng-asset"
+    };
+
+    pub fn transcode_uastc(
+        _image: CompressedImage,
+        _has_alpha: bool,
+        _supported: &[CompressedFormat],
+    ) -> anyhow::Result<CompressedImage> {
+        anyhow::bail!(UNAVAILABLE)
+    }
+
+    pub fn transcode_file(
+        _data: &[u8],
+        _supported: &[CompressedFormat],
+    ) -> anyhow::Result<CompressedImage> {
+        anyhow::bail!(UNAVAILABLE)
+    }
+}
+
+pub(crate) fn load_texture(manager: &Manager, path: &Path) -> Future<ugli::Texture> {
+    let manager = manager.clone();
+    let path = path.to_owned();
+    async move {
+        let data = manager.load_bytes(&path).await?;
+        let supported = manager.ugli().compressed_formats().to_vec();
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let image = platform::background(move || match extension.as_str() {
+            "basis" => basis::transcode_file(&data, &supported),
+            "dds" => CompressedImage::parse_dds(&data),
+            _ => CompressedImage::parse_ktx2(&data)?.transcode(&supported),
+        })
+        .await
+        .map_err(|e| e.context(format!("Failed to load {path:?}")))?;
+        image.upload(manager.ugli())
+    }
+    .boxed_local()
+}
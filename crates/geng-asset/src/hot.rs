@@ -5,11 +5,13 @@ pub struct Hot<T: Load> {
     manager: Manager,
     path: PathBuf,
     options: T::Options,
-    need_update: Arc<std::sync::atomic::AtomicBool>,
-    update: RefCell<Option<Future<T>>>,
+    /// Paths reported by the watcher since the last reload
+    changed: Arc<std::sync::Mutex<Vec<PathBuf>>>,
+    /// Reload in progress, with the paths it read
+    #[allow(clippy::type_complexity)]
+    update: RefCell<Option<Future<(T, Vec<PathBuf>)>>>,
     #[cfg(not(target_arch = "wasm32"))]
-    #[allow(dead_code)] // This is here for delaying the drop of the watcher
-    watcher: Option<notify::RecommendedWatcher>,
+    watcher: RefCell<Option<notify::RecommendedWatcher>>,
 }
 
 pub type Ref<'a, T> = std::cell::Ref<'a, T>;
@@ -24,65 +26,246 @@ impl<T: Load> Hot<T> {
                 ) {
                     *update = None;
                     match result {
-                        Ok(new) => *current = new,
+                        Ok((new, _paths)) => {
+                            *current = new;
+                            // Includes and nested assets could have changed
+                            #[cfg(not(target_arch = "wasm32"))]
+                            {
+                                *self.watcher.borrow_mut() = self.watch(&_paths);
+                            }
+                        }
                         Err(e) => log::error!("{e}"),
                     }
-                    self.need_update
-                        .store(false, std::sync::atomic::Ordering::SeqCst);
                 }
-            } else if self.need_update.load(std::sync::atomic::Ordering::SeqCst) {
-                *update = Some(
-                    self.manager
-                        .load_with(&self.path, &self.options)
-                        .boxed_local(),
-                )
+            } else {
+                let changed = std::mem::take(&mut *self.changed.lock().unwrap());
+                if !changed.is_empty() {
+                    // Cached dependencies would not be reloaded otherwise
+                    for path in changed {
+                        self.manager.evict(path);
+                    }
+                    *update = Some(self.manager.load_tracked(&self.path, &self.options))
+                }
             }
         }
         self.current.borrow()
     }
 }
 
+impl<T: Load> Hot<T> {
+    /// Watch the asset path and every path read while loading it
+    #[cfg(not(target_arch = "wasm32"))]
+    fn watch(&self, paths: &[PathBuf]) -> Option<notify::RecommendedWatcher> {
+        let changed = self.changed.clone();
+        watch(
+            &self.manager,
+            std::iter::once(self.path.as_path()).chain(paths.iter().map(PathBuf::as_path)),
+            move |event| {
+                if event.kind.is_modify() {
+                    changed.lock().unwrap().extend(event.paths);
+                }
+            },
+        )
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn watch<'a>(
+    manager: &Manager,
+    paths: impl IntoIterator<Item = &'a Path>,
+    on_event: impl Fn(notify::Event) + Send + 'static,
+) -> Option<notify::RecommendedWatcher> {
+    if !manager.hot_reload_enabled() {
+        return None;
+    }
+    use notify::Watcher;
+    let watcher =
+        notify::recommended_watcher(move |result: notify::Result<notify::Event>| match result {
+            Ok(event) => on_event(event),
+            Err(e) => log::error!("File watcher error: {e}"),
+        });
+    let mut watcher = match watcher {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            log::warn!("Failed to create file watcher: {e}");
+            return None;
+        }
+    };
+    let mut watched = std::collections::HashSet::new();
+    for path in paths {
+        if !watched.insert(path) {
+            continue;
+        }
+        match watcher.watch(path, notify::RecursiveMode::Recursive) {
+            Ok(()) => log::info!("watching {path:?}"),
+            Err(e) => log::warn!("Failed to watch {path:?}: {e}"),
+        }
+    }
+    Some(watcher)
+}
+
 impl<T: Load> Load for Hot<T> {
     type Options = T::Options;
     fn load(manager: &Manager, path: &Path, options: &Self::Options) -> Future<Self> {
         let manager = manager.clone();
         let path = path.to_owned();
         let options = options.clone();
-        let need_update = Arc::new(std::sync::atomic::AtomicBool::new(false));
-        #[cfg(not(target_arch = "wasm32"))]
-        let watcher = if manager.hot_reload_enabled() {
-            use notify::Watcher;
-            let need_update = need_update.clone();
-            let mut watcher =
-                notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
-                    let event = result.unwrap();
-                    if event.kind.is_modify() {
-                        need_update.store(true, std::sync::atomic::Ordering::SeqCst);
-                    }
-                })
-                .unwrap();
-            watcher
-                .watch(&path, notify::RecursiveMode::Recursive)
-                .unwrap();
-            log::info!("watching {path:?}");
-            Some(watcher)
-        } else {
-            None
-        };
         async move {
-            let initial = manager.load_with(&path, &options).await?;
-            Ok(Self {
-                need_update,
+            let (initial, _paths) = manager.load_tracked(&path, &options).await?;
+            let hot = Self {
+                #[cfg(not(target_arch = "wasm32"))]
+                watcher: RefCell::new(None),
+                changed: Default::default(),
                 options,
                 manager: manager.clone(),
                 path: path.to_owned(),
                 current: RefCell::new(initial),
                 update: RefCell::new(None),
-                #[cfg(not(target_arch = "wasm32"))]
-                watcher,
-            })
+            };
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                *hot.watcher.borrow_mut() = hot.watch(&_paths);
+            }
+            Ok(hot)
         }
         .boxed_local()
     }
     const DEFAULT_EXT: Option<&'static str> = T::DEFAULT_EXT;
 }
+
+type FieldUpdate = Future<(Box<dyn std::any::Any>, Vec<PathBuf>)>;
+
+/// Hot reloadable derived collection, only reloads fields that read changed files.
+///
+/// Changes to `_list.json` and other files read while loading a field
+/// reload that field, so adding items to a listed directory works too
+pub struct HotCollection<T: Reload> {
+    current: RefCell<T>,
+    manager: Manager,
+    path: PathBuf,
+    /// Paths read by every field
+    dependencies: RefCell<Vec<Vec<PathBuf>>>,
+    changed: Arc<std::sync::Mutex<Vec<PathBuf>>>,
+    updates: RefCell<Vec<(usize, FieldUpdate)>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    watcher: RefCell<Option<notify::RecommendedWatcher>>,
+}
+
+impl<T: Reload> HotCollection<T> {
+    pub fn get(&self) -> Ref<T> {
+        if let Ok(mut current) = self.current.try_borrow_mut() {
+            let mut updates = self.updates.borrow_mut();
+            let changed = std::mem::take(&mut *self.changed.lock().unwrap());
+            if !changed.is_empty() {
+                for path in &changed {
+                    self.manager.evict(path);
+                }
+                let dependencies = self.dependencies.borrow();
+                for (field, paths) in dependencies.iter().enumerate() {
+                    let affected = changed
+                        .iter()
+                        .any(|changed| paths.iter().any(|path| changed.starts_with(path)));
+                    if affected && !updates.iter().any(|(index, _)| *index == field) {
+                        log::info!("Reloading {}", T::FIELDS[field]);
+                        updates.push((field, load_field::<T>(&self.manager, &self.path, field)));
+                    }
+                }
+            }
+            let mut reloaded = false;
+            updates.retain_mut(|(field, future)| {
+                let std::task::Poll::Ready(result) = future.as_mut().poll(
+                    &mut std::task::Context::from_waker(futures::task::noop_waker_ref()),
+                ) else {
+                    return true;
+                };
+                match result.and_then(|(value, paths)| {
+                    current.set_field(*field, value)?;
+                    Ok(paths)
+                }) {
+                    Ok(paths) => {
+                        self.dependencies.borrow_mut()[*field] = paths;
+                        reloaded = true;
+                    }
+                    Err(e) => log::error!("{e:?}"),
+                }
+                false
+            });
+            if reloaded {
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    *self.watcher.borrow_mut() = self.watch();
+                }
+            }
+        }
+        self.current.borrow()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn watch(&self) -> Option<notify::RecommendedWatcher> {
+        let dependencies = self.dependencies.borrow();
+        let changed = self.changed.clone();
+        watch(
+            &self.manager,
+            dependencies.iter().flatten().map(PathBuf::as_path),
+            move |event| {
+                if event.kind.is_modify() || event.kind.is_create() || event.kind.is_remove() {
+                    changed
+                        .lock()
+                        .unwrap()
+                        .extend(event.paths.iter().map(|path| canonicalize(path)));
+                }
+            },
+        )
+    }
+}
+
+fn load_field<T: Reload>(manager: &Manager, path: &Path, field: usize) -> FieldUpdate {
+    manager
+        .track(|manager| T::load_field(manager, path, field))
+        .map_ok(|(value, paths)| {
+            let paths = paths.iter().map(|path| canonicalize(path)).collect();
+            (value, paths)
+        })
+        .boxed_local()
+}
+
+/// Absolute path if possible, so that watcher events can be matched with loaded paths
+pub(crate) fn canonicalize(path: &Path) -> PathBuf {
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Ok(path) = std::fs::canonicalize(path) {
+        return path;
+    }
+    path.to_owned()
+}
+
+impl<T: Reload> Load for HotCollection<T> {
+    type Options = ();
+    fn load(manager: &Manager, path: &Path, _options: &Self::Options) -> Future<Self> {
+        let manager = manager.clone();
+        let path = path.to_owned();
+        async move {
+            let fields = futures::future::try_join_all(
+                (0..T::FIELDS.len()).map(|field| load_field::<T>(&manager, &path, field)),
+            )
+            .await?;
+            let (values, dependencies) = fields.into_iter().unzip();
+            let hot = Self {
+                current: RefCell::new(T::from_fields(values)?),
+                manager,
+                path,
+                dependencies: RefCell::new(dependencies),
+                changed: Default::default(),
+                updates: RefCell::new(Vec::new()),
+                #[cfg(not(target_arch = "wasm32"))]
+                watcher: RefCell::new(None),
+            };
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                *hot.watcher.borrow_mut() = hot.watch();
+            }
+            Ok(hot)
+        }
+        .boxed_local()
+    }
+    const DEFAULT_EXT: Option<&'static str> = None;
+}
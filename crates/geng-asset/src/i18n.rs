@@ -0,0 +1,21 @@
+use super::*;
+
+/// Loads a Fluent (`.ftl`) or TOML (`.toml`) translation table
+impl Load for geng_i18n::Translations {
+    type Options = ();
+    fn load(manager: &Manager, path: &Path, _options: &Self::Options) -> Future<Self> {
+        let manager = manager.clone();
+        let path = path.to_owned();
+        async move {
+            let source = manager.load_string(&path).await?;
+            let extension = path.extension().and_then(|ext| ext.to_str());
+            match extension.map(str::to_ascii_lowercase).as_deref() {
+                Some("toml") => geng_i18n::Translations::from_toml(&source),
+                _ => geng_i18n::Translations::from_fluent(&source),
+            }
+            .map_err(|e| e.context(format!("Failed to load translations {path:?}")))
+        }
+        .boxed_local()
+    }
+    const DEFAULT_EXT: Option<&'static str> = Some("ftl");
+}
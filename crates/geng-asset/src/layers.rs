@@ -0,0 +1,130 @@
+use super::*;
+
+impl Manager {
+    /// Set asset roots, the first one is the base game directory
+    /// and the rest are layers overriding it in increasing priority (DLC, mods).
+    ///
+    /// Files under the base root are read from the highest priority layer that has them.
+    /// Only packed files are visible in layers on the web
+    pub fn set_roots(&self, roots: impl IntoIterator<Item = impl AsRef<Path>>) {
+        *self.inner.roots.borrow_mut() = roots
+            .into_iter()
+            .map(|root| root.as_ref().to_owned())
+            .collect();
+    }
+
+    pub fn roots(&self) -> Vec<PathBuf> {
+        self.inner.roots.borrow().clone()
+    }
+
+    /// Path that is actually read for `path`, taking layers into account
+    pub fn resolve(&self, path: impl AsRef<Path>) -> PathBuf {
+        let path = path.as_ref();
+        if self.ignore_layers {
+            return path.to_owned();
+        }
+        let roots = self.inner.roots.borrow();
+        let Some((base, layers)) = roots.split_first() else {
+            return path.to_owned();
+        };
+        let Ok(relative) = path.strip_prefix(base) else {
+            return path.to_owned();
+        };
+        layers
+            .iter()
+            .rev()
+            .map(|root| root.join(relative))
+            .find(|path| self.layer_has(path))
+            .unwrap_or_else(|| path.to_owned())
+    }
+
+    /// Every version of `path` present in the base root and the layers, lowest priority first.
+    ///
+    /// Without roots this is just `path`
+    pub fn variants(&self, path: impl AsRef<Path>) -> Vec<PathBuf> {
+        self.variant_managers(path.as_ref(), |path| self.layer_has(path))
+            .into_iter()
+            .map(|(_, path)| path)
+            .collect()
+    }
+
+    /// Load every [variant](Manager::variants) of an asset, e.g. to merge data files from mods
+    pub fn load_variants<T: Load>(
+        &self,
+        path: impl AsRef<Path>,
+        options: &T::Options,
+    ) -> Future<Vec<T>> {
+        future::try_join_all(
+            self.variant_managers(path.as_ref(), |path| self.layer_has(path))
+                .into_iter()
+                .map(|(manager, path)| manager.load_with::<T>(path, options)),
+        )
+        .boxed_local()
+    }
+
+    /// Load a list of names (like `_list.json`) merged from all [variants](Manager::variants),
+    /// keeping the order and skipping duplicates.
+    ///
+    /// Missing list files are generated from the [manifest](Manager::load_manifest) if there is one
+    pub fn load_list(&self, path: impl AsRef<Path>) -> Future<Vec<String>> {
+        let path = path.as_ref().to_owned();
+        let exists = |path: &Path| self.layer_has(path) || self.manifest_lists(path);
+        let lists = future::try_join_all(
+            self.variant_managers(&path, exists)
+                .into_iter()
+                .map(|(manager, path)| manager.load_list_file(path)),
+        );
+        async move {
+            let lists = lists.await?;
+            if lists.is_empty() {
+                anyhow::bail!("{path:?} was not found in any asset root");
+            }
+            let mut result = Vec::new();
+            for item in lists.into_iter().flatten() {
+                if !result.contains(&item) {
+                    result.push(item);
+                }
+            }
+            Ok(result)
+        }
+        .boxed_local()
+    }
+
+    /// Variants with the managers to load them with,
+    /// the base version has to be loaded without resolving through layers
+    fn variant_managers(
+        &self,
+        path: &Path,
+        exists: impl Fn(&Path) -> bool,
+    ) -> Vec<(Manager, PathBuf)> {
+        let roots = self.inner.roots.borrow();
+        let relative = roots
+            .split_first()
+            .filter(|_| !self.ignore_layers)
+            .and_then(|(base, layers)| Some((path.strip_prefix(base).ok()?, layers)));
+        let Some((relative, layers)) = relative else {
+            return vec![(self.clone(), path.to_owned())];
+        };
+        let base = Manager {
+            ignore_layers: true,
+            ..self.clone()
+        };
+        // Loose files can not be checked for existence on the web
+        let base_exists = cfg!(target_arch = "wasm32") || exists(path);
+        base_exists
+            .then(|| (base, path.to_owned()))
+            .into_iter()
+            .chain(
+                layers
+                    .iter()
+                    .map(|root| root.join(relative))
+                    .filter(|path| exists(path))
+                    .map(|path| (self.clone(), path)),
+            )
+            .collect()
+    }
+
+    fn layer_has(&self, path: &Path) -> bool {
+        self.is_mounted(path) || path.is_file()
+    }
+}
@@ -1,7 +1,6 @@
 use batbox_file as file;
 use futures::prelude::*;
 use geng_shader as shader;
-use std::borrow::BorrowMut;
 use std::cell::RefCell;
 use std::future::Future as StdFuture;
 use std::path::{Path, PathBuf};
@@ -10,28 +9,61 @@ use std::rc::Rc;
 use std::sync::Arc;
 use ugli::Ugli;
 
+mod cache;
+pub mod compressed;
 pub mod hot;
+mod i18n;
+mod layers;
+mod manifest;
+pub mod pack;
 mod platform;
+mod queue;
+mod serde_format;
+mod sprite;
 
-pub use hot::Hot;
+pub use cache::{CacheStats, Handle};
+pub use compressed::{CompressedImage, ImageFormat};
+pub use hot::{Hot, HotCollection};
+pub use manifest::{Manifest, ManifestEntry, ManifestReport, MANIFEST_FILE};
+pub use pack::{Pack, PackBuilder};
+pub use queue::{LoadHandle, LoadStatus, DEFAULT_MAX_CONCURRENT_LOADS};
+pub use serde_format::SerdeFormat;
+pub use sprite::SpriteSheetOptions;
 
 pub use geng_asset_derive::*;
 
 pub type Future<T> = Pin<Box<dyn StdFuture<Output = anyhow::Result<T>>>>;
 
+/// Where spawned loads run, see [Manager::new_headless]
+enum Host {
+    Window(geng_window::Window),
+    Headless(Box<dyn futures::task::LocalSpawn>),
+}
+
 struct ManagerImpl {
     ugli: Ugli,
-    window: geng_window::Window,
+    host: Host,
+    /// `None` for [Manager::new_headless]
     #[cfg(feature = "audio")]
-    audio: geng_audio::Audio,
+    audio: Option<geng_audio::Audio>,
     shader_lib: shader::Library,
     hot_reload_enabled: bool,
     marked_paths: RefCell<Vec<PathBuf>>,
+    cache: RefCell<cache::Cache>,
+    progress: RefCell<LoadProgress>,
+    mounts: RefCell<Vec<(PathBuf, Rc<pack::Pack>)>>,
+    roots: RefCell<Vec<PathBuf>>,
+    manifests: RefCell<Vec<(PathBuf, Rc<Manifest>)>>,
+    queue: RefCell<queue::Queue>,
 }
 
 #[derive(Clone)]
 pub struct Manager {
     inner: Rc<ManagerImpl>,
+    /// Active [Manager::load_tracked] calls that this manager is used for
+    trackers: Vec<Rc<RefCell<Vec<PathBuf>>>>,
+    /// Read the base version of files, see [Manager::load_variants]
+    ignore_layers: bool,
 }
 
 impl Manager {
@@ -39,28 +71,94 @@ impl Manager {
         window: &geng_window::Window,
         #[cfg(feature = "audio")] audio: &geng_audio::Audio,
         hot_reload: bool,
+    ) -> Self {
+        Self::new_impl(
+            window.ugli(),
+            Host::Window(window.clone()),
+            #[cfg(feature = "audio")]
+            Some(audio.clone()),
+            hot_reload,
+        )
+    }
+    /// Manager not attached to a window, for tools and tests.
+    ///
+    /// Queued loads (see [Manager::load_handle]) are spawned on `spawner`,
+    /// sounds can not be loaded
+    pub fn new_headless(
+        ugli: &Ugli,
+        spawner: impl futures::task::LocalSpawn + 'static,
+        hot_reload: bool,
+    ) -> Self {
+        Self::new_impl(
+            ugli,
+            Host::Headless(Box::new(spawner)),
+            #[cfg(feature = "audio")]
+            None,
+            hot_reload,
+        )
+    }
+    fn new_impl(
+        ugli: &Ugli,
+        host: Host,
+        #[cfg(feature = "audio")] audio: Option<geng_audio::Audio>,
+        hot_reload: bool,
     ) -> Self {
         Self {
             inner: Rc::new(ManagerImpl {
-                window: window.clone(),
-                ugli: window.ugli().clone(),
+                ugli: ugli.clone(),
+                host,
                 #[cfg(feature = "audio")]
-                audio: audio.clone(),
-                shader_lib: shader::Library::new(window.ugli(), true, None),
+                audio,
+                shader_lib: shader::Library::new(ugli, true, None),
                 hot_reload_enabled: hot_reload,
                 marked_paths: Default::default(),
+                cache: Default::default(),
+                progress: RefCell::new(LoadProgress::new()),
+                mounts: Default::default(),
+                roots: Default::default(),
+                manifests: Default::default(),
+                queue: Default::default(),
             }),
+            trackers: Vec::new(),
+            ignore_layers: false,
         }
     }
     pub fn marked_paths(&self) -> Vec<PathBuf> {
         self.inner.marked_paths.borrow().clone()
     }
+    /// Wait for the next frame, or just let other tasks run for [Manager::new_headless]
     pub async fn yield_now(&self) {
-        self.inner.window.yield_now().await
+        match &self.inner.host {
+            Host::Window(window) => window.yield_now().await,
+            Host::Headless(_) => {
+                let mut yielded = false;
+                future::poll_fn(|cx| {
+                    if std::mem::replace(&mut yielded, true) {
+                        return std::task::Poll::Ready(());
+                    }
+                    cx.waker().wake_by_ref();
+                    std::task::Poll::Pending
+                })
+                .await
+            }
+        }
+    }
+    /// Run `future` in the background
+    fn spawn(&self, future: impl StdFuture<Output = ()> + 'static) {
+        match &self.inner.host {
+            Host::Window(window) => window.spawn(future).detach(),
+            Host::Headless(spawner) => spawner
+                .spawn_local_obj(future.boxed_local().into())
+                .expect("Failed to spawn load"),
+        }
     }
+    /// Panics for managers created with [Manager::new_headless]
     #[cfg(feature = "audio")]
     pub fn audio(&self) -> &geng_audio::Audio {
-        &self.inner.audio
+        self.inner
+            .audio
+            .as_ref()
+            .expect("Headless asset manager has no audio")
     }
     pub fn ugli(&self) -> &Ugli {
         &self.inner.ugli
@@ -71,35 +169,130 @@ impl Manager {
     pub fn hot_reload_enabled(&self) -> bool {
         self.inner.hot_reload_enabled
     }
+    /// Record that `path` was read, see [Manager::load_tracked]
     pub fn mark_path(&self, path: impl AsRef<Path>) {
         self.inner
             .marked_paths
             .borrow_mut()
             .push(path.as_ref().to_owned());
+        self.mark_tracked(path);
+    }
+    /// Only record `path` for active [Manager::load_tracked] calls,
+    /// used for cached assets that were already marked when loaded
+    fn mark_tracked(&self, path: impl AsRef<Path>) {
+        for tracker in &self.trackers {
+            tracker.borrow_mut().push(path.as_ref().to_owned());
+        }
+    }
+    /// Load asset, also returning every path marked while loading it,
+    /// including nested assets and shader includes
+    pub fn load_tracked<T: Load>(
+        &self,
+        path: impl AsRef<Path>,
+        options: &T::Options,
+    ) -> Future<(T, Vec<PathBuf>)> {
+        self.track(|manager| T::load(manager, path.as_ref(), options))
+    }
+    /// Run a loading future, also returning every path marked by it
+    pub fn track<T: 'static>(
+        &self,
+        f: impl FnOnce(&Manager) -> Future<T>,
+    ) -> Future<(T, Vec<PathBuf>)> {
+        let tracker = Rc::new(RefCell::new(Vec::new()));
+        let mut manager = self.clone();
+        manager.trackers.push(tracker.clone());
+        let future = f(&manager);
+        async move {
+            let asset = future.await?;
+            let mut paths = tracker.take();
+            paths.sort();
+            paths.dedup();
+            Ok((asset, paths))
+        }
+        .boxed_local()
     }
     pub fn load<T: Load>(&self, path: impl AsRef<Path>) -> Future<T> {
-        T::load(self, path.as_ref(), &Default::default())
+        self.load_with(path, &Default::default())
     }
+    /// Load asset with given options.
+    ///
+    /// `Rc<T>` and [Handle] assets are shared through the cache (see [Manager::load_cached]),
+    /// so every file is only read and decoded once while the asset is in use
     pub fn load_with<T: Load>(&self, path: impl AsRef<Path>, options: &T::Options) -> Future<T> {
-        T::load(self, path.as_ref(), options)
+        self.report_progress(T::load(self, path.as_ref(), options))
+    }
+    /// Progress of every load started through this manager so far,
+    /// use [LoadProgress::since] to get progress of a single loading stage
+    pub fn load_progress(&self) -> LoadProgress {
+        *self.inner.progress.borrow()
+    }
+    /// Count `future` in [Manager::load_progress] until it completes or is cancelled
+    fn report_progress<T: 'static>(&self, future: Future<T>) -> Future<T> {
+        struct Guard(Manager);
+        impl Drop for Guard {
+            fn drop(&mut self) {
+                self.0.inner.progress.borrow_mut().progress += 1;
+            }
+        }
+        self.inner.progress.borrow_mut().total += 1;
+        let guard = Guard(self.clone());
+        async move {
+            let _guard = guard;
+            future.await
+        }
+        .boxed_local()
     }
     pub fn load_string(&self, path: impl AsRef<Path>) -> Future<String> {
-        self.mark_path(path.as_ref());
+        let data = self.load_bytes(path.as_ref());
         let path = path.as_ref().to_owned();
-        file::load_string(path).boxed_local()
+        async move {
+            String::from_utf8(data.await?).map_err(|_| anyhow::anyhow!("{path:?} is not UTF-8"))
+        }
+        .boxed_local()
     }
+    /// Read file from the [mounted packs](Manager::mount) or the filesystem,
+    /// [resolving](Manager::resolve) it through asset layers
     pub fn load_bytes(&self, path: impl AsRef<Path>) -> Future<Vec<u8>> {
-        self.mark_path(path.as_ref());
-        let path = path.as_ref().to_owned();
-        file::load_bytes(path).boxed_local()
+        let path = self.resolve(path);
+        self.mark_path(&path);
+        let manager = self.clone();
+        async move {
+            let data = match manager.read_mounted(&path) {
+                Some(data) => data?,
+                None => file::load_bytes(manager.versioned_path(&path)).await?,
+            };
+            if let Some((manifest, relative)) = manager.manifest_for(&path) {
+                match manifest.verify(&relative, &data) {
+                    // Assets are expected to change while hot reloading
+                    Err(e) if manager.hot_reload_enabled() => log::warn!("{e}"),
+                    result => result?,
+                }
+            }
+            manager.inner.progress.borrow_mut().bytes += data.len();
+            Ok(data)
+        }
+        .boxed_local()
     }
+    /// Load data in a [SerdeFormat] detected from the extension
     pub fn load_serde<T: 'static + serde::de::DeserializeOwned>(
         &self,
         path: impl AsRef<Path>,
     ) -> Future<T> {
-        self.mark_path(path.as_ref());
+        self.load_serde_with(path, None)
+    }
+    /// Load data in the given [SerdeFormat], detected from the extension if `None`
+    pub fn load_serde_with<T: 'static + serde::de::DeserializeOwned>(
+        &self,
+        path: impl AsRef<Path>,
+        format: Option<SerdeFormat>,
+    ) -> Future<T> {
         let path = path.as_ref().to_owned();
-        file::load_detect(path).boxed_local()
+        let Some(format) = format.or_else(|| SerdeFormat::detect(&path)) else {
+            return future::ready(Err(anyhow::anyhow!("Can not detect format of {path:?}")))
+                .boxed_local();
+        };
+        let data = self.load_bytes(&path);
+        async move { format.deserialize(&path, &data.await?) }.boxed_local()
     }
     /// Load asset from given path with specified or default extension
     pub fn load_ext<T: Load>(
@@ -133,28 +326,61 @@ pub trait Load: Sized + 'static {
     type Options: Clone + Default;
     fn load(manager: &Manager, path: &Path, options: &Self::Options) -> Future<Self>;
     const DEFAULT_EXT: Option<&'static str>;
+    /// Approximate memory used by the asset including GPU resources, see [Manager::cache_stats]
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
 }
 
-impl<T: 'static> Load for Rc<T>
-where
-    T: Load,
-{
-    type Options = T::Options;
-    fn load(manager: &Manager, path: &Path, options: &Self::Options) -> Future<Self> {
-        let inner = T::load(manager, path, options);
-        async move { Ok(Rc::new(inner.await?)) }.boxed_local()
-    }
-    const DEFAULT_EXT: Option<&'static str> = T::DEFAULT_EXT;
+/// Field by field loading of derived collections, used by [HotCollection]
+/// to reload only the fields affected by changed files.
+///
+/// Implemented by `#[derive(Load)]`
+pub trait Reload: Load {
+    /// Field names in declaration order
+    const FIELDS: &'static [&'static str];
+    fn load_field(
+        manager: &Manager,
+        base_path: &Path,
+        field: usize,
+    ) -> Future<Box<dyn std::any::Any>>;
+    /// Assemble from values returned by [Reload::load_field] for every field
+    fn from_fields(fields: Vec<Box<dyn std::any::Any>>) -> anyhow::Result<Self>;
+    fn set_field(&mut self, field: usize, value: Box<dyn std::any::Any>) -> anyhow::Result<()>;
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ProgramOptions {
+    /// Inserted as `#define NAME VALUE` before the shader source,
+    /// e.g. `#[load(options(defines = r#"vec![("USE_FOG".to_owned(), "1".to_owned())]"#))]`
+    pub defines: Vec<(String, String)>,
 }
 
 impl Load for ugli::Program {
-    type Options = ();
-    fn load(manager: &Manager, path: &Path, _options: &Self::Options) -> Future<Self> {
-        let glsl: Future<String> = manager.load(path);
+    type Options = ProgramOptions;
+    fn load(manager: &Manager, path: &Path, options: &Self::Options) -> Future<Self> {
         let manager = manager.clone();
+        let path = path.to_owned();
+        let options = options.clone();
         async move {
-            let glsl: String = glsl.await?;
-            manager.shader_lib().compile(&glsl)
+            let mut files = std::collections::HashMap::new();
+            let mut queue = vec![path.clone()];
+            while let Some(path) = queue.pop() {
+                if files.contains_key(&path) {
+                    continue;
+                }
+                let glsl: String = manager.load_string(&path).await?;
+                queue.extend(shader::relative_includes(&path, &glsl)?);
+                files.insert(path, glsl);
+            }
+            let defines: Vec<(&str, &str)> = options
+                .defines
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str()))
+                .collect();
+            let mut program = manager.shader_lib().compile_file(&path, &files, &defines)?;
+            program.set_name(path.to_string_lossy());
+            Ok(program)
         }
         .boxed_local()
     }
@@ -182,6 +408,9 @@ impl Load for String {
         async move { manager.load_string(path).await }.boxed_local()
     }
     const DEFAULT_EXT: Option<&'static str> = Some("txt");
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>() + self.capacity()
+    }
 }
 
 impl Load for Vec<u8> {
@@ -192,6 +421,9 @@ impl Load for Vec<u8> {
         async move { manager.load_bytes(path).await }.boxed_local()
     }
     const DEFAULT_EXT: Option<&'static str> = None;
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>() + self.capacity()
+    }
 }
 
 impl Load for geng_font::Font {
@@ -209,10 +441,16 @@ impl Load for geng_font::Font {
     const DEFAULT_EXT: Option<&'static str> = Some("ttf");
 }
 
-#[derive(Debug)]
+/// Number of assets loaded by a [Manager], see [Manager::load_progress]
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
 pub struct LoadProgress {
+    /// Completed loads
     pub progress: usize,
+    /// Started loads, nested assets are counted separately
     pub total: usize,
+    /// Bytes of files read
+    pub bytes: usize,
 }
 
 impl LoadProgress {
@@ -220,8 +458,28 @@ impl LoadProgress {
         Self {
             progress: 0,
             total: 0,
+            bytes: 0,
+        }
+    }
+    /// Progress made after `start` was taken.
+    ///
+    /// Loads that were already running at `start` still count when they complete,
+    /// so `progress` is capped at `total`
+    pub fn since(&self, start: &Self) -> Self {
+        let total = self.total.saturating_sub(start.total);
+        Self {
+            progress: self.progress.saturating_sub(start.progress).min(total),
+            total,
+            bytes: self.bytes.saturating_sub(start.bytes),
         }
     }
+    /// Completed fraction of loads in `0..=1`, `0` if nothing was started
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        (self.progress as f64 / self.total as f64).min(1.0)
+    }
 }
 
 impl Default for LoadProgress {
@@ -231,7 +489,7 @@ impl Default for LoadProgress {
 }
 
 #[cfg(feature = "audio")]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SoundOptions {
     pub looped: bool,
 }
@@ -252,8 +510,19 @@ impl Load for geng_audio::Sound {
         let path = path.to_owned();
         let options = options.clone();
         Box::pin(async move {
-            manager.mark_path(&path);
-            let mut sound = manager.audio().load(path).await?;
+            if manager.inner.audio.is_none() {
+                anyhow::bail!("Can not load {path:?}, headless asset manager has no audio");
+            }
+            let data = manager.load_bytes(&path).await?;
+            // The browser decodes sounds off the main thread by itself
+            #[cfg(target_arch = "wasm32")]
+            let mut sound = manager.audio().decode(data).await?;
+            #[cfg(not(target_arch = "wasm32"))]
+            let mut sound = {
+                let audio = manager.audio().clone();
+                platform::background(move || futures::executor::block_on(audio.decode(data)))
+                    .await?
+            };
             sound.looped = options.looped;
             Ok(sound)
         })
@@ -261,7 +530,7 @@ impl Load for geng_audio::Sound {
     const DEFAULT_EXT: Option<&'static str> = Some("wav"); // TODO change to mp3 since wav doesnt work in safari?
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TextureOptions {
     pub filter: ugli::Filter,
     pub wrap_mode: ugli::WrapMode,
@@ -285,7 +554,17 @@ impl Load for ugli::Texture {
         let path = path.to_owned();
         let options = options.clone();
         async move {
-            let mut texture = platform::load_texture(&manager, &path, &options).await?;
+            let compressed = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| {
+                    compressed::COMPRESSED_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str())
+                });
+            let mut texture = if compressed {
+                compressed::load_texture(&manager, &path).await?
+            } else {
+                platform::load_texture(&manager, &path, &options).await?
+            };
             texture.set_filter(options.filter);
             texture.set_wrap_mode(options.wrap_mode);
             Ok(texture)
@@ -293,17 +572,25 @@ impl Load for ugli::Texture {
         .boxed_local()
     }
     const DEFAULT_EXT: Option<&'static str> = Some("png");
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>() + self.data_size()
+    }
 }
 
 impl Load for image::RgbaImage {
     type Options = ();
     fn load(manager: &Manager, path: &Path, _options: &Self::Options) -> Future<Self> {
-        manager
-            .load::<Vec<u8>>(path)
-            .map(|data| Ok(image::load_from_memory(&data?)?.to_rgba8()))
-            .boxed_local()
+        let data = manager.load::<Vec<u8>>(path);
+        async move {
+            let data = data.await?;
+            platform::background(move || platform::decode_image(&data, false)).await
+        }
+        .boxed_local()
     }
     const DEFAULT_EXT: Option<&'static str> = Some("png");
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>() + self.as_raw().capacity()
+    }
 }
 
 pub trait Optional {
@@ -0,0 +1,240 @@
+use super::*;
+
+use std::collections::BTreeMap;
+
+/// File name [Manager::load_manifest] looks for in the asset root
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ManifestEntry {
+    pub size: u64,
+    /// CRC32 of the contents as hex, catches corruption and stale caches
+    /// but is not meant to protect against tampering
+    pub hash: String,
+}
+
+impl ManifestEntry {
+    pub fn new(data: &[u8]) -> Self {
+        Self {
+            size: data.len() as u64,
+            hash: format!("{:08x}", crc32fast::hash(data)),
+        }
+    }
+}
+
+/// Size and hash of every file in an asset directory.
+///
+/// Generated with [Manifest::generate] or the `geng-manifest` binary,
+/// used by [Manager::load_manifest]
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Manifest {
+    /// Keyed by `/` separated path relative to the asset root
+    pub files: BTreeMap<String, ManifestEntry>,
+}
+
+/// Difference between a [Manifest] and the files on disk
+#[derive(Debug, Clone, Default)]
+pub struct ManifestReport {
+    /// In the manifest but not on disk
+    pub missing: Vec<String>,
+    /// On disk but not in the manifest
+    pub extra: Vec<String>,
+    /// Size or hash does not match the manifest
+    pub modified: Vec<String>,
+}
+
+impl ManifestReport {
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty() && self.modified.is_empty()
+    }
+}
+
+impl Manifest {
+    /// Hash every file in `dir` recursively, skipping the manifest itself
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn generate(dir: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let mut files = BTreeMap::new();
+        pack::walk_dir(dir.as_ref(), &mut |path, file| {
+            if path != MANIFEST_FILE {
+                files.insert(path, ManifestEntry::new(&std::fs::read(file)?));
+            }
+            Ok(())
+        })?;
+        Ok(Self { files })
+    }
+
+    /// Compare with the files currently in `dir`
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn check(&self, dir: impl AsRef<Path>) -> anyhow::Result<ManifestReport> {
+        let actual = Self::generate(dir)?;
+        let mut report = ManifestReport::default();
+        for (path, entry) in &self.files {
+            match actual.files.get(path) {
+                None => report.missing.push(path.clone()),
+                Some(actual) if actual != entry => report.modified.push(path.clone()),
+                Some(_) => {}
+            }
+        }
+        report.extra = actual
+            .files
+            .keys()
+            .filter(|path| !self.files.contains_key(*path))
+            .cloned()
+            .collect();
+        Ok(report)
+    }
+
+    pub fn get(&self, path: &str) -> Option<&ManifestEntry> {
+        self.files.get(path)
+    }
+
+    /// Check that `data` read from `path` matches the manifest,
+    /// files not in the manifest are not checked
+    pub fn verify(&self, path: &str, data: &[u8]) -> anyhow::Result<()> {
+        let Some(expected) = self.files.get(path) else {
+            return Ok(());
+        };
+        let actual = ManifestEntry::new(data);
+        if actual != *expected {
+            anyhow::bail!(
+                "Integrity check failed for {path:?}: expected {} bytes with hash {}, got {} bytes with hash {}",
+                expected.size,
+                expected.hash,
+                actual.size,
+                actual.hash,
+            );
+        }
+        Ok(())
+    }
+
+    /// Names of the items in directory `dir` in the same form as in `_list.json`:
+    /// file names without extension and subdirectory names,
+    /// skipping the ones starting with `_`
+    pub fn list(&self, dir: &str) -> Vec<String> {
+        let prefix = match dir {
+            "" => String::new(),
+            dir => format!("{}/", dir.trim_end_matches('/')),
+        };
+        let mut result: Vec<String> = Vec::new();
+        for path in self.files.keys() {
+            let Some(relative) = path.strip_prefix(&prefix) else {
+                continue;
+            };
+            let name = match relative.split_once('/') {
+                Some((dir, _)) => dir,
+                None => relative.rsplit_once('.').map_or(relative, |(stem, _)| stem),
+            };
+            if name.starts_with('_') || relative == MANIFEST_FILE {
+                continue;
+            }
+            result.push(name.to_owned());
+        }
+        result.sort();
+        result.dedup();
+        result
+    }
+
+    /// Hash of the whole manifest, changes whenever any asset does
+    pub fn version(&self) -> String {
+        let mut hasher = crc32fast::Hasher::new();
+        for (path, entry) in &self.files {
+            hasher.update(path.as_bytes());
+            hasher.update(&entry.size.to_le_bytes());
+            hasher.update(entry.hash.as_bytes());
+        }
+        format!("{:08x}", hasher.finalize())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn write(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+impl Manager {
+    /// Use `manifest` for files under `root`:
+    /// loaded files are verified against it, `listed_in` lists can be generated from it,
+    /// and on the web file hashes are added to urls so that stale cached files are never used
+    pub fn set_manifest(&self, root: impl AsRef<Path>, manifest: Manifest) {
+        self.inner
+            .manifests
+            .borrow_mut()
+            .push((root.as_ref().to_owned(), Rc::new(manifest)));
+    }
+
+    /// Load [MANIFEST_FILE] from `root` and [use it](Manager::set_manifest).
+    ///
+    /// On native missing, extra and modified files are reported as warnings
+    pub fn load_manifest(&self, root: impl AsRef<Path>) -> Future<()> {
+        let manager = self.clone();
+        let root = root.as_ref().to_owned();
+        async move {
+            let path = root.join(MANIFEST_FILE);
+            let data = match manager.read_mounted(&path) {
+                Some(data) => data?,
+                None => file::load_bytes(&path).await?,
+            };
+            let manifest: Manifest = serde_json::from_slice(&data)
+                .map_err(|e| anyhow::anyhow!("Failed to parse {path:?}: {e}"))?;
+            #[cfg(not(target_arch = "wasm32"))]
+            if root.is_dir() {
+                let report = manifest.check(&root)?;
+                for path in &report.missing {
+                    log::warn!("Asset {path:?} is in the manifest but missing");
+                }
+                for path in &report.extra {
+                    log::warn!("Asset {path:?} is not in the manifest");
+                }
+                for path in &report.modified {
+                    log::warn!("Asset {path:?} does not match the manifest");
+                }
+            }
+            manager.set_manifest(root, manifest);
+            Ok(())
+        }
+        .boxed_local()
+    }
+
+    /// Manifest covering `path` and the path relative to its root
+    pub(crate) fn manifest_for(&self, path: &Path) -> Option<(Rc<Manifest>, String)> {
+        let manifests = self.inner.manifests.borrow();
+        manifests
+            .iter()
+            .rev()
+            .find_map(|(root, manifest)| Some((manifest.clone(), pack::pack_path(root, path)?)))
+    }
+
+    /// Whether a list for `path` can be generated from a manifest, see [Manifest::list]
+    pub(crate) fn manifest_lists(&self, path: &Path) -> bool {
+        self.manifest_for(path)
+            .is_some_and(|(manifest, relative)| !manifest.list(list_dir(&relative)).is_empty())
+    }
+
+    /// Load a `_list.json` style list or generate it from the manifest if there is no such file
+    pub(crate) fn load_list_file(&self, path: PathBuf) -> Future<Vec<String>> {
+        if let Some((manifest, relative)) = self.manifest_for(&path) {
+            if manifest.get(&relative).is_none() {
+                return future::ready(Ok(manifest.list(list_dir(&relative)))).boxed_local();
+            }
+        }
+        self.load_serde(path)
+    }
+
+    /// Url to fetch `path` from, with the hash from the manifest added on the web
+    pub(crate) fn versioned_path(&self, path: &Path) -> PathBuf {
+        if cfg!(target_arch = "wasm32") {
+            if let Some((manifest, relative)) = self.manifest_for(path) {
+                if let Some(entry) = manifest.get(&relative) {
+                    return PathBuf::from(format!("{}?v={}", path.display(), entry.hash));
+                }
+            }
+        }
+        path.to_owned()
+    }
+}
+
+/// Directory containing the list file at relative `path`
+fn list_dir(path: &str) -> &str {
+    path.rsplit_once('/').map_or("", |(dir, _)| dir)
+}
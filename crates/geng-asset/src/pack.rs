@@ -0,0 +1,274 @@
+//! Packed asset archives.
+//!
+//! A pack is a single file containing many assets, which is much faster to fetch on the web
+//! than hundreds of loose files. Packs are created with [PackBuilder] or the `geng-pack` binary
+//! and mounted with [Manager::mount]/[Manager::mount_pack].
+//!
+//! Layout: `GENGPACK` magic, format version (`u32` LE), index length (`u64` LE),
+//! bincode serialized index, then file blobs, each deflate compressed unless that did not help.
+use super::*;
+
+use std::collections::HashMap;
+use std::io::{Read, Write};
+
+const MAGIC: &[u8; 8] = b"GENGPACK";
+const VERSION: u32 = 1;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct IndexEntry {
+    /// `/` separated path relative to the packed directory
+    path: String,
+    /// Offset from the end of the index
+    offset: u64,
+    /// Size of the blob stored in the pack
+    stored_size: u64,
+    /// Size after decompression
+    size: u64,
+    compressed: bool,
+}
+
+/// Asset archive loaded into memory
+pub struct Pack {
+    entries: HashMap<String, IndexEntry>,
+    data: Vec<u8>,
+    /// Start of the blobs in `data`
+    blobs: usize,
+}
+
+impl Pack {
+    pub fn from_bytes(data: Vec<u8>) -> anyhow::Result<Self> {
+        let header_size = MAGIC.len() + 4 + 8;
+        if data.len() < header_size || &data[..MAGIC.len()] != MAGIC {
+            anyhow::bail!("Not a geng pack");
+        }
+        let version = u32::from_le_bytes(data[8..12].try_into().unwrap());
+        if version != VERSION {
+            anyhow::bail!("Unsupported pack version {version}, expected {VERSION}");
+        }
+        let index_size = u64::from_le_bytes(data[12..20].try_into().unwrap());
+        let blobs = usize::try_from(index_size)
+            .ok()
+            .and_then(|index_size| header_size.checked_add(index_size))
+            .filter(|&blobs| blobs <= data.len())
+            .ok_or_else(|| anyhow::anyhow!("Pack index is truncated"))?;
+        let index: Vec<IndexEntry> = bincode::deserialize(&data[header_size..blobs])?;
+        for entry in &index {
+            let end = (blobs as u64)
+                .checked_add(entry.offset)
+                .and_then(|start| start.checked_add(entry.stored_size));
+            if end.is_none_or(|end| end > data.len() as u64) {
+                anyhow::bail!("Pack is truncated, {:?} is out of bounds", entry.path);
+            }
+        }
+        Ok(Self {
+            entries: index
+                .into_iter()
+                .map(|entry| (entry.path.clone(), entry))
+                .collect(),
+            data,
+            blobs,
+        })
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Self::from_bytes(std::fs::read(path)?)
+    }
+
+    /// `/` separated paths of all packed files
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    pub fn contains(&self, path: &str) -> bool {
+        self.entries.contains_key(path)
+    }
+
+    /// Decompressed contents of a file, `None` if it is not in the pack
+    pub fn read(&self, path: &str) -> Option<anyhow::Result<Vec<u8>>> {
+        let entry = self.entries.get(path)?;
+        let start = self.blobs + entry.offset as usize;
+        let blob = &self.data[start..start + entry.stored_size as usize];
+        if !entry.compressed {
+            return Some(Ok(blob.to_vec()));
+        }
+        // The size comes from the file, don't trust it for huge allocations
+        let mut data = Vec::with_capacity(entry.size.min(blob.len() as u64 * 16) as usize);
+        // Stop one byte past the expected size so a crafted blob can't exhaust memory
+        let result = flate2::read::DeflateDecoder::new(blob)
+            .take(entry.size.saturating_add(1))
+            .read_to_end(&mut data)
+            .map_err(|e| anyhow::anyhow!("Failed to decompress {path:?} from pack: {e}"));
+        Some(result.and_then(|_| {
+            if data.len() as u64 != entry.size {
+                anyhow::bail!(
+                    "Size of {path:?} in pack does not match the index, expected {} bytes",
+                    entry.size,
+                );
+            }
+            Ok(data)
+        }))
+    }
+}
+
+/// Creates [Pack] files
+#[derive(Default)]
+pub struct PackBuilder {
+    files: Vec<(String, Vec<u8>)>,
+}
+
+impl PackBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file, `path` is `/` separated and relative to the mount root
+    pub fn add(&mut self, path: impl Into<String>, data: Vec<u8>) {
+        self.files.push((path.into(), data));
+    }
+
+    /// Add every file in `dir` recursively
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn add_dir(&mut self, dir: impl AsRef<Path>) -> anyhow::Result<()> {
+        walk_dir(dir.as_ref(), &mut |path, file| {
+            self.add(path, std::fs::read(file)?);
+            Ok(())
+        })
+    }
+
+    pub fn write(&self, mut writer: impl Write) -> anyhow::Result<()> {
+        let mut index = Vec::new();
+        let mut blobs = Vec::new();
+        for (path, data) in &self.files {
+            let mut encoder =
+                flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::best());
+            encoder.write_all(data)?;
+            let compressed = encoder.finish()?;
+            // Images and sounds are usually compressed already
+            let (stored, compressed) = if compressed.len() < data.len() {
+                (compressed.as_slice(), true)
+            } else {
+                (data.as_slice(), false)
+            };
+            index.push(IndexEntry {
+                path: path.clone(),
+                offset: blobs.len() as u64,
+                stored_size: stored.len() as u64,
+                size: data.len() as u64,
+                compressed,
+            });
+            blobs.extend_from_slice(stored);
+        }
+        let index = bincode::serialize(&index)?;
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&(index.len() as u64).to_le_bytes())?;
+        writer.write_all(&index)?;
+        writer.write_all(&blobs)?;
+        Ok(())
+    }
+
+    pub fn to_bytes(&self) -> anyhow::Result<Vec<u8>> {
+        let mut result = Vec::new();
+        self.write(&mut result)?;
+        Ok(result)
+    }
+}
+
+/// Call `f` with the `/` separated relative path and the full path of every file in `dir`
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn walk_dir(
+    dir: &Path,
+    f: &mut impl FnMut(String, &Path) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    fn visit(
+        dir: &Path,
+        prefix: &str,
+        f: &mut impl FnMut(String, &Path) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
+        let mut entries = std::fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+        entries.sort_by_key(|entry| entry.file_name());
+        for entry in entries {
+            let name = entry.file_name();
+            let name = name
+                .to_str()
+                .ok_or_else(|| anyhow::anyhow!("Non UTF-8 file name {name:?}"))?;
+            let path = format!("{prefix}{name}");
+            if entry.file_type()?.is_dir() {
+                visit(&entry.path(), &format!("{path}/"), f)?;
+            } else {
+                f(path, &entry.path())?;
+            }
+        }
+        Ok(())
+    }
+    visit(dir, "", f)
+}
+
+/// Pack every file in `dir` into `output`, can be used from build scripts
+#[cfg(not(target_arch = "wasm32"))]
+pub fn pack_dir(dir: impl AsRef<Path>, output: impl AsRef<Path>) -> anyhow::Result<()> {
+    let mut builder = PackBuilder::new();
+    builder.add_dir(dir)?;
+    builder.write(std::io::BufWriter::new(std::fs::File::create(output)?))
+}
+
+/// Path relative to `root` as a `/` separated pack path
+pub(crate) fn pack_path(root: &Path, path: &Path) -> Option<String> {
+    let mut components = Vec::new();
+    for component in path.strip_prefix(root).ok()?.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                components.pop()?;
+            }
+            std::path::Component::Normal(name) => components.push(name.to_str()?),
+            _ => return None,
+        }
+    }
+    Some(components.join("/"))
+}
+
+impl Manager {
+    /// Mount a pack so that files under `root` are read from it,
+    /// files missing from the pack are still read from the filesystem.
+    ///
+    /// Packs mounted later take priority
+    pub fn mount(&self, root: impl AsRef<Path>, pack: Pack) {
+        self.inner
+            .mounts
+            .borrow_mut()
+            .push((root.as_ref().to_owned(), Rc::new(pack)));
+    }
+
+    /// Load a pack file and [mount](Manager::mount) it at `root`
+    pub fn mount_pack(&self, root: impl AsRef<Path>, pack_path: impl AsRef<Path>) -> Future<()> {
+        let manager = self.clone();
+        let root = root.as_ref().to_owned();
+        let pack_path = pack_path.as_ref().to_owned();
+        async move {
+            let data = file::load_bytes(&pack_path).await?;
+            let pack = Pack::from_bytes(data)
+                .map_err(|e| e.context(format!("Failed to load pack {pack_path:?}")))?;
+            manager.mount(root, pack);
+            Ok(())
+        }
+        .boxed_local()
+    }
+
+    pub(crate) fn is_mounted(&self, path: &Path) -> bool {
+        let mounts = self.inner.mounts.borrow();
+        mounts
+            .iter()
+            .any(|(root, pack)| pack_path(root, path).is_some_and(|path| pack.contains(&path)))
+    }
+
+    /// Contents of `path` from the mounted packs, `None` if no pack contains it
+    pub(crate) fn read_mounted(&self, path: &Path) -> Option<anyhow::Result<Vec<u8>>> {
+        let mounts = self.inner.mounts.borrow();
+        mounts.iter().rev().find_map(|(root, pack)| {
+            let path = pack_path(root, path)?;
+            pack.read(&path)
+        })
+    }
+}
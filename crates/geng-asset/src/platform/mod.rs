@@ -9,3 +9,20 @@ mod web;
 pub use native::*;
 #[cfg(target_arch = "wasm32")]
 pub use web::*;
+
+/// Decode an image to RGBA, runs in the [background]
+pub fn decode_image(data: &[u8], premultiply_alpha: bool) -> anyhow::Result<image::RgbaImage> {
+    let image = image::load_from_memory(data)?;
+    let mut image = match image {
+        image::DynamicImage::ImageRgba8(image) => image,
+        _ => image.to_rgba8(),
+    };
+    if premultiply_alpha {
+        for pixel in image.pixels_mut() {
+            use image::Pixel;
+            *pixel = pixel
+                .map_without_alpha(|x| (x as f32 * (pixel[3] as f32 / 0xff as f32)).round() as u8);
+        }
+    }
+    Ok(image)
+}
@@ -1,5 +1,56 @@
 use super::*;
 
+use std::sync::mpsc;
+use std::sync::{Mutex, OnceLock};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Threads decoding assets so that only GPU uploads happen on the main thread
+fn pool() -> &'static Mutex<mpsc::Sender<Job>> {
+    static POOL: OnceLock<Mutex<mpsc::Sender<Job>>> = OnceLock::new();
+    POOL.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let threads = std::thread::available_parallelism()
+            .map_or(1, |n| n.get().saturating_sub(1))
+            .max(1);
+        for index in 0..threads {
+            let receiver = receiver.clone();
+            std::thread::Builder::new()
+                .name(format!("geng-asset-{index}"))
+                .spawn(move || loop {
+                    let job = receiver.lock().unwrap().recv();
+                    match job {
+                        Ok(job) => job(),
+                        Err(_) => break,
+                    }
+                })
+                .expect("Failed to spawn asset decoding thread");
+        }
+        Mutex::new(sender)
+    })
+}
+
+/// Run `f` on the asset decoding thread pool
+pub fn background<T: Send + 'static>(
+    f: impl FnOnce() -> anyhow::Result<T> + Send + 'static,
+) -> Future<T> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    let job: Job = Box::new(move || {
+        // Receiver gets an error if `f` panics, keeping the thread alive
+        if let Ok(result) = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)) {
+            let _ = sender.send(result);
+        }
+    });
+    pool().lock().unwrap().send(job).unwrap();
+    async move {
+        receiver
+            .await
+            .map_err(|_| anyhow::anyhow!("Asset decoding thread panicked"))?
+    }
+    .boxed_local()
+}
+
 pub fn load_texture(
     manager: &Manager,
     path: &std::path::Path,
@@ -10,22 +61,8 @@ pub fn load_texture(
     let options = options.clone();
     async move {
         log::debug!("Loading {:?}", path);
-        let image = image::load_from_memory(&manager.load_bytes(path).await?)?;
-        manager.yield_now().await;
-        let mut image = match image {
-            image::DynamicImage::ImageRgba8(image) => image,
-            _ => image.to_rgba8(),
-        };
-        manager.yield_now().await;
-        if options.premultiply_alpha {
-            for pixel in image.pixels_mut() {
-                use image::Pixel;
-                *pixel = pixel.map_without_alpha(|x| {
-                    (x as f32 * (pixel[3] as f32 / 0xff as f32)).round() as u8
-                });
-            }
-        }
-        manager.yield_now().await;
+        let data = manager.load_bytes(path).await?;
+        let image = background(move || decode_image(&data, options.premultiply_alpha)).await?;
         Ok(ugli::Texture::from_image_image(manager.ugli(), image))
     }
     .boxed_local()
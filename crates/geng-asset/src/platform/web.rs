@@ -1,47 +1,45 @@
 use super::*;
-use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// There are no threads on the web, so `f` runs right away.
+///
+/// Images and sounds are decoded by the browser off the main thread instead
+pub fn background<T: Send + 'static>(
+    f: impl FnOnce() -> anyhow::Result<T> + Send + 'static,
+) -> Future<T> {
+    future::ready(f()).boxed_local()
+}
 
 pub fn load_texture(
     manager: &Manager,
     path: &Path,
     options: &TextureOptions,
 ) -> Future<ugli::Texture> {
-    let (sender, receiver) = futures::channel::oneshot::channel();
-    let image = web_sys::HtmlImageElement::new().unwrap();
-    let path = Rc::new(path.to_owned());
+    let manager = manager.clone();
+    let path = path.to_owned();
     let options = options.clone();
-    let handler = {
-        let image = image.clone();
-        let ugli = manager.ugli().clone();
-        let path = path.clone();
-        move |success: bool| {
-            sender
-                .send(if success {
-                    Ok(ugli::Texture::from_html_image_element(
-                        &ugli,
-                        &image,
-                        options.premultiply_alpha,
-                    ))
-                } else {
-                    Err(anyhow::anyhow!("Failed to load image from {:?}", path))
-                })
-                .map_err(|_| ())
-                .unwrap();
-        }
-    };
-    #[wasm_bindgen(inline_js = r#"
-        export function setup_image(image, handler) {
-            image.onload = function() { handler(true); };
-            image.onerror = function() { handler(false); };
-        }
-        "#)]
-    extern "C" {
-        fn setup_image(image: &web_sys::HtmlImageElement, handler: wasm_bindgen::JsValue);
+    async move {
+        let data = manager.load_bytes(&path).await?;
+        let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(data.as_slice()));
+        let blob = web_sys::Blob::new_with_u8_array_sequence(&parts).unwrap();
+        let bitmap_options = web_sys::ImageBitmapOptions::new();
+        bitmap_options.set_image_orientation(web_sys::ImageOrientation::FlipY);
+        bitmap_options.set_premultiply_alpha(if options.premultiply_alpha {
+            web_sys::PremultiplyAlpha::Premultiply
+        } else {
+            web_sys::PremultiplyAlpha::None
+        });
+        let promise = web_sys::window()
+            .unwrap()
+            .create_image_bitmap_with_blob_and_image_bitmap_options(&blob, &bitmap_options)
+            .unwrap();
+        let bitmap: web_sys::ImageBitmap = wasm_bindgen_futures::JsFuture::from(promise)
+            .await
+            .map_err(|_| anyhow::anyhow!("Failed to decode image {path:?}"))?
+            .unchecked_into();
+        let texture = ugli::Texture::from_image_bitmap(manager.ugli(), &bitmap);
+        bitmap.close();
+        Ok(texture)
     }
-    setup_image(
-        &image,
-        wasm_bindgen::closure::Closure::once_into_js(handler),
-    );
-    image.set_src(path.to_str().unwrap());
-    Box::pin(async move { receiver.await? })
+    .boxed_local()
 }
@@ -0,0 +1,283 @@
+//! Prioritized loading with a bounded number of concurrent loads, see [Manager::load_handle]
+use super::*;
+
+use std::cell::Cell;
+use std::rc::Weak;
+use std::task::{Poll, Waker};
+
+/// Default for [Manager::set_max_concurrent_loads]
+pub const DEFAULT_MAX_CONCURRENT_LOADS: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadStatus {
+    /// Waiting for a free slot
+    Queued,
+    Loading,
+    Finished,
+    Cancelled,
+}
+
+pub(crate) struct Queue {
+    max_concurrent: usize,
+    running: usize,
+    next_order: u64,
+    jobs: Vec<Rc<dyn AnyJob>>,
+}
+
+impl Default for Queue {
+    fn default() -> Self {
+        Self {
+            max_concurrent: DEFAULT_MAX_CONCURRENT_LOADS,
+            running: 0,
+            next_order: 0,
+            jobs: Vec::new(),
+        }
+    }
+}
+
+trait AnyJob {
+    fn priority(&self) -> i32;
+    fn order(&self) -> u64;
+    fn is_queued(&self) -> bool;
+    fn start(self: Rc<Self>, manager: &Manager);
+}
+
+enum JobState<T> {
+    Queued,
+    Loading(future::AbortHandle),
+    /// `None` once the result was taken
+    Finished(Option<anyhow::Result<T>>),
+    Cancelled,
+}
+
+/// [Manager] that queued jobs refer to without keeping it alive,
+/// the queue itself is owned by the manager
+struct WeakManager {
+    inner: Weak<ManagerImpl>,
+    trackers: Vec<Rc<RefCell<Vec<PathBuf>>>>,
+    ignore_layers: bool,
+}
+
+impl WeakManager {
+    fn new(manager: &Manager) -> Self {
+        Self {
+            inner: Rc::downgrade(&manager.inner),
+            trackers: manager.trackers.clone(),
+            ignore_layers: manager.ignore_layers,
+        }
+    }
+
+    fn upgrade(&self) -> Option<Manager> {
+        Some(Manager {
+            inner: self.inner.upgrade()?,
+            trackers: self.trackers.clone(),
+            ignore_layers: self.ignore_layers,
+        })
+    }
+}
+
+struct Job<T: Load> {
+    manager: WeakManager,
+    path: PathBuf,
+    options: T::Options,
+    priority: Cell<i32>,
+    order: u64,
+    state: RefCell<JobState<T>>,
+    waker: RefCell<Option<Waker>>,
+}
+
+impl<T: Load> Job<T> {
+    fn status(&self) -> LoadStatus {
+        match *self.state.borrow() {
+            JobState::Queued => LoadStatus::Queued,
+            JobState::Loading(_) => LoadStatus::Loading,
+            JobState::Finished(_) => LoadStatus::Finished,
+            JobState::Cancelled => LoadStatus::Cancelled,
+        }
+    }
+
+    fn finish(&self, state: JobState<T>) {
+        let was_loading = matches!(
+            std::mem::replace(&mut *self.state.borrow_mut(), state),
+            JobState::Loading(_)
+        );
+        if let Some(waker) = self.waker.borrow_mut().take() {
+            waker.wake();
+        }
+        if was_loading {
+            if let Some(manager) = self.manager.upgrade() {
+                manager.inner.queue.borrow_mut().running -= 1;
+                manager.schedule();
+            }
+        }
+    }
+
+    fn cancel(&self) {
+        let mut state = self.state.borrow_mut();
+        match &*state {
+            JobState::Queued => *state = JobState::Cancelled,
+            JobState::Loading(abort) => {
+                abort.abort();
+                drop(state);
+                self.finish(JobState::Cancelled);
+            }
+            JobState::Finished(_) | JobState::Cancelled => {}
+        }
+    }
+}
+
+impl<T: Load> AnyJob for Job<T> {
+    fn priority(&self) -> i32 {
+        self.priority.get()
+    }
+    fn order(&self) -> u64 {
+        self.order
+    }
+    fn is_queued(&self) -> bool {
+        matches!(*self.state.borrow(), JobState::Queued)
+    }
+    fn start(self: Rc<Self>, manager: &Manager) {
+        let (future, abort) = future::abortable(manager.load_with::<T>(&self.path, &self.options));
+        *self.state.borrow_mut() = JobState::Loading(abort);
+        let job = self.clone();
+        manager.spawn(async move {
+            // Aborted loads are finished by LoadHandle::cancel
+            if let Ok(result) = future.await {
+                job.finish(JobState::Finished(Some(result)));
+            }
+        });
+    }
+}
+
+/// Handle to a load started with [Manager::load_handle].
+///
+/// Can be awaited or polled with [LoadHandle::take] every frame,
+/// dropping the handle cancels the load
+pub struct LoadHandle<T: Load> {
+    job: Rc<Job<T>>,
+}
+
+impl<T: Load> LoadHandle<T> {
+    pub fn status(&self) -> LoadStatus {
+        self.job.status()
+    }
+    pub fn is_finished(&self) -> bool {
+        self.status() == LoadStatus::Finished
+    }
+    pub fn priority(&self) -> i32 {
+        self.job.priority.get()
+    }
+    /// Change priority of a queued load, higher priority loads start first
+    pub fn set_priority(&self, priority: i32) {
+        self.job.priority.set(priority);
+    }
+    /// Stop loading, nested loads are dropped at their next await point
+    pub fn cancel(&self) {
+        self.job.cancel();
+    }
+    /// Take the result once the load has finished
+    pub fn take(&self) -> Option<anyhow::Result<T>> {
+        match &mut *self.job.state.borrow_mut() {
+            JobState::Finished(result) => result.take(),
+            _ => None,
+        }
+    }
+}
+
+impl<T: Load> StdFuture for LoadHandle<T> {
+    type Output = anyhow::Result<T>;
+    fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        let path = &self.job.path;
+        match &mut *self.job.state.borrow_mut() {
+            JobState::Finished(result) => {
+                Poll::Ready(result.take().unwrap_or_else(|| {
+                    Err(anyhow::anyhow!("Result of {path:?} was already taken"))
+                }))
+            }
+            JobState::Cancelled => {
+                Poll::Ready(Err(anyhow::anyhow!("Loading {path:?} was cancelled")))
+            }
+            JobState::Queued if self.job.manager.inner.strong_count() == 0 => Poll::Ready(Err(
+                anyhow::anyhow!("Manager was dropped before loading {path:?}"),
+            )),
+            JobState::Queued | JobState::Loading(_) => {
+                *self.job.waker.borrow_mut() = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T: Load> Drop for LoadHandle<T> {
+    fn drop(&mut self) {
+        self.job.cancel();
+    }
+}
+
+impl Manager {
+    /// Queue loading an asset, at most [Manager::max_concurrent_loads] handles are loading at once
+    /// and higher `priority` loads start first, in the order they were queued on ties.
+    ///
+    /// Nested assets are loaded as part of their handle and are not limited separately
+    pub fn load_handle<T: Load>(
+        &self,
+        path: impl AsRef<Path>,
+        options: &T::Options,
+        priority: i32,
+    ) -> LoadHandle<T> {
+        let order = {
+            let mut queue = self.inner.queue.borrow_mut();
+            queue.next_order += 1;
+            queue.next_order
+        };
+        let job = Rc::new(Job::<T> {
+            manager: WeakManager::new(self),
+            path: path.as_ref().to_owned(),
+            options: options.clone(),
+            priority: Cell::new(priority),
+            order,
+            state: RefCell::new(JobState::Queued),
+            waker: Default::default(),
+        });
+        self.inner.queue.borrow_mut().jobs.push(job.clone());
+        self.schedule();
+        LoadHandle { job }
+    }
+
+    pub fn max_concurrent_loads(&self) -> usize {
+        self.inner.queue.borrow().max_concurrent
+    }
+
+    pub fn set_max_concurrent_loads(&self, max: usize) {
+        self.inner.queue.borrow_mut().max_concurrent = max.max(1);
+        self.schedule();
+    }
+
+    /// Number of [handles](Manager::load_handle) waiting for a free slot
+    pub fn queued_loads(&self) -> usize {
+        let queue = self.inner.queue.borrow();
+        queue.jobs.iter().filter(|job| job.is_queued()).count()
+    }
+
+    /// Start queued jobs while there are free slots
+    fn schedule(&self) {
+        loop {
+            let job = {
+                let mut queue = self.inner.queue.borrow_mut();
+                queue.jobs.retain(|job| job.is_queued());
+                if queue.running >= queue.max_concurrent {
+                    return;
+                }
+                let Some(index) = (0..queue.jobs.len()).max_by_key(|&index| {
+                    let job = &queue.jobs[index];
+                    (job.priority(), std::cmp::Reverse(job.order()))
+                }) else {
+                    return;
+                };
+                queue.running += 1;
+                queue.jobs.swap_remove(index)
+            };
+            job.start(self);
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use super::*;
+
+/// Data format used by [Manager::load_serde], detected from the file extension by default.
+/// Use [Manager::load_serde_with] to choose it explicitly
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SerdeFormat {
+    /// `.json`
+    Json,
+    /// `.ron`
+    Ron,
+    /// `.toml`
+    Toml,
+    /// `.yaml`, `.yml`
+    Yaml,
+    /// `.msgpack`, `.mp`
+    MessagePack,
+    /// `.bincode`, `.bin`
+    Bincode,
+}
+
+impl SerdeFormat {
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        Some(match ext.to_ascii_lowercase().as_str() {
+            "json" => Self::Json,
+            "ron" => Self::Ron,
+            "toml" => Self::Toml,
+            "yaml" | "yml" => Self::Yaml,
+            "msgpack" | "mp" => Self::MessagePack,
+            "bincode" | "bin" => Self::Bincode,
+            _ => return None,
+        })
+    }
+
+    pub fn detect(path: &Path) -> Option<Self> {
+        Self::from_extension(path.extension()?.to_str()?)
+    }
+
+    pub fn is_binary(&self) -> bool {
+        matches!(self, Self::MessagePack | Self::Bincode)
+    }
+
+    /// Deserialize `data` read from `path`, errors include the path and line/column if known
+    pub fn deserialize<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &Path,
+        data: &[u8],
+    ) -> anyhow::Result<T> {
+        let path = path.display();
+        let text = || {
+            std::str::from_utf8(data).map_err(|e| anyhow::anyhow!("{path}: not valid UTF-8: {e}"))
+        };
+        match self {
+            Self::Json => serde_json::from_str(text()?).map_err(|e| {
+                let message = e.to_string();
+                let message = strip_location(&message);
+                anyhow::anyhow!("{path}:{}:{}: {message}", e.line(), e.column())
+            }),
+            Self::Ron => ron::from_str(text()?).map_err(|e| {
+                anyhow::anyhow!("{path}:{}:{}: {}", e.position.line, e.position.col, e.code)
+            }),
+            Self::Toml => {
+                let text = text()?;
+                toml::from_str(text).map_err(|e| {
+                    let message = e.message();
+                    match e.span() {
+                        Some(span) => {
+                            let (line, column) = line_column(text, span.start);
+                            anyhow::anyhow!("{path}:{line}:{column}: {message}")
+                        }
+                        None => anyhow::anyhow!("{path}: {message}"),
+                    }
+                })
+            }
+            Self::Yaml => serde_yaml::from_str(text()?).map_err(|e| {
+                let message = e.to_string();
+                let message = strip_location(&message);
+                match e.location() {
+                    Some(location) => anyhow::anyhow!(
+                        "{path}:{}:{}: {message}",
+                        location.line(),
+                        location.column(),
+                    ),
+                    None => anyhow::anyhow!("{path}: {message}"),
+                }
+            }),
+            Self::MessagePack => {
+                rmp_serde::from_slice(data).map_err(|e| anyhow::anyhow!("{path}: {e}"))
+            }
+            Self::Bincode => bincode::deserialize(data).map_err(|e| anyhow::anyhow!("{path}: {e}")),
+        }
+    }
+}
+
+/// Location is reported separately in a consistent `path:line:column` form
+fn strip_location(message: &str) -> &str {
+    message
+        .split_once(" at line ")
+        .map_or(message, |(message, _)| message)
+}
+
+/// 1-based line and column of a byte offset
+fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit_once('\n')
+        .map_or(before, |(_, line)| line)
+        .chars()
+        .count()
+        + 1;
+    (line, column)
+}
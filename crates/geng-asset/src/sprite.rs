@@ -0,0 +1,36 @@
+use super::*;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SpriteSheetOptions {
+    pub texture: TextureOptions,
+}
+
+/// Loads an Aseprite or TexturePacker JSON export or a grid spec in any [SerdeFormat],
+/// together with the image it refers to (or the `.png` next to it)
+impl Load for geng_sprite::SpriteSheet {
+    type Options = SpriteSheetOptions;
+    fn load(manager: &Manager, path: &Path, options: &Self::Options) -> Future<Self> {
+        let manager = manager.clone();
+        let path = path.to_owned();
+        let options = options.clone();
+        async move {
+            let data = manager.load_bytes(&path).await?;
+            let format = SerdeFormat::detect(&path).unwrap_or(SerdeFormat::Json);
+            let descriptor: geng_sprite::Descriptor = format.deserialize(&path, &data)?;
+            let image = match &descriptor.image {
+                Some(image) => path.parent().unwrap_or(Path::new("")).join(image),
+                None => path.with_extension("png"),
+            };
+            let texture = manager
+                .load_with::<ugli::Texture>(&image, &options.texture)
+                .await?;
+            geng_sprite::SpriteSheet::new(texture, &descriptor)
+                .map_err(|e| e.context(format!("Failed to load sprite sheet {path:?}")))
+        }
+        .boxed_local()
+    }
+    const DEFAULT_EXT: Option<&'static str> = Some("json");
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>() + self.texture().memory_usage()
+    }
+}
@@ -0,0 +1,121 @@
+#![cfg(target_os = "linux")]
+
+use futures::executor::LocalPool;
+use geng_asset::Manager;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+fn manager() -> Option<(Manager, LocalPool)> {
+    let ugli = ugli::testing::create_headless()?;
+    let pool = LocalPool::new();
+    let manager = Manager::new_headless(&ugli, pool.spawner(), false);
+    Some((manager, pool))
+}
+
+fn write_file(test: &str, contents: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "geng-asset-cache-test-{test}-{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("text.txt");
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+/// Number of times `path` was read
+fn reads(manager: &Manager, path: &Path) -> usize {
+    manager
+        .marked_paths()
+        .iter()
+        .filter(|marked| *marked == path)
+        .count()
+}
+
+#[test]
+fn hits() {
+    let Some((manager, mut pool)) = manager() else {
+        return;
+    };
+    let path = write_file("hits", "hello");
+    let (a, b) = pool.run_until(futures::future::join(
+        manager.load::<Rc<String>>(&path),
+        manager.load::<Rc<String>>(&path),
+    ));
+    let (a, b) = (a.unwrap(), b.unwrap());
+    assert!(Rc::ptr_eq(&a, &b));
+    let c = pool
+        .run_until(manager.load::<geng_asset::Handle<String>>(&path))
+        .unwrap();
+    assert_eq!(*c, "hello");
+    assert!(std::ptr::eq(&*a, &*c));
+    assert_eq!(reads(&manager, &path), 1);
+    // Owned assets are not shared
+    let owned: String = pool.run_until(manager.load(&path)).unwrap();
+    assert_eq!(owned, "hello");
+    assert_eq!(reads(&manager, &path), 2);
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn eviction() {
+    let Some((manager, mut pool)) = manager() else {
+        return;
+    };
+    let path = write_file("eviction", "old");
+    let load = |pool: &mut LocalPool| pool.run_until(manager.load::<Rc<String>>(&path)).unwrap();
+
+    // Dropped with the last handle
+    drop(load(&mut pool));
+    load(&mut pool);
+    assert_eq!(reads(&manager, &path), 2);
+
+    // Evicted explicitly while still in use
+    let old = load(&mut pool);
+    std::fs::write(&path, "new").unwrap();
+    assert!(Rc::ptr_eq(&old, &load(&mut pool)));
+    manager.evict(&path);
+    let new = load(&mut pool);
+    assert_eq!((old.as_str(), new.as_str()), ("old", "new"));
+    assert!(Rc::ptr_eq(&new, &load(&mut pool)));
+
+    manager.clear_cache();
+    assert!(!Rc::ptr_eq(&new, &load(&mut pool)));
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn memory_stats() {
+    let Some((manager, mut pool)) = manager() else {
+        return;
+    };
+    let path = write_file("memory-stats", "0123456789");
+    assert!(manager.cache_stats().is_empty());
+    let text = pool.run_until(manager.load::<Rc<String>>(&path)).unwrap();
+    let bytes = pool.run_until(manager.load::<Rc<Vec<u8>>>(&path)).unwrap();
+    let mut stats = manager.cache_stats();
+    stats.sort_by_key(|stats| stats.type_name);
+    let stats: Vec<_> = stats
+        .iter()
+        .map(|stats| (stats.type_name, stats.count, stats.memory))
+        .collect();
+    let mut expected = vec![
+        (
+            std::any::type_name::<String>(),
+            1,
+            std::mem::size_of::<String>() + text.capacity(),
+        ),
+        (
+            std::any::type_name::<Vec<u8>>(),
+            1,
+            std::mem::size_of::<Vec<u8>>() + bytes.capacity(),
+        ),
+    ];
+    expected.sort();
+    assert_eq!(stats, expected);
+    drop(text);
+    drop(bytes);
+    assert!(manager.cache_stats().is_empty());
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
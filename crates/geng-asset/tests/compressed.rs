@@ -0,0 +1,98 @@
+use geng_asset::{CompressedImage, ImageFormat};
+use ugli::CompressedFormat;
+
+fn ktx2(vk_format: u32, size: [u32; 2], levels: &[Vec<u8>]) -> Vec<u8> {
+    let mut data = b"\xABKTX 20\xBB\r\n\x1A\n".to_vec();
+    let header = [
+        vk_format,
+        1,
+        size[0],
+        size[1],
+        0,
+        0,
+        1,
+        levels.len() as u32,
+        0,
+    ];
+    for value in header {
+        data.extend(value.to_le_bytes());
+    }
+    // Empty descriptor, key/value and global data
+    data.extend([0u8; 32]);
+    let mut offset = 80 + levels.len() * 24;
+    for level in levels {
+        data.extend((offset as u64).to_le_bytes());
+        data.extend((level.len() as u64).to_le_bytes());
+        data.extend((level.len() as u64).to_le_bytes());
+        offset += level.len();
+    }
+    for level in levels {
+        data.extend(level);
+    }
+    data
+}
+
+#[test]
+fn ktx2_levels() {
+    // BC7 8x4 with mips 4x2 and 2x1, one block row each
+    let levels = vec![vec![1; 32], vec![2; 16], vec![3; 16]];
+    let image = CompressedImage::parse_ktx2(&ktx2(145, [8, 4], &levels)).unwrap();
+    assert_eq!(image.format, ImageFormat::Compressed(CompressedFormat::Bc7));
+    assert_eq!((image.size.x, image.size.y), (8, 4));
+    assert_eq!(image.levels, levels);
+
+    let wrong_size = vec![vec![1; 16]];
+    assert!(CompressedImage::parse_ktx2(&ktx2(145, [8, 4], &wrong_size)).is_err());
+    assert!(CompressedImage::parse_ktx2(&ktx2(9999, [8, 4], &levels)).is_err());
+    assert!(CompressedImage::parse_ktx2(b"not a ktx2 file").is_err());
+
+    assert!(CompressedImage::parse_ktx2(&ktx2(145, [0, 4], &levels)).is_err());
+    assert!(CompressedImage::parse_ktx2(&ktx2(145, [u32::MAX, u32::MAX], &levels)).is_err());
+    // An 8x4 texture has at most 4 levels
+    let mut too_many_levels = ktx2(145, [8, 4], &levels);
+    too_many_levels[40..44].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(CompressedImage::parse_ktx2(&too_many_levels).is_err());
+}
+
+fn dds(four_cc: &[u8; 4], size: [u32; 2], levels: &[Vec<u8>]) -> Vec<u8> {
+    let mut header = [0u32; 31];
+    header[0] = 124;
+    header[1] = 0x1 | 0x2 | 0x4 | 0x1000 | 0x20000;
+    header[2] = size[1];
+    header[3] = size[0];
+    header[6] = levels.len() as u32;
+    header[18] = 32;
+    header[19] = 0x4;
+    header[20] = u32::from_le_bytes(*four_cc);
+    let mut data = b"DDS ".to_vec();
+    for value in header {
+        data.extend(value.to_le_bytes());
+    }
+    for level in levels {
+        data.extend(level);
+    }
+    data
+}
+
+#[test]
+fn dds_levels() {
+    // DXT5 4x4 with mips 2x2 and 1x1, a single block each
+    let levels = vec![vec![1; 16], vec![2; 16], vec![3; 16]];
+    let image = CompressedImage::parse_dds(&dds(b"DXT5", [4, 4], &levels)).unwrap();
+    assert_eq!(image.format, ImageFormat::Compressed(CompressedFormat::Bc3));
+    assert_eq!(image.levels, levels);
+
+    let image = CompressedImage::parse_dds(&dds(b"DXT1", [8, 8], &[vec![0; 32]])).unwrap();
+    assert_eq!(
+        image.format,
+        ImageFormat::Compressed(CompressedFormat::Bc1Alpha)
+    );
+
+    assert!(CompressedImage::parse_dds(&dds(b"DXT5", [8, 8], &[vec![0; 16]])).is_err());
+    assert!(CompressedImage::parse_dds(&dds(b"NOPE", [4, 4], &levels)).is_err());
+    assert!(CompressedImage::parse_dds(&dds(b"DXT5", [4, 0], &levels)).is_err());
+    assert!(CompressedImage::parse_dds(&dds(b"DXT5", [1 << 20, 4], &levels)).is_err());
+    let mut too_many_levels = dds(b"DXT5", [4, 4], &levels);
+    too_many_levels[28..32].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(CompressedImage::parse_dds(&too_many_levels).is_err());
+}
@@ -0,0 +1,37 @@
+use geng_asset::{Manifest, MANIFEST_FILE};
+
+#[test]
+fn generate_list_and_check() {
+    let dir = std::env::temp_dir().join(format!(
+        "geng-asset-manifest-test-generate-{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("levels/forest")).unwrap();
+    std::fs::write(dir.join("levels/forest/map.json"), "{}").unwrap();
+    std::fs::write(dir.join("levels/desert.json"), "{}").unwrap();
+    std::fs::write(dir.join("levels/desert.png"), "png").unwrap();
+    std::fs::write(dir.join("levels/_list.json"), "[]").unwrap();
+    std::fs::write(dir.join(MANIFEST_FILE), "{}").unwrap();
+
+    let manifest = Manifest::generate(&dir).unwrap();
+    assert_eq!(manifest.files.len(), 4);
+    assert_eq!(manifest.list("levels"), ["desert", "forest"]);
+    assert!(manifest.verify("levels/desert.png", b"png").is_ok());
+    assert!(manifest.verify("levels/desert.png", b"gif").is_err());
+    assert!(manifest.check(&dir).unwrap().is_ok());
+
+    std::fs::write(dir.join("extra.txt"), "").unwrap();
+    std::fs::remove_file(dir.join("levels/desert.png")).unwrap();
+    std::fs::write(dir.join("levels/desert.json"), "[]").unwrap();
+    let report = manifest.check(&dir).unwrap();
+    assert_eq!(report.extra, ["extra.txt"]);
+    assert_eq!(report.missing, ["levels/desert.png"]);
+    assert_eq!(report.modified, ["levels/desert.json"]);
+    assert_ne!(
+        Manifest::generate(&dir).unwrap().version(),
+        manifest.version()
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,61 @@
+use geng_asset::{Pack, PackBuilder};
+
+#[test]
+fn roundtrip() {
+    let text = "hello ".repeat(100);
+    let mut builder = PackBuilder::new();
+    builder.add("text.txt", text.clone().into_bytes());
+    builder.add("dir/data.bin", vec![1, 2, 3]);
+    let pack = Pack::from_bytes(builder.to_bytes().unwrap()).unwrap();
+
+    let mut paths: Vec<&str> = pack.paths().collect();
+    paths.sort();
+    assert_eq!(paths, ["dir/data.bin", "text.txt"]);
+    assert_eq!(pack.read("text.txt").unwrap().unwrap(), text.into_bytes());
+    assert_eq!(pack.read("dir/data.bin").unwrap().unwrap(), [1, 2, 3]);
+    assert!(pack.read("missing.txt").is_none());
+}
+
+#[test]
+fn rejects_garbage() {
+    assert!(Pack::from_bytes(b"not a pack".to_vec()).is_err());
+    let mut data = PackBuilder::new().to_bytes().unwrap();
+    data[8] = 42;
+    assert!(Pack::from_bytes(data).is_err());
+
+    // Index length close to u64::MAX
+    let mut data = PackBuilder::new().to_bytes().unwrap();
+    data[12..20].copy_from_slice(&(u64::MAX - 4).to_le_bytes());
+    assert!(Pack::from_bytes(data).is_err());
+
+    // Entry offset that overflows when added to the index end
+    let mut builder = PackBuilder::new();
+    builder.add("a.txt", b"hello".to_vec());
+    let mut data = builder.to_bytes().unwrap();
+    let index_size = u64::from_le_bytes(data[12..20].try_into().unwrap()) as usize;
+    // bincode index: entry count, path length, path, then the entry's `offset`
+    let offset = 20 + 8 + 8 + "a.txt".len();
+    assert!(offset + 8 <= 20 + index_size);
+    data[offset..offset + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+    assert!(Pack::from_bytes(data).is_err());
+}
+
+#[test]
+fn rejects_wrong_decompressed_size() {
+    let text = "hello ".repeat(100);
+    let mut builder = PackBuilder::new();
+    builder.add("a.txt", text.clone().into_bytes());
+    let data = builder.to_bytes().unwrap();
+    // bincode index: entry count, path length, path, `offset`, `stored_size`, then `size`
+    let size = 20 + 8 + 8 + "a.txt".len() + 8 + 8;
+    assert_eq!(
+        u64::from_le_bytes(data[size..size + 8].try_into().unwrap()),
+        text.len() as u64,
+    );
+    for wrong_size in [10, text.len() as u64 - 1, text.len() as u64 + 1] {
+        let mut data = data.clone();
+        data[size..size + 8].copy_from_slice(&wrong_size.to_le_bytes());
+        let pack = Pack::from_bytes(data).unwrap();
+        assert!(pack.read("a.txt").unwrap().is_err(), "{wrong_size}");
+    }
+}
@@ -0,0 +1,44 @@
+use geng_asset::LoadProgress;
+
+fn progress(progress: usize, total: usize) -> LoadProgress {
+    let mut result = LoadProgress::new();
+    result.progress = progress;
+    result.total = total;
+    result
+}
+
+#[test]
+fn since() {
+    // Two loads were running at the start of the stage, one more was started since
+    let start = progress(1, 3);
+    let stage = progress(2, 4).since(&start);
+    assert_eq!((stage.progress, stage.total), (1, 1));
+    assert_eq!(stage.fraction(), 1.0);
+    let stage = progress(4, 4).since(&start);
+    assert_eq!((stage.progress, stage.total), (1, 1));
+    assert_eq!(stage.fraction(), 1.0);
+    let stage = progress(1, 5).since(&start);
+    assert_eq!((stage.progress, stage.total), (0, 2));
+    assert_eq!(stage.fraction(), 0.0);
+    assert_eq!(start.since(&start).fraction(), 0.0);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn manager() {
+    let Some(ugli) = ugli::testing::create_headless() else {
+        return;
+    };
+    let mut pool = futures::executor::LocalPool::new();
+    let manager = geng_asset::Manager::new_headless(&ugli, pool.spawner(), false);
+    let path = std::env::temp_dir().join(format!("geng-asset-progress-{}.txt", std::process::id()));
+    std::fs::write(&path, "12345").unwrap();
+    let start = manager.load_progress();
+    let load = manager.load::<String>(&path);
+    let stage = manager.load_progress().since(&start);
+    assert_eq!((stage.progress, stage.total, stage.bytes), (0, 1, 0));
+    pool.run_until(load).unwrap();
+    let stage = manager.load_progress().since(&start);
+    assert_eq!((stage.progress, stage.total, stage.bytes), (1, 1, 5));
+    std::fs::remove_file(path).unwrap();
+}
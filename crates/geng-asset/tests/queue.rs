@@ -0,0 +1,135 @@
+#![cfg(target_os = "linux")]
+
+use futures::executor::LocalPool;
+use futures::FutureExt;
+use geng_asset::{LoadHandle, LoadStatus, Manager};
+use std::path::{Path, PathBuf};
+
+fn manager() -> Option<(Manager, LocalPool)> {
+    let ugli = ugli::testing::create_headless()?;
+    let pool = LocalPool::new();
+    let manager = Manager::new_headless(&ugli, pool.spawner(), false);
+    Some((manager, pool))
+}
+
+/// Directory with `count` text files named `0.txt`, `1.txt`, ...
+fn write_files(test: &str, count: usize) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "geng-asset-queue-test-{test}-{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    for index in 0..count {
+        std::fs::write(dir.join(format!("{index}.txt")), index.to_string()).unwrap();
+    }
+    dir
+}
+
+fn load(manager: &Manager, dir: &Path, index: usize, priority: i32) -> LoadHandle<String> {
+    manager.load_handle(dir.join(format!("{index}.txt")), &(), priority)
+}
+
+#[test]
+fn priority_order() {
+    let Some((manager, mut pool)) = manager() else {
+        return;
+    };
+    let dir = write_files("priority", 5);
+    manager.set_max_concurrent_loads(1);
+    let handles = [
+        load(&manager, &dir, 0, 0),
+        load(&manager, &dir, 1, 1),
+        load(&manager, &dir, 2, 5),
+        load(&manager, &dir, 3, 5),
+        load(&manager, &dir, 4, 0),
+    ];
+    // Can be raised while queued
+    handles[4].set_priority(10);
+    pool.run_until_stalled();
+    let order: Vec<PathBuf> = manager.marked_paths();
+    let expected: Vec<PathBuf> = [0, 4, 2, 3, 1]
+        .into_iter()
+        .map(|index| dir.join(format!("{index}.txt")))
+        .collect();
+    assert_eq!(order, expected);
+    for (index, handle) in handles.iter().enumerate() {
+        assert_eq!(handle.take().unwrap().unwrap(), index.to_string());
+    }
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn concurrency_limit() {
+    let Some((manager, mut pool)) = manager() else {
+        return;
+    };
+    let dir = write_files("concurrency", 5);
+    manager.set_max_concurrent_loads(2);
+    let handles: Vec<_> = (0..5).map(|index| load(&manager, &dir, index, 0)).collect();
+    let statuses = || handles.iter().map(LoadHandle::status).collect::<Vec<_>>();
+    assert_eq!(
+        statuses(),
+        [
+            LoadStatus::Loading,
+            LoadStatus::Loading,
+            LoadStatus::Queued,
+            LoadStatus::Queued,
+            LoadStatus::Queued,
+        ]
+    );
+    assert_eq!(manager.queued_loads(), 3);
+    pool.run_until_stalled();
+    assert_eq!(statuses(), [LoadStatus::Finished; 5]);
+    assert_eq!(manager.queued_loads(), 0);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn cancellation() {
+    let Some((manager, mut pool)) = manager() else {
+        return;
+    };
+    let dir = write_files("cancel", 4);
+    manager.set_max_concurrent_loads(1);
+    let loading = load(&manager, &dir, 0, 0);
+    let queued = load(&manager, &dir, 1, 0);
+    let dropped = load(&manager, &dir, 2, 0);
+    let last = load(&manager, &dir, 3, 0);
+    assert_eq!(loading.status(), LoadStatus::Loading);
+
+    queued.cancel();
+    assert_eq!(queued.status(), LoadStatus::Cancelled);
+    drop(dropped);
+    // Cancelling a running load frees its slot for the next one
+    loading.cancel();
+    assert_eq!(loading.status(), LoadStatus::Cancelled);
+    assert_eq!(last.status(), LoadStatus::Loading);
+    assert_eq!(manager.queued_loads(), 0);
+
+    pool.run_until_stalled();
+    assert!(pool.run_until(queued).is_err());
+    assert!(pool.run_until(loading).is_err());
+    assert_eq!(pool.run_until(last).unwrap(), "3");
+    let expected: Vec<PathBuf> = vec![dir.join("3.txt")];
+    assert_eq!(manager.marked_paths(), expected);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+/// Queued loads don't keep the manager alive
+#[test]
+fn dropped_manager() {
+    let Some((manager, mut pool)) = manager() else {
+        return;
+    };
+    let dir = write_files("dropped", 2);
+    manager.set_max_concurrent_loads(1);
+    let first = load(&manager, &dir, 0, 0);
+    let mut second = load(&manager, &dir, 1, 0);
+    drop(manager);
+    pool.run_until_stalled();
+    assert_eq!(first.take().unwrap().unwrap(), "0");
+    let result = (&mut second).now_or_never().expect("load is stuck");
+    assert!(result.is_err());
+    std::fs::remove_dir_all(dir).unwrap();
+}
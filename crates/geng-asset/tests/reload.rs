@@ -0,0 +1,84 @@
+#![cfg(target_os = "linux")]
+
+use futures::executor::LocalPool;
+use geng_asset::{HotCollection, Manager};
+use std::path::{Path, PathBuf};
+
+// The derive refers to `geng::asset`
+mod geng {
+    pub use geng_asset as asset;
+}
+
+#[derive(geng::asset::Load)]
+struct Texts {
+    #[load(path = "a.txt")]
+    a: String,
+    #[load(path = "b.txt")]
+    b: String,
+}
+
+#[derive(geng::asset::Load)]
+struct Generic<T: geng::asset::Load<Options = ()>> {
+    #[load(path = "a.txt")]
+    a: T,
+}
+
+fn temp_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "geng-asset-reload-test-{test}-{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn reads(manager: &Manager, path: &Path) -> usize {
+    let path = std::fs::canonicalize(path).unwrap();
+    manager
+        .marked_paths()
+        .iter()
+        .filter(|marked| std::fs::canonicalize(marked).is_ok_and(|marked| marked == path))
+        .count()
+}
+
+#[test]
+fn generic() {
+    let Some(ugli) = ugli::testing::create_headless() else {
+        return;
+    };
+    let mut pool = LocalPool::new();
+    let manager = Manager::new_headless(&ugli, pool.spawner(), false);
+    let dir = temp_dir("generic");
+    std::fs::write(dir.join("a.txt"), "a").unwrap();
+    let texts: Generic<String> = pool.run_until(manager.load(&dir)).unwrap();
+    assert_eq!(texts.a, "a");
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn reload_single_field() {
+    let Some(ugli) = ugli::testing::create_headless() else {
+        return;
+    };
+    let mut pool = LocalPool::new();
+    let manager = Manager::new_headless(&ugli, pool.spawner(), true);
+    let dir = temp_dir("single-field");
+    let (a, b) = (dir.join("a.txt"), dir.join("b.txt"));
+    std::fs::write(&a, "a").unwrap();
+    std::fs::write(&b, "b").unwrap();
+    let texts: HotCollection<Texts> = pool.run_until(manager.load(&dir)).unwrap();
+    assert_eq!(texts.get().b, "b");
+
+    std::fs::write(&b, "b2").unwrap();
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while texts.get().b != "b2" {
+        assert!(std::time::Instant::now() < deadline, "b was not reloaded");
+        pool.run_until_stalled();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    assert_eq!(texts.get().a, "a");
+    assert_eq!(reads(&manager, &a), 1);
+    assert!(reads(&manager, &b) >= 2);
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,121 @@
+use geng_asset::SerdeFormat;
+use std::path::Path;
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Config {
+    name: String,
+    size: u32,
+}
+
+fn config() -> Config {
+    Config {
+        name: "test".to_owned(),
+        size: 5,
+    }
+}
+
+#[test]
+fn detect() {
+    assert_eq!(
+        SerdeFormat::detect(Path::new("a/config.yml")),
+        Some(SerdeFormat::Yaml)
+    );
+    assert_eq!(
+        SerdeFormat::detect(Path::new("config.RON")),
+        Some(SerdeFormat::Ron)
+    );
+    assert_eq!(SerdeFormat::detect(Path::new("config.txt")), None);
+}
+
+#[test]
+fn text_formats() {
+    let sources = [
+        ("config.json", r#"{ "name": "test", "size": 5 }"#),
+        ("config.ron", r#"(name: "test", size: 5)"#),
+        ("config.toml", "name = \"test\"\nsize = 5\n"),
+        ("config.yaml", "name: test\nsize: 5\n"),
+    ];
+    for (path, source) in sources {
+        let path = Path::new(path);
+        let format = SerdeFormat::detect(path).unwrap();
+        let value: Config = format.deserialize(path, source.as_bytes()).unwrap();
+        assert_eq!(value, config());
+    }
+}
+
+#[test]
+fn binary_formats() {
+    let data = rmp_serde::to_vec_named(&config()).unwrap();
+    let value: Config = SerdeFormat::MessagePack
+        .deserialize(Path::new("config.msgpack"), &data)
+        .unwrap();
+    assert_eq!(value, config());
+}
+
+#[test]
+fn error_location() {
+    let sources = [
+        (
+            "config.json",
+            "{\n  \"name\": \"test\",\n  \"size\": \"five\"\n}",
+            3,
+        ),
+        (
+            "config.ron",
+            "(\n  name: \"test\",\n  size: \"five\",\n)",
+            3,
+        ),
+        ("config.toml", "name = \"test\"\nsize = \"five\"\n", 2),
+        ("config.yaml", "name: test\nsize: five\n", 2),
+    ];
+    for (path, source, line) in sources {
+        let path = Path::new(path);
+        let error = SerdeFormat::detect(path)
+            .unwrap()
+            .deserialize::<Config>(path, source.as_bytes())
+            .unwrap_err()
+            .to_string();
+        let location = format!("{}:{line}:", path.display());
+        assert!(error.starts_with(&location), "{error}");
+    }
+}
+
+// The derive refers to `geng::asset`
+#[cfg(target_os = "linux")]
+mod geng {
+    pub use geng_asset as asset;
+}
+
+#[cfg(target_os = "linux")]
+#[derive(Debug, PartialEq, serde::Deserialize, geng::asset::Load)]
+#[load(serde = "ron")]
+struct Level {
+    name: String,
+}
+
+/// Derived assets take `()` options, the format comes from the extension
+#[cfg(target_os = "linux")]
+#[test]
+fn derive() {
+    let Some(ugli) = ugli::testing::create_headless() else {
+        return;
+    };
+    let mut pool = futures::executor::LocalPool::new();
+    let manager = geng_asset::Manager::new_headless(&ugli, pool.spawner(), false);
+    let dir = std::env::temp_dir().join(format!(
+        "geng-asset-serde-test-derive-{}",
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("level.ron"), r#"(name: "ron")"#).unwrap();
+    std::fs::write(dir.join("level.json"), r#"{ "name": "json" }"#).unwrap();
+    let level: Level = pool
+        .run_until(manager.load_with(dir.join("level.ron"), &()))
+        .unwrap();
+    assert_eq!(level.name, "ron");
+    let level: Level = pool
+        .run_until(manager.load_with(dir.join("level.json"), &()))
+        .unwrap();
+    assert_eq!(level.name, "json");
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,34 @@
+#![cfg(target_os = "linux")]
+
+use batbox_color::Rgba;
+use batbox_la::*;
+use geng_camera::PixelPerfectCamera;
+use std::path::Path;
+
+#[test]
+fn quads() {
+    let Some(ugli) = ugli::testing::create_headless() else {
+        return;
+    };
+    let helper = geng_draw2d::Helper::new(&ugli, false);
+    let mut texture = ugli::Texture::new_uninitialized(&ugli, vec2(16, 16));
+    let mut framebuffer =
+        ugli::Framebuffer::new_color(&ugli, ugli::ColorAttachment::Texture(&mut texture));
+    ugli::clear(&mut framebuffer, Some(Rgba::BLACK), None, None);
+    // Pixel aligned, so the result is exact
+    for (min, max, color) in [
+        (vec2(2.0, 2.0), vec2(10.0, 10.0), Rgba::RED),
+        (vec2(8.0, 8.0), vec2(14.0, 14.0), Rgba::GREEN),
+    ] {
+        helper.draw2d(
+            &mut framebuffer,
+            &PixelPerfectCamera,
+            &geng_draw2d::Quad::new(Aabb2 { min, max }, color),
+        );
+    }
+    ugli::testing::assert_snapshot(
+        &framebuffer.read_color().into_image(),
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots/quads.png"),
+        0,
+    );
+}
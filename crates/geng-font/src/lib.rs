@@ -14,7 +14,7 @@ pub enum DistanceMode {
     Max,
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Options {
     pub pixel_size: f32,
     pub max_distance: f32,
@@ -0,0 +1,99 @@
+//! Splitting Fluent resources into message patterns, see [Translations::from_fluent]
+use super::*;
+
+struct Entry {
+    id: String,
+    /// `None` for the message value, attribute name otherwise
+    attribute: Option<String>,
+    lines: Vec<String>,
+}
+
+impl Entry {
+    fn key(&self) -> String {
+        match &self.attribute {
+            Some(attribute) => format!("{}.{attribute}", self.id),
+            None => self.id.clone(),
+        }
+    }
+
+    /// Continuation lines are joined with newlines, without their indentation
+    fn pattern(&self) -> Option<String> {
+        let lines = self.lines.iter().map(|line| line.trim());
+        let pattern = lines.collect::<Vec<_>>().join("\n");
+        let pattern = pattern.trim_matches('\n');
+        (!pattern.is_empty()).then(|| pattern.to_owned())
+    }
+}
+
+/// Keys and patterns of all messages, terms and attributes
+pub(crate) fn parse(source: &str) -> anyhow::Result<Vec<(String, String)>> {
+    let mut result = Vec::new();
+    let mut current: Option<Entry> = None;
+    let mut has_attributes = false;
+    let mut finish = |entry: Option<Entry>, has_attributes: bool| -> anyhow::Result<()> {
+        let Some(entry) = entry else { return Ok(()) };
+        match entry.pattern() {
+            Some(pattern) => result.push((entry.key(), pattern)),
+            // Messages may consist of attributes only
+            None if entry.attribute.is_none() && has_attributes => {}
+            None => anyhow::bail!("{:?} has no value", entry.key()),
+        }
+        Ok(())
+    };
+    for (index, line) in source.lines().enumerate() {
+        let line_number = index + 1;
+        let error = |message: &str| anyhow::anyhow!("Line {line_number}: {message}");
+        if line.starts_with('#') {
+            finish(current.take(), has_attributes)?;
+            continue;
+        }
+        if line.trim().is_empty() {
+            if let Some(entry) = &mut current {
+                entry.lines.push(String::new());
+            }
+            continue;
+        }
+        if line.starts_with([' ', '\t']) {
+            let Some(entry) = &mut current else {
+                return Err(error("indented line outside of a message"));
+            };
+            let trimmed = line.trim_start();
+            if let Some(attribute) = trimmed.strip_prefix('.') {
+                let (name, value) = attribute
+                    .split_once('=')
+                    .ok_or_else(|| error("expected `.attribute = value`"))?;
+                let name = name.trim();
+                if !is_identifier(name) {
+                    return Err(error(&format!("invalid attribute name {name:?}")));
+                }
+                let id = entry.id.clone();
+                has_attributes = true;
+                finish(current.take(), has_attributes)?;
+                current = Some(Entry {
+                    id,
+                    attribute: Some(name.to_owned()),
+                    lines: vec![value.to_owned()],
+                });
+            } else {
+                entry.lines.push(trimmed.to_owned());
+            }
+            continue;
+        }
+        finish(current.take(), has_attributes)?;
+        has_attributes = false;
+        let (id, value) = line
+            .split_once('=')
+            .ok_or_else(|| error("expected `key = value`"))?;
+        let id = id.trim();
+        if !is_identifier(id.strip_prefix('-').unwrap_or(id)) {
+            return Err(error(&format!("invalid message name {id:?}")));
+        }
+        current = Some(Entry {
+            id: id.to_owned(),
+            attribute: None,
+            lines: vec![value.to_owned()],
+        });
+    }
+    finish(current.take(), has_attributes)?;
+    Ok(result)
+}
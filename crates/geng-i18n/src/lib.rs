@@ -0,0 +1,105 @@
+//! Translation tables loaded at runtime and the current locale, see [Localization]
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::rc::Rc;
+
+mod fluent;
+mod localization;
+mod message;
+
+pub use localization::*;
+pub use message::*;
+
+/// Format arguments, referred to as `{ $name }` in messages
+pub type Args<'a> = [(&'a str, &'a dyn Display)];
+
+/// Messages of a single locale
+#[derive(Debug, Clone, Default)]
+pub struct Translations {
+    messages: HashMap<String, Message>,
+}
+
+impl Translations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a subset of [Fluent](https://projectfluent.org/):
+    /// messages, terms, attributes (as `message.attribute`) and placeables,
+    /// but no selectors or functions
+    pub fn from_fluent(source: &str) -> anyhow::Result<Self> {
+        let mut translations = Self::new();
+        for (key, pattern) in fluent::parse(source)? {
+            let message = Message::parse(&pattern)
+                .map_err(|e| e.context(format!("Failed to parse message {key:?}")))?;
+            translations.insert(key, message);
+        }
+        Ok(translations)
+    }
+
+    /// Parse a TOML file of string values with the same placeables as in Fluent,
+    /// keys of nested tables are joined with `.`
+    pub fn from_toml(source: &str) -> anyhow::Result<Self> {
+        fn flatten(
+            prefix: &str,
+            table: toml::Table,
+            translations: &mut Translations,
+        ) -> anyhow::Result<()> {
+            for (key, value) in table {
+                let key = match prefix {
+                    "" => key,
+                    _ => format!("{prefix}.{key}"),
+                };
+                match value {
+                    toml::Value::String(pattern) => {
+                        let message = Message::parse(&pattern)
+                            .map_err(|e| e.context(format!("Failed to parse message {key:?}")))?;
+                        translations.insert(key, message);
+                    }
+                    toml::Value::Table(table) => flatten(&key, table, translations)?,
+                    _ => anyhow::bail!("Translation {key:?} must be a string or a table"),
+                }
+            }
+            Ok(())
+        }
+        let mut translations = Self::new();
+        flatten("", toml::from_str(source)?, &mut translations)?;
+        Ok(translations)
+    }
+
+    pub fn insert(&mut self, key: impl Into<String>, message: Message) {
+        self.messages.insert(key.into(), message);
+    }
+
+    /// Add all messages of `other`, replacing existing ones
+    pub fn extend(&mut self, other: Translations) {
+        self.messages.extend(other.messages);
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Message> {
+        self.messages.get(key)
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.messages.contains_key(key)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.messages.keys().map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Format message `key`, `None` if there is no such message
+    pub fn format(&self, key: &str, args: &Args) -> Option<String> {
+        let message = self.get(key)?;
+        Some(message.format(args, &|key| self.get(key)))
+    }
+}
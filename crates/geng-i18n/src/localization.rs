@@ -0,0 +1,138 @@
+use super::*;
+
+/// Translations of all locales together with the current locale.
+///
+/// Cheap to clone, all clones share the same state
+#[derive(Clone)]
+pub struct Localization {
+    inner: Rc<LocalizationImpl>,
+}
+
+struct LocalizationImpl {
+    locale: RefCell<String>,
+    fallback_locale: RefCell<Option<String>>,
+    /// By [normalized](normalize) locale
+    translations: RefCell<HashMap<String, Translations>>,
+    revision: Cell<u64>,
+    subscribers: RefCell<Vec<futures::channel::mpsc::UnboundedSender<String>>>,
+}
+
+impl Localization {
+    pub fn new(locale: &str) -> Self {
+        Self {
+            inner: Rc::new(LocalizationImpl {
+                locale: RefCell::new(locale.to_owned()),
+                fallback_locale: RefCell::new(None),
+                translations: RefCell::new(HashMap::new()),
+                revision: Cell::new(0),
+                subscribers: RefCell::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Start with the locale of the user's system or browser
+    pub fn detect() -> Self {
+        Self::new(&batbox_i18n::detect_lang())
+    }
+
+    pub fn locale(&self) -> String {
+        self.inner.locale.borrow().clone()
+    }
+
+    /// Switch the locale, notifying [Localization::locale_changes] subscribers
+    pub fn set_locale(&self, locale: &str) {
+        if normalize(&self.inner.locale.borrow()) == normalize(locale) {
+            return;
+        }
+        log::info!("Switching locale to {locale:?}");
+        *self.inner.locale.borrow_mut() = locale.to_owned();
+        self.changed();
+        self.inner
+            .subscribers
+            .borrow_mut()
+            .retain(|sender| sender.unbounded_send(locale.to_owned()).is_ok());
+    }
+
+    pub fn fallback_locale(&self) -> Option<String> {
+        self.inner.fallback_locale.borrow().clone()
+    }
+
+    /// Locale used for keys missing in the current one
+    pub fn set_fallback_locale(&self, locale: Option<&str>) {
+        *self.inner.fallback_locale.borrow_mut() = locale.map(str::to_owned);
+        self.changed();
+    }
+
+    /// Add translations for `locale`, replacing messages with the same keys
+    pub fn add(&self, locale: &str, translations: Translations) {
+        self.inner
+            .translations
+            .borrow_mut()
+            .entry(normalize(locale))
+            .or_default()
+            .extend(translations);
+        self.changed();
+    }
+
+    /// Locales that have translations, normalized to lowercase with `-` separators
+    pub fn locales(&self) -> Vec<String> {
+        let mut locales: Vec<String> = self.inner.translations.borrow().keys().cloned().collect();
+        locales.sort();
+        locales
+    }
+
+    /// Incremented every time the text of some key may have changed,
+    /// so that resolved text can be cached until it changes
+    pub fn revision(&self) -> u64 {
+        self.inner.revision.get()
+    }
+
+    /// Stream of new locales, one for every [Localization::set_locale] call that changes it
+    pub fn locale_changes(&self) -> impl futures::Stream<Item = String> {
+        let (sender, receiver) = futures::channel::mpsc::unbounded();
+        self.inner.subscribers.borrow_mut().push(sender);
+        receiver
+    }
+
+    /// Text of `key`, or the key itself if no locale has it
+    pub fn get(&self, key: &str) -> String {
+        self.format(key, &[])
+    }
+
+    /// Format `key` with `args`, or return the key itself if no locale has it
+    pub fn format(&self, key: &str, args: &Args) -> String {
+        self.try_format(key, args).unwrap_or_else(|| key.to_owned())
+    }
+
+    /// Format `key` looking it up in the current locale, its parent locales
+    /// (`en` for `en-US`) and then the fallback locale
+    pub fn try_format(&self, key: &str, args: &Args) -> Option<String> {
+        let translations = self.inner.translations.borrow();
+        let mut chain: Vec<&Translations> = Vec::new();
+        let locales = std::iter::once(self.locale()).chain(self.fallback_locale());
+        for locale in locales {
+            let mut locale = normalize(&locale);
+            loop {
+                if let Some(table) = translations.get(&locale) {
+                    chain.push(table);
+                }
+                match locale.rfind('-') {
+                    Some(index) => locale.truncate(index),
+                    None => break,
+                }
+            }
+        }
+        let lookup = |key: &str| chain.iter().find_map(|table| table.get(key));
+        let message = lookup(key)?;
+        Some(message.format(args, &lookup))
+    }
+
+    fn changed(&self) {
+        self.inner.revision.set(self.inner.revision.get() + 1);
+    }
+}
+
+/// Lowercase with `-` separators, so that `en_US` and `en-us` are the same locale
+fn normalize(locale: &str) -> String {
+    locale.replace('_', "-").to_lowercase()
+}
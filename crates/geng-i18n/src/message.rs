@@ -0,0 +1,187 @@
+use super::*;
+
+/// Maximum depth of message references, to stop on cycles
+const MAX_DEPTH: usize = 16;
+
+/// Parsed message pattern: text with `{ $variable }`, `{ "literal" }`
+/// and `{ message }` / `{ -term }` placeables
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Text(String),
+    Variable(String),
+    Reference(String),
+}
+
+impl Message {
+    /// Message without placeables
+    pub fn text(text: impl Into<String>) -> Self {
+        Self {
+            parts: vec![Part::Text(text.into())],
+        }
+    }
+
+    pub fn parse(pattern: &str) -> anyhow::Result<Self> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' => {
+                    let mut placeable = String::new();
+                    let mut in_string = false;
+                    let mut escaped = false;
+                    loop {
+                        let Some(c) = chars.next() else {
+                            anyhow::bail!("Unclosed placeable in {pattern:?}");
+                        };
+                        if c == '}' && !in_string {
+                            break;
+                        }
+                        if c == '"' && !escaped {
+                            in_string = !in_string;
+                        }
+                        escaped = in_string && !escaped && c == '\\';
+                        placeable.push(c);
+                    }
+                    let placeable = placeable.trim();
+                    if let Some(literal) = placeable.strip_prefix('"') {
+                        text.push_str(&unescape(literal.strip_suffix('"').unwrap_or(literal))?);
+                        continue;
+                    }
+                    if placeable.contains("->") {
+                        anyhow::bail!("Selectors are not supported: {{{placeable}}}");
+                    }
+                    if !text.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    if let Some(name) = placeable.strip_prefix('$') {
+                        if !is_identifier(name) {
+                            anyhow::bail!("Invalid variable name {name:?}");
+                        }
+                        parts.push(Part::Variable(name.to_owned()));
+                    } else if is_reference(placeable) {
+                        parts.push(Part::Reference(placeable.to_owned()));
+                    } else {
+                        anyhow::bail!("Unsupported placeable {{{placeable}}}");
+                    }
+                }
+                '}' => anyhow::bail!("Unmatched }} in {pattern:?}"),
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() || parts.is_empty() {
+            parts.push(Part::Text(text));
+        }
+        Ok(Self { parts })
+    }
+
+    /// Names of the variables used by this message
+    pub fn variables(&self) -> impl Iterator<Item = &str> {
+        self.parts.iter().filter_map(|part| match part {
+            Part::Variable(name) => Some(name.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Format with `args`, resolving references with `lookup`.
+    ///
+    /// Missing variables and references are left as `{$name}` and `{name}`
+    pub fn format<'a>(
+        &'a self,
+        args: &Args,
+        lookup: &dyn Fn(&str) -> Option<&'a Message>,
+    ) -> String {
+        let mut result = String::new();
+        self.write(&mut result, args, lookup, 0);
+        result
+    }
+
+    fn write<'a>(
+        &'a self,
+        result: &mut String,
+        args: &Args,
+        lookup: &dyn Fn(&str) -> Option<&'a Message>,
+        depth: usize,
+    ) {
+        use std::fmt::Write as _;
+        for part in &self.parts {
+            match part {
+                Part::Text(text) => result.push_str(text),
+                Part::Variable(name) => match args.iter().find(|(arg, _)| arg == name) {
+                    Some((_, value)) => {
+                        let _ = write!(result, "{value}");
+                    }
+                    None => {
+                        let _ = write!(result, "{{${name}}}");
+                    }
+                },
+                Part::Reference(key) => match lookup(key) {
+                    Some(message) if depth < MAX_DEPTH => {
+                        message.write(result, args, lookup, depth + 1)
+                    }
+                    _ => {
+                        let _ = write!(result, "{{{key}}}");
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl From<&str> for Message {
+    fn from(text: &str) -> Self {
+        Self::text(text)
+    }
+}
+
+impl From<String> for Message {
+    fn from(text: String) -> Self {
+        Self::text(text)
+    }
+}
+
+fn unescape(literal: &str) -> anyhow::Result<String> {
+    let mut result = String::new();
+    let mut chars = literal.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('"') => result.push('"'),
+            Some('\\') => result.push('\\'),
+            Some('u') => {
+                let code: String = chars.by_ref().take(4).collect();
+                let c = u32::from_str_radix(&code, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| anyhow::anyhow!("Invalid escape \\u{code}"))?;
+                result.push(c);
+            }
+            c => anyhow::bail!(
+                "Invalid escape \\{}",
+                c.map(String::from).unwrap_or_default()
+            ),
+        }
+    }
+    Ok(result)
+}
+
+pub(crate) fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// `message`, `-term` or `message.attribute`
+fn is_reference(key: &str) -> bool {
+    let key = key.strip_prefix('-').unwrap_or(key);
+    let (id, attribute) = key.split_once('.').unwrap_or((key, "a"));
+    is_identifier(id) && is_identifier(attribute)
+}
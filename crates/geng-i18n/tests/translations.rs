@@ -0,0 +1,95 @@
+use geng_i18n::*;
+
+#[test]
+fn fluent() {
+    let translations = Translations::from_fluent(
+        r#"
+# Comment
+-brand = Geng
+hello = Hello, { $name }!
+about = About { -brand }
+multiline =
+    First line
+    second line
+login =
+    .title = Log in to { -brand }
+    .placeholder = Name
+braces = {"{"}literal{"}"}
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        translations.format("hello", &[("name", &"world")]).unwrap(),
+        "Hello, world!"
+    );
+    assert_eq!(
+        translations.format("hello", &[]).unwrap(),
+        "Hello, {$name}!"
+    );
+    assert_eq!(translations.format("about", &[]).unwrap(), "About Geng");
+    assert_eq!(
+        translations.format("multiline", &[]).unwrap(),
+        "First line\nsecond line"
+    );
+    assert!(!translations.contains("login"));
+    assert_eq!(
+        translations.format("login.title", &[]).unwrap(),
+        "Log in to Geng"
+    );
+    assert_eq!(translations.format("braces", &[]).unwrap(), "{literal}");
+
+    assert!(Translations::from_fluent("count = { $n ->\n *[other] Many\n}").is_err());
+    assert!(Translations::from_fluent("broken = { $name").is_err());
+    assert!(Translations::from_fluent("empty =").is_err());
+}
+
+#[test]
+fn toml() {
+    let translations = Translations::from_toml(
+        r#"
+title = "Game"
+[menu]
+play = "Play { title }"
+score = "Score: { $points }"
+"#,
+    )
+    .unwrap();
+    assert_eq!(translations.format("menu.play", &[]).unwrap(), "Play Game");
+    assert_eq!(
+        translations
+            .format("menu.score", &[("points", &42)])
+            .unwrap(),
+        "Score: 42"
+    );
+    assert!(Translations::from_toml("number = 5").is_err());
+}
+
+#[test]
+fn locale_fallback() {
+    let localization = Localization::new("de-AT");
+    localization.add("en", Translations::from_toml(r#"yes = "Yes""#).unwrap());
+    localization.add(
+        "de",
+        Translations::from_toml(
+            r#"
+yes = "Ja"
+greeting = "Hallo"
+"#,
+        )
+        .unwrap(),
+    );
+    localization.add(
+        "de_AT",
+        Translations::from_toml(r#"greeting = "Servus""#).unwrap(),
+    );
+    localization.set_fallback_locale(Some("en"));
+    assert_eq!(localization.get("greeting"), "Servus");
+    assert_eq!(localization.get("yes"), "Ja");
+    assert_eq!(localization.get("missing"), "missing");
+
+    let revision = localization.revision();
+    localization.set_locale("fr");
+    assert!(localization.revision() > revision);
+    assert_eq!(localization.get("yes"), "Yes");
+    assert_eq!(localization.get("greeting"), "greeting");
+}
@@ -0,0 +1,104 @@
+use super::*;
+
+type Hook<T> = Box<dyn FnMut(&T)>;
+
+/// Saves a slot periodically while there are unsaved changes,
+/// and when the window loses focus or is about to close.
+///
+/// Call [Autosave::update] and [Autosave::handle_event] from the corresponding `State` methods
+pub struct Autosave<T> {
+    slots: Slots<T>,
+    slot: String,
+    /// Seconds between saves
+    pub interval: f64,
+    timer: f64,
+    dirty: bool,
+    hooks: Vec<Hook<T>>,
+}
+
+impl<T: Serialize + DeserializeOwned> Autosave<T> {
+    pub fn new(slots: Slots<T>, slot: &str, interval: f64) -> Self {
+        Self {
+            slots,
+            slot: slot.to_owned(),
+            interval,
+            timer: 0.0,
+            dirty: false,
+            hooks: Vec::new(),
+        }
+    }
+
+    pub fn slots(&self) -> &Slots<T> {
+        &self.slots
+    }
+
+    pub fn slot(&self) -> &str {
+        &self.slot
+    }
+
+    /// Switch to saving into `slot`, unsaved changes are kept
+    pub fn set_slot(&mut self, slot: &str) {
+        self.slot = slot.to_owned();
+    }
+
+    /// Load the current slot
+    pub fn load(&self) -> anyhow::Result<Option<T>> {
+        self.slots.load(&self.slot)
+    }
+
+    /// Record that the data has changed and should be saved
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Call `f` after every save, for example to show a save indicator
+    pub fn on_save(&mut self, f: impl FnMut(&T) + 'static) {
+        self.hooks.push(Box::new(f));
+    }
+
+    /// Save if there are changes and the interval has passed since the last save.
+    ///
+    /// Errors are logged and saving is retried after another interval
+    pub fn update(&mut self, delta_time: f64, data: &T) {
+        self.timer += delta_time;
+        if self.dirty && self.timer >= self.interval {
+            self.flush_logged(data);
+        }
+    }
+
+    /// Save unsaved changes when the window loses focus or is about to close
+    pub fn handle_event(&mut self, event: &geng_window::Event, data: &T) {
+        if let geng_window::Event::Focused(false) | geng_window::Event::CloseRequested = event {
+            self.flush_logged(data);
+        }
+    }
+
+    /// Save now if there are unsaved changes
+    pub fn flush(&mut self, data: &T) -> anyhow::Result<()> {
+        if self.dirty {
+            self.save(data)?;
+        }
+        Ok(())
+    }
+
+    /// Save now
+    pub fn save(&mut self, data: &T) -> anyhow::Result<()> {
+        self.timer = 0.0;
+        self.slots.save(&self.slot, data)?;
+        self.dirty = false;
+        for hook in &mut self.hooks {
+            hook(data);
+        }
+        Ok(())
+    }
+
+    fn flush_logged(&mut self, data: &T) {
+        if let Err(e) = self.flush(data) {
+            log::error!("Autosave to {:?} failed: {e:?}", self.slot);
+        }
+    }
+}
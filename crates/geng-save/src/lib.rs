@@ -0,0 +1,89 @@
+//! Save games and settings: named slots of versioned serde data, see [Slots] and [Autosave]
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::rc::Rc;
+
+mod autosave;
+mod platform;
+mod slots;
+
+pub use autosave::*;
+pub use slots::*;
+
+/// Where slots are stored: a directory in [batbox_preferences::base_path] natively,
+/// `localStorage` on the web.
+///
+/// Writes are atomic, a slot either has the old or the new data
+#[derive(Clone)]
+pub struct Storage {
+    backend: Rc<platform::Backend>,
+}
+
+impl Storage {
+    pub fn new() -> Self {
+        Self {
+            backend: Rc::new(platform::Backend::new()),
+        }
+    }
+
+    /// Store slots in the directory `path`
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn at(path: impl Into<std::path::PathBuf>) -> Self {
+        Self {
+            backend: Rc::new(platform::Backend::at(path)),
+        }
+    }
+
+    /// Separate set of slots, like `saves` and `settings`
+    pub fn namespace(&self, name: &str) -> anyhow::Result<Self> {
+        check_name(name)?;
+        Ok(Self {
+            backend: Rc::new(self.backend.namespace(name)),
+        })
+    }
+
+    pub fn read(&self, slot: &str) -> anyhow::Result<Option<String>> {
+        check_name(slot)?;
+        self.backend.read(slot)
+    }
+
+    pub fn write(&self, slot: &str, data: &str) -> anyhow::Result<()> {
+        check_name(slot)?;
+        self.backend.write(slot, data)
+    }
+
+    /// Remove the slot, does nothing if it does not exist
+    pub fn remove(&self, slot: &str) -> anyhow::Result<()> {
+        check_name(slot)?;
+        self.backend.remove(slot)
+    }
+
+    pub fn exists(&self, slot: &str) -> anyhow::Result<bool> {
+        Ok(self.read(slot)?.is_some())
+    }
+
+    /// Names of all slots, sorted
+    pub fn slots(&self) -> anyhow::Result<Vec<String>> {
+        let mut slots = self.backend.slots()?;
+        slots.retain(|slot| check_name(slot).is_ok());
+        slots.sort();
+        Ok(slots)
+    }
+}
+
+impl Default for Storage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Slot and namespace names are used as file names,
+/// so only ASCII letters, digits, `-` and `_` are allowed
+fn check_name(name: &str) -> anyhow::Result<()> {
+    let valid = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+    if name.is_empty() || !name.chars().all(valid) {
+        anyhow::bail!(
+            "Invalid slot name {name:?}, only ASCII letters, digits, - and _ are allowed"
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use super::*;
+
+use std::io::Write;
+use std::path::PathBuf;
+
+const EXTENSION: &str = "json";
+
+/// A directory with a `<slot>.json` file per slot
+pub struct Backend {
+    path: PathBuf,
+}
+
+impl Backend {
+    pub fn new() -> Self {
+        Self::at(batbox_preferences::base_path().join("saves"))
+    }
+
+    pub fn at(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn namespace(&self, name: &str) -> Self {
+        Self::at(self.path.join(name))
+    }
+
+    fn file(&self, slot: &str) -> PathBuf {
+        self.path.join(format!("{slot}.{EXTENSION}"))
+    }
+
+    pub fn read(&self, slot: &str) -> anyhow::Result<Option<String>> {
+        match std::fs::read_to_string(self.file(slot)) {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write to a temporary file first and rename it over the slot,
+    /// so that a crash never leaves a partially written save
+    pub fn write(&self, slot: &str, data: &str) -> anyhow::Result<()> {
+        std::fs::create_dir_all(&self.path)?;
+        let path = self.file(slot);
+        let tmp = self.path.join(format!("{slot}.{EXTENSION}.tmp"));
+        let mut file = std::fs::File::create(&tmp)?;
+        file.write_all(data.as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    pub fn remove(&self, slot: &str) -> anyhow::Result<()> {
+        match std::fs::remove_file(self.file(slot)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn slots(&self) -> anyhow::Result<Vec<String>> {
+        let entries = match std::fs::read_dir(&self.path) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut slots = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(EXTENSION) {
+                continue;
+            }
+            if let Some(slot) = path.file_stem().and_then(|stem| stem.to_str()) {
+                slots.push(slot.to_owned());
+            }
+        }
+        Ok(slots)
+    }
+}
+
+/// Seconds since the unix epoch
+pub fn now() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0.0, |duration| duration.as_secs_f64())
+}
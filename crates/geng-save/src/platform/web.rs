@@ -0,0 +1,75 @@
+use super::*;
+
+/// `localStorage` entries named `<prefix><slot>`
+pub struct Backend {
+    prefix: String,
+}
+
+impl Backend {
+    pub fn new() -> Self {
+        Self::with_prefix("geng-save/")
+    }
+
+    pub fn with_prefix(prefix: &str) -> Self {
+        Self {
+            prefix: prefix.to_owned(),
+        }
+    }
+
+    pub fn namespace(&self, name: &str) -> Self {
+        Self::with_prefix(&format!("{}{name}/", self.prefix))
+    }
+
+    /// Looked up on every access, it is unavailable in some private browsing modes
+    fn storage(&self) -> anyhow::Result<web_sys::Storage> {
+        web_sys::window()
+            .ok_or_else(|| anyhow::anyhow!("No window"))?
+            .local_storage()
+            .map_err(js_error)?
+            .ok_or_else(|| anyhow::anyhow!("localStorage is not available"))
+    }
+
+    pub fn read(&self, slot: &str) -> anyhow::Result<Option<String>> {
+        self.storage()?
+            .get_item(&format!("{}{slot}", self.prefix))
+            .map_err(js_error)
+    }
+
+    /// A single `setItem` call, which replaces the value atomically
+    pub fn write(&self, slot: &str, data: &str) -> anyhow::Result<()> {
+        self.storage()?
+            .set_item(&format!("{}{slot}", self.prefix), data)
+            .map_err(js_error)
+    }
+
+    pub fn remove(&self, slot: &str) -> anyhow::Result<()> {
+        self.storage()?
+            .remove_item(&format!("{}{slot}", self.prefix))
+            .map_err(js_error)
+    }
+
+    pub fn slots(&self) -> anyhow::Result<Vec<String>> {
+        let storage = self.storage()?;
+        let mut slots = Vec::new();
+        for index in 0..storage.length().map_err(js_error)? {
+            let Some(key) = storage.key(index).map_err(js_error)? else {
+                continue;
+            };
+            // Nested namespaces have a `/` after the prefix
+            match key.strip_prefix(&self.prefix) {
+                Some(slot) if !slot.contains('/') => slots.push(slot.to_owned()),
+                _ => {}
+            }
+        }
+        Ok(slots)
+    }
+}
+
+fn js_error(error: wasm_bindgen::JsValue) -> anyhow::Error {
+    anyhow::anyhow!("{error:?}")
+}
+
+/// Seconds since the unix epoch
+pub fn now() -> f64 {
+    js_sys::Date::now() / 1000.0
+}
@@ -0,0 +1,145 @@
+use super::*;
+
+use std::collections::BTreeMap;
+
+type Migration = Box<dyn Fn(serde_json::Value) -> anyhow::Result<serde_json::Value>>;
+
+/// What is stored in a slot, `data` is kept as JSON until migrated
+#[derive(Serialize, Deserialize)]
+struct Envelope<T> {
+    version: u32,
+    /// Seconds since the unix epoch
+    saved_at: f64,
+    data: T,
+}
+
+/// Metadata of a saved slot, for save/load menus
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotInfo {
+    pub name: String,
+    /// Version of the data when it was saved
+    pub version: u32,
+    /// Seconds since the unix epoch
+    pub saved_at: f64,
+}
+
+/// Slots of `T` saved as JSON with a version number.
+///
+/// Data saved by older versions is upgraded with [migrations](Slots::migration)
+/// before deserializing, one version at a time
+pub struct Slots<T> {
+    storage: Storage,
+    version: u32,
+    migrations: BTreeMap<u32, Migration>,
+    phantom_data: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T: Serialize + DeserializeOwned> Slots<T> {
+    /// `version` is the version of the current `T`
+    pub fn new(storage: &Storage, version: u32) -> Self {
+        Self {
+            storage: storage.clone(),
+            version,
+            migrations: BTreeMap::new(),
+            phantom_data: std::marker::PhantomData,
+        }
+    }
+
+    /// Add a migration of data saved with version `from` to version `from + 1`
+    pub fn migration(
+        mut self,
+        from: u32,
+        f: impl Fn(serde_json::Value) -> anyhow::Result<serde_json::Value> + 'static,
+    ) -> Self {
+        self.migrations.insert(from, Box::new(f));
+        self
+    }
+
+    pub fn storage(&self) -> &Storage {
+        &self.storage
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn save(&self, slot: &str, data: &T) -> anyhow::Result<()> {
+        let envelope = Envelope {
+            version: self.version,
+            saved_at: platform::now(),
+            data,
+        };
+        self.storage
+            .write(slot, &serde_json::to_string(&envelope)?)?;
+        log::debug!("Saved slot {slot:?}");
+        Ok(())
+    }
+
+    /// Load and migrate the data of `slot`, `None` if it was never saved
+    pub fn load(&self, slot: &str) -> anyhow::Result<Option<T>> {
+        let Some(json) = self.storage.read(slot)? else {
+            return Ok(None);
+        };
+        let load = || -> anyhow::Result<T> {
+            let envelope: Envelope<serde_json::Value> = serde_json::from_str(&json)?;
+            let data = self.migrate(envelope.version, envelope.data)?;
+            Ok(serde_json::from_value(data)?)
+        };
+        load()
+            .map(Some)
+            .map_err(|e| e.context(format!("Failed to load slot {slot:?}")))
+    }
+
+    fn migrate(
+        &self,
+        version: u32,
+        mut data: serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        if version > self.version {
+            anyhow::bail!(
+                "Saved with version {version}, newer than the current version {}",
+                self.version,
+            );
+        }
+        for from in version..self.version {
+            let Some(migration) = self.migrations.get(&from) else {
+                anyhow::bail!("No migration from version {from} to {}", from + 1);
+            };
+            data = migration(data)
+                .map_err(|e| e.context(format!("Migration from version {from} failed")))?;
+        }
+        Ok(data)
+    }
+
+    pub fn info(&self, slot: &str) -> anyhow::Result<Option<SlotInfo>> {
+        let Some(json) = self.storage.read(slot)? else {
+            return Ok(None);
+        };
+        let envelope: Envelope<serde::de::IgnoredAny> = serde_json::from_str(&json)
+            .map_err(|e| anyhow::anyhow!("Failed to read slot {slot:?}: {e}"))?;
+        Ok(Some(SlotInfo {
+            name: slot.to_owned(),
+            version: envelope.version,
+            saved_at: envelope.saved_at,
+        }))
+    }
+
+    /// Info of all slots in the storage, most recently saved first.
+    /// Slots that can not be read are skipped
+    pub fn list(&self) -> anyhow::Result<Vec<SlotInfo>> {
+        let mut result = Vec::new();
+        for slot in self.storage.slots()? {
+            match self.info(&slot) {
+                Ok(Some(info)) => result.push(info),
+                Ok(None) => {}
+                Err(e) => log::warn!("{e:?}"),
+            }
+        }
+        result.sort_by(|a, b| b.saved_at.total_cmp(&a.saved_at));
+        Ok(result)
+    }
+
+    pub fn delete(&self, slot: &str) -> anyhow::Result<()> {
+        self.storage.remove(slot)
+    }
+}
@@ -0,0 +1,127 @@
+use geng_save::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct SaveV3 {
+    name: String,
+    level: u32,
+    coins: u64,
+}
+
+fn storage(test: &str) -> Storage {
+    let path = std::env::temp_dir().join(format!("geng-save-test-{test}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&path);
+    Storage::at(path)
+}
+
+fn slots(storage: &Storage) -> Slots<SaveV3> {
+    Slots::new(storage, 3)
+        // Version 1 had no coins
+        .migration(1, |mut data| {
+            data["coins"] = 0.into();
+            Ok(data)
+        })
+        // Version 2 called level `stage`
+        .migration(2, |mut data| {
+            let stage = data["stage"].take();
+            data["level"] = stage;
+            Ok(data)
+        })
+}
+
+#[test]
+fn save_and_load() {
+    let storage = storage("save");
+    let slots = slots(&storage);
+    assert_eq!(slots.load("first").unwrap(), None);
+    let save = SaveV3 {
+        name: "Hero".to_owned(),
+        level: 5,
+        coins: 100,
+    };
+    slots.save("first", &save).unwrap();
+    slots.save("second", &save).unwrap();
+    assert_eq!(slots.load("first").unwrap(), Some(save));
+    assert_eq!(storage.slots().unwrap(), ["first", "second"]);
+    let list = slots.list().unwrap();
+    assert_eq!(list.len(), 2);
+    assert!(list.iter().all(|info| info.version == 3));
+
+    slots.delete("first").unwrap();
+    assert_eq!(storage.slots().unwrap(), ["second"]);
+    assert!(slots
+        .save(
+            "../escape",
+            &SaveV3 {
+                name: String::new(),
+                level: 0,
+                coins: 0
+            }
+        )
+        .is_err());
+
+    let settings = storage.namespace("settings").unwrap();
+    settings.write("audio", "{}").unwrap();
+    assert_eq!(settings.slots().unwrap(), ["audio"]);
+    assert_eq!(storage.slots().unwrap(), ["second"]);
+}
+
+#[test]
+fn migrations() {
+    let storage = storage("migrations");
+    storage
+        .write(
+            "old",
+            r#"{ "version": 1, "saved_at": 0, "data": { "name": "Old", "stage": 2 } }"#,
+        )
+        .unwrap();
+    assert_eq!(
+        slots(&storage).load("old").unwrap(),
+        Some(SaveV3 {
+            name: "Old".to_owned(),
+            level: 2,
+            coins: 0,
+        })
+    );
+
+    // Missing migration
+    let slots_without_migrations = Slots::<SaveV3>::new(&storage, 3);
+    assert!(slots_without_migrations.load("old").is_err());
+
+    storage
+        .write("new", r#"{ "version": 4, "saved_at": 0, "data": {} }"#)
+        .unwrap();
+    assert!(slots(&storage).load("new").is_err());
+}
+
+#[test]
+fn autosave() {
+    let storage = storage("autosave");
+    let mut autosave = Autosave::new(slots(&storage), "auto", 10.0);
+    let saves = std::rc::Rc::new(std::cell::Cell::new(0));
+    autosave.on_save({
+        let saves = saves.clone();
+        move |_| saves.set(saves.get() + 1)
+    });
+    let mut save = SaveV3 {
+        name: "Hero".to_owned(),
+        level: 1,
+        coins: 0,
+    };
+    autosave.update(20.0, &save);
+    assert_eq!(saves.get(), 0, "nothing to save");
+
+    save.coins = 10;
+    autosave.mark_dirty();
+    autosave.update(20.0, &save);
+    assert_eq!(saves.get(), 1);
+    assert!(!autosave.is_dirty());
+
+    save.coins = 20;
+    autosave.mark_dirty();
+    autosave.update(1.0, &save);
+    assert_eq!(saves.get(), 1, "interval has not passed");
+    autosave.handle_event(&geng_window::Event::Focused(false), &save);
+    assert_eq!(saves.get(), 2);
+    assert_eq!(autosave.load().unwrap(), Some(save));
+}
@@ -0,0 +1,11 @@
+#if __VERSION__ >= 150
+  #define texture2D texture
+  #ifdef FRAGMENT_SHADER
+    #define varying in
+    #define gl_FragColor frag_color
+    out vec4 gl_FragColor;
+  #else
+    #define attribute in
+    #define varying out
+  #endif
+#endif
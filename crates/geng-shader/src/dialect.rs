@@ -0,0 +1,376 @@
+use super::*;
+
+/// GLSL dialect that modern shaders are translated to.
+///
+/// Shaders starting with a `#version` directive are treated as modern GLSL
+/// (`in`/`out`, `texture()`, a declared fragment output) and translated,
+/// shaders without it are compiled as is with the legacy prefix
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Dialect {
+    /// `#version 330 core`
+    Gl33,
+    /// `#version 300 es`, GLES 3.0 / WebGL2
+    Gles30,
+    /// `#version 100`, GLES 2.0 / WebGL1
+    WebGl1,
+}
+
+impl Dialect {
+    pub const ALL: [Self; 3] = [Self::Gl33, Self::Gles30, Self::WebGl1];
+
+    /// Best dialect supported by the context
+    pub fn for_context(ugli: &Ugli) -> Self {
+        #[cfg(target_arch = "wasm32")]
+        if ugli.is_webgl2() {
+            return Self::Gles30;
+        }
+        let _ = ugli;
+        if cfg!(any(target_arch = "wasm32", target_os = "android")) {
+            Self::WebGl1
+        } else {
+            Self::Gl33
+        }
+    }
+
+    fn header(&self, shader_type: ugli::ShaderType) -> String {
+        let precision = "precision highp int;\nprecision highp float;\n";
+        match (self, shader_type) {
+            (Self::Gl33, _) => "#version 330 core\n".to_owned(),
+            (Self::Gles30, _) => format!("#version 300 es\n{precision}"),
+            (Self::WebGl1, ugli::ShaderType::Vertex) => format!("#version 100\n{precision}"),
+            (Self::WebGl1, ugli::ShaderType::Fragment) => format!(
+                "#version 100\n#extension GL_OES_standard_derivatives : enable\n{precision}"
+            ),
+        }
+    }
+}
+
+/// Whether `source` is written in modern GLSL, i.e. starts with a `#version` directive
+pub(crate) fn is_modern(source: &str) -> bool {
+    source
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with("//"))
+        .is_some_and(is_version_directive)
+}
+
+pub(crate) fn is_version_directive(line: &str) -> bool {
+    line.trim_start()
+        .strip_prefix('#')
+        .is_some_and(|rest| rest.trim_start().starts_with("version"))
+}
+
+pub(crate) fn push_header(
+    output: &mut Preprocessed,
+    dialect: Dialect,
+    shader_type: ugli::ShaderType,
+) {
+    output.push_lines("<prefix>", &dialect.header(shader_type));
+}
+
+/// Files of the [Library] written to work with every dialect, they are not checked
+/// for unsupported features (the prelude defines `inverse` for GLSL ES 1.00)
+const BUILTIN_FILES: &[&str] = &["<compat>", "<prelude>", "<noise>"];
+
+/// Identifiers that can not be translated to GLSL ES 1.00
+const WEBGL1_UNSUPPORTED: &[&str] = &[
+    "uint",
+    "uvec2",
+    "uvec3",
+    "uvec4",
+    "flat",
+    "noperspective",
+    "switch",
+    "texelFetch",
+    "textureSize",
+    "textureGrad",
+    "textureOffset",
+    "isampler2D",
+    "usampler2D",
+    "sampler2DArray",
+    "sampler3D",
+    "gl_VertexID",
+    "gl_InstanceID",
+    "transpose",
+    "mat2x3",
+    "mat2x4",
+    "mat3x2",
+    "mat3x4",
+    "mat4x2",
+    "mat4x3",
+];
+
+/// Identifiers that are not available in GLSL ES 3.00
+const GLES30_UNSUPPORTED: &[&str] = &[
+    "double", "dvec2", "dvec3", "dvec4", "dmat2", "dmat3", "dmat4",
+];
+
+/// Rewrite modern GLSL in `source` for `dialect`, reporting unsupported features
+pub(crate) fn translate(
+    source: &mut Preprocessed,
+    dialect: Dialect,
+    shader_type: ugli::ShaderType,
+) -> anyhow::Result<()> {
+    let mut translator = Translator {
+        dialect,
+        shader_type,
+        in_comment: false,
+        depth: 0,
+        conditions: Vec::new(),
+        cube_samplers: HashSet::new(),
+        fragment_output: None,
+        errors: Vec::new(),
+    };
+    let lines: Vec<String> = source
+        .lines()
+        .enumerate()
+        .map(|(index, line)| translator.line(line, index))
+        .collect();
+    translator.errors.retain(|&(index, _)| {
+        !source
+            .location(index + 1)
+            .is_some_and(|(file, _)| BUILTIN_FILES.contains(&file))
+    });
+    if !translator.errors.is_empty() {
+        let errors: Vec<String> = translator
+            .errors
+            .into_iter()
+            .map(|(index, error)| match source.location(index + 1) {
+                Some((file, line)) => format!("{file}:{line}: {error}"),
+                None => error,
+            })
+            .collect();
+        anyhow::bail!(
+            "{shader_type:?} shader uses features not supported by {dialect:?}:\n{}",
+            errors.join("\n"),
+        );
+    }
+    source.replace_lines(lines);
+    Ok(())
+}
+
+struct Translator {
+    dialect: Dialect,
+    shader_type: ugli::ShaderType,
+    in_comment: bool,
+    /// Brace and paren depth, declarations are only looked for at 0
+    depth: i32,
+    /// Known values of `#ifdef VERTEX_SHADER`/`FRAGMENT_SHADER` blocks, `None` for other conditions
+    conditions: Vec<Option<bool>>,
+    cube_samplers: HashSet<String>,
+    fragment_output: Option<String>,
+    errors: Vec<(usize, String)>,
+}
+
+impl Translator {
+    fn stage_define(&self) -> &'static str {
+        match self.shader_type {
+            ugli::ShaderType::Vertex => "VERTEX_SHADER",
+            ugli::ShaderType::Fragment => "FRAGMENT_SHADER",
+        }
+    }
+
+    fn condition(&self, name: &str) -> Option<bool> {
+        match name {
+            "VERTEX_SHADER" | "FRAGMENT_SHADER" => Some(name == self.stage_define()),
+            _ => None,
+        }
+    }
+
+    fn directive(&mut self, directive: &str) {
+        let mut tokens = directive
+            .split(|c: char| c.is_whitespace() || "()!".contains(c))
+            .filter(|token| !token.is_empty());
+        match tokens.next() {
+            Some("ifdef") => {
+                let condition = tokens.next().and_then(|name| self.condition(name));
+                self.conditions.push(condition);
+            }
+            Some("ifndef") => {
+                let condition = tokens.next().and_then(|name| self.condition(name));
+                self.conditions.push(condition.map(|value| !value));
+            }
+            Some("if") => {
+                let condition = match (tokens.next(), tokens.next(), tokens.next()) {
+                    (Some("defined"), Some(name), None) if !directive.contains('!') => {
+                        self.condition(name)
+                    }
+                    _ => None,
+                };
+                self.conditions.push(condition);
+            }
+            Some("elif") => {
+                if let Some(condition) = self.conditions.last_mut() {
+                    *condition = None;
+                }
+            }
+            Some("else") => {
+                if let Some(condition) = self.conditions.last_mut() {
+                    *condition = condition.map(|value| !value);
+                }
+            }
+            Some("endif") => {
+                self.conditions.pop();
+            }
+            _ => {}
+        }
+    }
+
+    fn line(&mut self, line: &str, index: usize) -> String {
+        if !self.in_comment {
+            if let Some(directive) = line.trim_start().strip_prefix('#') {
+                self.directive(directive);
+                return line.to_owned();
+            }
+        }
+        if self.conditions.contains(&Some(false)) {
+            return line.to_owned();
+        }
+        let tokens = tokenize(line, &mut self.in_comment);
+        let mut edits: Vec<(usize, usize, String)> = Vec::new();
+        for (i, &(start, token)) in tokens.iter().enumerate() {
+            let next = tokens.get(i + 1).map(|&(_, token)| token);
+            match token {
+                "{" | "(" => self.depth += 1,
+                "}" | ")" => self.depth -= 1,
+                _ => {}
+            }
+            if self.dialect == Dialect::Gles30 && GLES30_UNSUPPORTED.contains(&token) {
+                self.errors
+                    .push((index, format!("`{token}` is not supported")));
+            }
+            if self.dialect != Dialect::WebGl1 {
+                continue;
+            }
+            if WEBGL1_UNSUPPORTED.contains(&token) {
+                self.errors
+                    .push((index, format!("`{token}` is not supported")));
+            }
+            let global = self.depth == 0;
+            let replacement = match (token, self.shader_type) {
+                ("in", ugli::ShaderType::Vertex) if global => "attribute",
+                ("in", ugli::ShaderType::Fragment) if global => "varying",
+                ("out", ugli::ShaderType::Vertex) if global => "varying",
+                ("out", ugli::ShaderType::Fragment) if global => {
+                    return self.fragment_output(line, &tokens, index);
+                }
+                ("layout", ugli::ShaderType::Fragment)
+                    if global && tokens.iter().any(|&(_, token)| token == "out") =>
+                {
+                    return self.fragment_output(line, &tokens, index);
+                }
+                ("layout", _) => {
+                    self.errors.push((
+                        index,
+                        "layout qualifiers are only supported for the fragment output".to_owned(),
+                    ));
+                    continue;
+                }
+                ("samplerCube", _) => {
+                    if let Some(name) = next {
+                        self.cube_samplers.insert(name.to_owned());
+                    }
+                    continue;
+                }
+                ("texture", _) if next == Some("(") => {
+                    let sampler = tokens.get(i + 2).map(|&(_, token)| token);
+                    match sampler.is_some_and(|sampler| self.cube_samplers.contains(sampler)) {
+                        true => "textureCube",
+                        false => "texture2D",
+                    }
+                }
+                ("textureProj", _) if next == Some("(") => "texture2DProj",
+                ("textureLod", ugli::ShaderType::Vertex) if next == Some("(") => "texture2DLod",
+                ("textureLod", ugli::ShaderType::Fragment) => {
+                    self.errors.push((
+                        index,
+                        "`textureLod` is only supported in vertex shaders".to_owned(),
+                    ));
+                    continue;
+                }
+                ("%", _) => {
+                    self.errors
+                        .push((index, "`%` operator is not supported, use mod()".to_owned()));
+                    continue;
+                }
+                _ => continue,
+            };
+            edits.push((start, start + token.len(), replacement.to_owned()));
+        }
+        let mut result = String::new();
+        let mut last = 0;
+        for (start, end, replacement) in edits {
+            result.push_str(&line[last..start]);
+            result.push_str(&replacement);
+            last = end;
+        }
+        result.push_str(&line[last..]);
+        result
+    }
+
+    /// `[layout(location = 0)] out vec4 name;` is turned into `#define name gl_FragColor`
+    fn fragment_output(&mut self, line: &str, tokens: &[(usize, &str)], index: usize) -> String {
+        let declaration: Vec<&str> = tokens.iter().map(|&(_, token)| token).collect();
+        let declaration = match declaration.iter().position(|&token| token == ")") {
+            Some(end) if declaration.first() == Some(&"layout") => &declaration[end + 1..],
+            _ => &declaration[..],
+        };
+        let name = match declaration {
+            ["out", _, name, ";"] => name,
+            _ => {
+                self.errors.push((
+                    index,
+                    "fragment output must be declared on its own line as `out vec4 name;`"
+                        .to_owned(),
+                ));
+                return line.to_owned();
+            }
+        };
+        if self.fragment_output.is_some() {
+            self.errors.push((
+                index,
+                "multiple fragment outputs are not supported".to_owned(),
+            ));
+        }
+        self.fragment_output = Some(name.to_string());
+        format!("#define {name} gl_FragColor")
+    }
+}
+
+/// Identifiers, numbers and punctuation of a line with their byte offsets, skipping comments
+fn tokenize<'a>(line: &'a str, in_comment: &mut bool) -> Vec<(usize, &'a str)> {
+    let mut tokens = Vec::new();
+    let mut chars = line.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if *in_comment {
+            if c == '*' && chars.next_if(|&(_, c)| c == '/').is_some() {
+                *in_comment = false;
+            }
+            continue;
+        }
+        if c == '/' {
+            if chars.next_if(|&(_, c)| c == '/').is_some() {
+                break;
+            }
+            if chars.next_if(|&(_, c)| c == '*').is_some() {
+                *in_comment = true;
+                continue;
+            }
+        }
+        if c.is_whitespace() {
+            continue;
+        }
+        let mut end = start + c.len_utf8();
+        let number = c.is_ascii_digit()
+            || c == '.' && chars.peek().is_some_and(|&(_, c)| c.is_ascii_digit());
+        if number || c.is_alphanumeric() || c == '_' {
+            while let Some((index, c)) =
+                chars.next_if(|&(_, c)| c.is_alphanumeric() || c == '_' || number && c == '.')
+            {
+                end = index + c.len_utf8();
+            }
+        }
+        tokens.push((start, &line[start..end]));
+    }
+    tokens
+}
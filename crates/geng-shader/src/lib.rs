@@ -1,29 +1,66 @@
-use std::collections::HashMap;
+use anyhow::Context as _;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 use ugli::Ugli;
 
+mod dialect;
+mod preprocess;
+#[cfg(not(target_arch = "wasm32"))]
+mod validate;
+
+pub use dialect::Dialect;
+pub use preprocess::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use validate::*;
+
 pub struct Library {
-    ugli: Ugli,
+    ugli: Option<Ugli>,
     files: HashMap<String, String>,
     prefix: Option<(String, String)>, // TODO remove?
+    dialect: Dialect,
+    cache: RefCell<HashMap<CacheKey, Rc<ugli::Program>>>,
 }
 
+/// Source and defines of a program compiled with [Library::compile_with_defines]
+type CacheKey = (String, Vec<(String, String)>);
+
 impl Library {
     pub fn empty(ugli: &Ugli) -> Self {
+        Self::empty_impl(Some(ugli), Dialect::for_context(ugli))
+    }
+    fn empty_impl(ugli: Option<&Ugli>, dialect: Dialect) -> Self {
         Self {
-            ugli: ugli.clone(),
+            ugli: ugli.cloned(),
             files: HashMap::new(),
             prefix: None,
+            dialect,
+            cache: Default::default(),
         }
     }
     pub fn new(ugli: &Ugli, antialias: bool, prefix: Option<(String, String)>) -> Self {
-        let mut library = Self::empty(ugli);
+        Self::new_impl(Some(ugli), Dialect::for_context(ugli), antialias, prefix)
+    }
+    /// Library without a GL context targeting given dialect,
+    /// can be used to process and [validate](Library::validate_file) shaders but not compile them
+    pub fn offline(dialect: Dialect, antialias: bool) -> Self {
+        Self::new_impl(None, dialect, antialias, None)
+    }
+    fn new_impl(
+        ugli: Option<&Ugli>,
+        dialect: Dialect,
+        antialias: bool,
+        prefix: Option<(String, String)>,
+    ) -> Self {
+        let mut library = Self::empty_impl(ugli, dialect);
         let mut prelude = include_str!("prelude.glsl").to_owned();
         if antialias {
-            prelude = "#define GENG_ANTIALIAS\n".to_owned() + &prelude;
+            prelude.insert_str(0, "#define GENG_ANTIALIAS\n");
         }
-        fn default_prefix() -> (String, String) {
+        let default_prefix = || -> (String, String) {
             let common_glsl = "precision highp int;\nprecision highp float;\n";
-            if cfg!(any(target_arch = "wasm32", target_os = "android")) {
+            if dialect != Dialect::Gl33 {
                 (
                     format!("{common_glsl}#define VERTEX_SHADER\n"),
                     format!("#extension GL_OES_standard_derivatives : enable\n{common_glsl}#define FRAGMENT_SHADER\n"),
@@ -34,8 +71,9 @@ impl Library {
                     format!("#version 150\n{common_glsl}#define FRAGMENT_SHADER\n"),
                 )
             }
-        }
+        };
         library.prefix = Some(prefix.unwrap_or_else(default_prefix));
+        library.add("compat", include_str!("compat.glsl"));
         library.add("prelude", &prelude);
         library.add("noise", include_str!("noise.glsl"));
         library
@@ -45,66 +83,264 @@ impl Library {
         self.files.insert(file_name.to_owned(), source.to_owned());
     }
 
-    fn preprocess(&self, source: &str) -> Result<String, anyhow::Error> {
-        let mut result = String::new();
-        for line in source.lines() {
-            if line.starts_with("#include") {
-                let mut iter = line.split_whitespace();
-                iter.next();
-                let file = iter.next().expect("Expected path to include");
-                assert!(iter.next().is_none(), "Unexpected token");
-                assert!(
-                    file.starts_with('<') && file.ends_with('>'),
-                    "include path should be enclosed in angular brackets"
-                );
-                let file = file.trim_start_matches('<').trim_end_matches('>');
-                if let Some(file) = self.files.get(file) {
-                    result.push_str(&self.preprocess(file)?);
-                } else {
-                    anyhow::bail!("{:?} not found in shader library", file);
-                }
-            } else {
-                result.push_str(line);
-                result.push('\n');
-            }
-        }
-        Ok(result)
+    /// Dialect that shaders starting with `#version` are translated to
+    pub fn dialect(&self) -> Dialect {
+        self.dialect
     }
+    pub fn set_dialect(&mut self, dialect: Dialect) {
+        self.dialect = dialect;
+        self.clear_cache();
+    }
+
     pub fn process(
         &self,
         shader_type: ugli::ShaderType,
         source: &str,
     ) -> Result<String, anyhow::Error> {
-        let mut result = String::new();
-        if let Some((vertex_prefix, fragment_prefix)) = &self.prefix {
-            result.push_str(match shader_type {
-                ugli::ShaderType::Vertex => vertex_prefix,
-                ugli::ShaderType::Fragment => fragment_prefix,
-            });
-            result.push('\n');
+        Ok(self
+            .process_impl(shader_type, "<source>", None, source, &HashMap::new(), &[])?
+            .source()
+            .to_owned())
+    }
+
+    /// Process file at `path`, resolving `#include "path"` relative to it.
+    ///
+    /// `files` should contain the file itself and all files it includes, see [relative_includes].
+    /// `defines` are inserted as `#define NAME VALUE` before the prelude
+    pub fn process_file(
+        &self,
+        shader_type: ugli::ShaderType,
+        path: &Path,
+        files: &HashMap<PathBuf, String>,
+        defines: &[(&str, &str)],
+    ) -> Result<Preprocessed, anyhow::Error> {
+        let source = files
+            .get(path)
+            .ok_or_else(|| anyhow::anyhow!("{path:?} not found"))?;
+        self.process_impl(
+            shader_type,
+            &path.display().to_string(),
+            Some(path),
+            source,
+            files,
+            defines,
+        )
+    }
+
+    fn process_impl(
+        &self,
+        shader_type: ugli::ShaderType,
+        name: &str,
+        path: Option<&Path>,
+        source: &str,
+        files: &HashMap<PathBuf, String>,
+        defines: &[(&str, &str)],
+    ) -> Result<Preprocessed, anyhow::Error> {
+        let modern = dialect::is_modern(source);
+        let mut preprocessor = Preprocessor {
+            library: self,
+            files,
+            once: HashSet::new(),
+            stack: Vec::new(),
+            modern,
+            output: Preprocessed::default(),
+        };
+        if modern {
+            dialect::push_header(&mut preprocessor.output, self.dialect, shader_type);
+        } else if let Some((vertex_prefix, fragment_prefix)) = &self.prefix {
+            preprocessor.output.push_lines(
+                "<prefix>",
+                match shader_type {
+                    ugli::ShaderType::Vertex => vertex_prefix,
+                    ugli::ShaderType::Fragment => fragment_prefix,
+                },
+            );
+        }
+        preprocessor.output.push_lines(
+            "<prefix>",
+            match shader_type {
+                ugli::ShaderType::Vertex => "#define VERTEX_SHADER\n",
+                ugli::ShaderType::Fragment => "#define FRAGMENT_SHADER\n",
+            },
+        );
+        for (name, value) in defines {
+            preprocessor
+                .output
+                .push_lines("<defines>", &format!("#define {name} {value}"));
+        }
+        if !modern && self.files.contains_key("compat") {
+            preprocessor.file("<source>", None, "#include <compat>")?;
         }
-        result.push_str(match shader_type {
-            ugli::ShaderType::Vertex => "#define VERTEX_SHADER\n",
-            ugli::ShaderType::Fragment => "#define FRAGMENT_SHADER\n",
-        });
-        result.push_str(&self.preprocess("#include <prelude>")?);
-        result.push_str(&self.preprocess(source)?);
-        Ok(result)
+        preprocessor.file("<source>", None, "#include <prelude>")?;
+        preprocessor.file(name, path, source)?;
+        let mut output = preprocessor.output;
+        if modern {
+            dialect::translate(&mut output, self.dialect, shader_type)?;
+        }
+        Ok(output)
     }
+
     pub fn compile(&self, source: &str) -> Result<ugli::Program, anyhow::Error> {
-        let shader = |shader_type| -> anyhow::Result<ugli::Shader> {
-            Ok(ugli::Shader::new(
-                &self.ugli,
+        self.compile_uncached(source, &[])
+    }
+
+    /// Compile a permutation of `source` with given `#define NAME VALUE`s.
+    ///
+    /// Programs are cached by source and defines, so this can be called every frame
+    pub fn compile_with_defines(
+        &self,
+        source: &str,
+        defines: &[(&str, &str)],
+    ) -> Result<Rc<ugli::Program>, anyhow::Error> {
+        let key: CacheKey = (
+            source.to_owned(),
+            defines
+                .iter()
+                .map(|&(name, value)| (name.to_owned(), value.to_owned()))
+                .collect(),
+        );
+        if let Some(program) = self.cache.borrow().get(&key) {
+            return Ok(program.clone());
+        }
+        let program = Rc::new(self.compile_uncached(source, defines)?);
+        self.cache.borrow_mut().insert(key, program.clone());
+        Ok(program)
+    }
+
+    /// Drop all programs cached by [Library::compile_with_defines]
+    pub fn clear_cache(&self) {
+        self.cache.borrow_mut().clear();
+    }
+
+    fn compile_uncached(
+        &self,
+        source: &str,
+        defines: &[(&str, &str)],
+    ) -> Result<ugli::Program, anyhow::Error> {
+        let process = |shader_type| {
+            self.process_impl(
                 shader_type,
-                &self.process(shader_type, source)?,
-            )?)
+                "<source>",
+                None,
+                source,
+                &HashMap::new(),
+                defines,
+            )
+        };
+        self.compile_processed(
+            process(ugli::ShaderType::Vertex)?,
+            process(ugli::ShaderType::Fragment)?,
+        )
+    }
+
+    /// Compile file at `path`, see [Library::process_file]
+    pub fn compile_file(
+        &self,
+        path: &Path,
+        files: &HashMap<PathBuf, String>,
+        defines: &[(&str, &str)],
+    ) -> Result<ugli::Program, anyhow::Error> {
+        self.compile_processed(
+            self.process_file(ugli::ShaderType::Vertex, path, files, defines)?,
+            self.process_file(ugli::ShaderType::Fragment, path, files, defines)?,
+        )
+    }
+
+    fn compile_processed(
+        &self,
+        vertex: Preprocessed,
+        fragment: Preprocessed,
+    ) -> Result<ugli::Program, anyhow::Error> {
+        let ugli = self
+            .ugli
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Shader library has no GL context"))?;
+        let shader = |shader_type, source: &Preprocessed| -> anyhow::Result<ugli::Shader> {
+            ugli::Shader::new(ugli, shader_type, source.source()).map_err(|e| {
+                ugli::ShaderCompilationError {
+                    log: source.remap_log(&e.log),
+                }
+                .into()
+            })
         };
         Ok(ugli::Program::new(
-            &self.ugli,
+            ugli,
             [
-                &shader(ugli::ShaderType::Vertex)?,
-                &shader(ugli::ShaderType::Fragment)?,
+                &shader(ugli::ShaderType::Vertex, &vertex)?,
+                &shader(ugli::ShaderType::Fragment, &fragment)?,
             ],
         )?)
     }
 }
+
+struct Preprocessor<'a> {
+    library: &'a Library,
+    files: &'a HashMap<PathBuf, String>,
+    /// Files with `#pragma once` that were already included
+    once: HashSet<String>,
+    /// Files currently being processed, to detect recursive includes
+    stack: Vec<String>,
+    /// Source is modern GLSL to be translated, see [Dialect]
+    modern: bool,
+    output: Preprocessed,
+}
+
+impl Preprocessor<'_> {
+    fn file(&mut self, name: &str, path: Option<&Path>, source: &str) -> anyhow::Result<()> {
+        if self.once.contains(name) {
+            return Ok(());
+        }
+        if self.stack.iter().any(|file| file == name) {
+            anyhow::bail!("{name} includes itself");
+        }
+        self.stack.push(name.to_owned());
+        let file: Rc<str> = name.into();
+        for (index, line) in source.lines().enumerate() {
+            let location = || format!("{name}:{}", index + 1);
+            if self.modern && dialect::is_version_directive(line) {
+                // Replaced by the header of the target dialect
+                continue;
+            }
+            if is_pragma_once(line) {
+                self.once.insert(name.to_owned());
+                continue;
+            }
+            match Include::parse(line) {
+                None => self.output.push_line(&file, index + 1, line),
+                Some(include) => match include.with_context(location)? {
+                    Include::Library(include) => {
+                        let source = self
+                            .library
+                            .files
+                            .get(include)
+                            .ok_or_else(|| {
+                                anyhow::anyhow!("{include:?} not found in shader library")
+                            })
+                            .with_context(location)?;
+                        self.file(&format!("<{include}>"), None, source)
+                            .with_context(location)?;
+                    }
+                    Include::Relative(include) => {
+                        let path = path
+                            .ok_or_else(|| {
+                                anyhow::anyhow!(
+                                    "Relative include {include:?} in a file without a path"
+                                )
+                            })
+                            .with_context(location)?;
+                        let path = resolve_relative(path, include);
+                        let source = self
+                            .files
+                            .get(&path)
+                            .ok_or_else(|| anyhow::anyhow!("{path:?} not found"))
+                            .with_context(location)?;
+                        self.file(&path.display().to_string(), Some(&path), source)
+                            .with_context(location)?;
+                    }
+                },
+            }
+        }
+        self.stack.pop();
+        Ok(())
+    }
+}
@@ -1,15 +1,3 @@
-#if __VERSION__ >= 150
-  #define texture2D texture
-  #ifdef FRAGMENT_SHADER
-    #define varying in
-    #define gl_FragColor frag_color
-    out vec4 gl_FragColor;
-  #else
-    #define attribute in
-    #define varying out
-  #endif
-#endif
-
 #define PI 3.1415926535897932384626433832795
 
 vec2 rotate(vec2 v, float a)
@@ -166,7 +154,11 @@ vec4 smoothTexture2D(vec2 data_uv, sampler2D tex, ivec2 tex_size) {
     // compute bilinear sample uv coordinates
     vec2 uv = (floor(tx) + 0.5 + tx_offset) / tex_size_f;
     // sample the texture
+#if __VERSION__ >= 130
+    return texture(tex, uv);
+#else
     return texture2D(tex, uv);
+#endif
 }
 #endif
 
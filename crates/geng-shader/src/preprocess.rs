@@ -0,0 +1,165 @@
+use super::*;
+
+/// Shader source after resolving includes, remembers where every line came from
+/// so that driver errors can be mapped back to the original files
+#[derive(Debug, Clone, Default)]
+pub struct Preprocessed {
+    source: String,
+    lines: Vec<(Rc<str>, usize)>,
+}
+
+impl Preprocessed {
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// File name and line number (1-based) of a line (1-based) of the processed source
+    pub fn location(&self, line: usize) -> Option<(&str, usize)> {
+        let (file, line) = self.lines.get(line.checked_sub(1)?)?;
+        Some((file, *line))
+    }
+
+    /// Rewrite locations in a shader info log (`0:12`, `0(12)`) to `file:line`
+    pub fn remap_log(&self, log: &str) -> String {
+        log.lines()
+            .map(|line| self.remap_log_line(line).unwrap_or_else(|| line.to_owned()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn remap_log_line(&self, line: &str) -> Option<String> {
+        for (start, _) in line.match_indices('0') {
+            if line[..start]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_ascii_alphanumeric())
+            {
+                continue;
+            }
+            let rest = &line[start + 1..];
+            let close = match rest.chars().next() {
+                Some(':') => None,
+                Some('(') => Some(')'),
+                _ => continue,
+            };
+            let digits = &rest[1..];
+            let digits = &digits[..digits
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(digits.len())];
+            if digits.is_empty() {
+                continue;
+            }
+            let mut end = start + 2 + digits.len();
+            if let Some(close) = close {
+                if !line[end..].starts_with(close) {
+                    continue;
+                }
+                end += 1;
+            }
+            let (file, file_line) = self.location(digits.parse().ok()?)?;
+            return Some(format!(
+                "{}{file}:{file_line}{}",
+                &line[..start],
+                &line[end..]
+            ));
+        }
+        None
+    }
+
+    pub(crate) fn lines(&self) -> std::str::Lines<'_> {
+        self.source.lines()
+    }
+
+    /// Replace the source keeping the line mapping, number of lines must stay the same
+    pub(crate) fn replace_lines(&mut self, lines: Vec<String>) {
+        assert_eq!(lines.len(), self.lines.len());
+        self.source = lines.into_iter().map(|line| line + "\n").collect();
+    }
+
+    pub(crate) fn push_line(&mut self, file: &Rc<str>, line_number: usize, line: &str) {
+        self.source.push_str(line);
+        self.source.push('\n');
+        self.lines.push((file.clone(), line_number));
+    }
+
+    pub(crate) fn push_lines(&mut self, file: &str, source: &str) {
+        let file: Rc<str> = file.into();
+        for (index, line) in source.lines().enumerate() {
+            self.push_line(&file, index + 1, line);
+        }
+    }
+}
+
+/// Include directive target
+pub(crate) enum Include<'a> {
+    /// `#include <name>`, a file registered in the [Library]
+    Library(&'a str),
+    /// `#include "path"`, relative to the including file
+    Relative(&'a str),
+}
+
+impl<'a> Include<'a> {
+    /// Returns `None` if the line is not an include directive
+    pub fn parse(line: &'a str) -> Option<anyhow::Result<Self>> {
+        let rest = line.trim_start().strip_prefix('#')?.trim_start();
+        let rest = rest.strip_prefix("include")?;
+        if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+            return None;
+        }
+        let rest = rest.trim();
+        let parse = || {
+            if let Some(name) = rest.strip_prefix('<').and_then(|s| s.strip_suffix('>')) {
+                return Ok(Self::Library(name));
+            }
+            if let Some(path) = rest.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+                return Ok(Self::Relative(path));
+            }
+            anyhow::bail!("Expected #include <name> or #include \"path\", got {rest:?}")
+        };
+        Some(parse())
+    }
+}
+
+pub(crate) fn is_pragma_once(line: &str) -> bool {
+    line.trim_start()
+        .strip_prefix('#')
+        .is_some_and(|rest| rest.split_whitespace().eq(["pragma", "once"]))
+}
+
+/// Resolve `relative` against the directory of `path`, collapsing `.` and `..`
+pub fn resolve_relative(path: &Path, relative: &str) -> PathBuf {
+    let mut result = PathBuf::new();
+    let joined = path.parent().unwrap_or(Path::new("")).join(relative);
+    for component in joined.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => match result.components().next_back() {
+                Some(std::path::Component::Normal(_)) => {
+                    result.pop();
+                }
+                _ => result.push(".."),
+            },
+            component => result.push(component),
+        }
+    }
+    result
+}
+
+/// Paths of files included with `#include "path"` in `source` located at `path`.
+///
+/// Only looks at the given source, includes of the returned files need to be resolved separately
+pub fn relative_includes(path: &Path, source: &str) -> anyhow::Result<Vec<PathBuf>> {
+    let mut result = Vec::new();
+    for (index, line) in source.lines().enumerate() {
+        match Include::parse(line) {
+            Some(Ok(Include::Relative(relative))) => {
+                result.push(resolve_relative(path, relative));
+            }
+            Some(Err(e)) => {
+                return Err(e.context(format!("{}:{}", path.display(), index + 1)));
+            }
+            _ => {}
+        }
+    }
+    Ok(result)
+}
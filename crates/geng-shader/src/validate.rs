@@ -0,0 +1,92 @@
+use super::*;
+
+impl Library {
+    /// Check that both stages of the program at `path` compile for the library's dialect
+    /// using the glslang front-end, without a GPU. See [Library::process_file] for `files`
+    pub fn validate_file(
+        &self,
+        path: &Path,
+        files: &HashMap<PathBuf, String>,
+    ) -> anyhow::Result<()> {
+        for shader_type in [ugli::ShaderType::Vertex, ugli::ShaderType::Fragment] {
+            let source = self.process_file(shader_type, path, files, &[])?;
+            validate_stage(shader_type, &source).with_context(|| {
+                format!("{path:?} failed to validate as {shader_type:?} shader")
+            })?;
+        }
+        Ok(())
+    }
+}
+
+fn validate_stage(shader_type: ugli::ShaderType, source: &Preprocessed) -> anyhow::Result<()> {
+    let stage = match shader_type {
+        ugli::ShaderType::Vertex => glslang::ShaderStage::Vertex,
+        ugli::ShaderType::Fragment => glslang::ShaderStage::Fragment,
+    };
+    let compiler =
+        glslang::Compiler::acquire().context("Failed to initialize the glslang compiler")?;
+    let glsl = glslang::ShaderSource::from(source.source());
+    // No SPIR-V target, only check the source against its own `#version`
+    let options = glslang::CompilerOptions {
+        target: glslang::Target::None(None),
+        ..Default::default()
+    };
+    let input = glslang::ShaderInput::new(
+        &glsl,
+        stage,
+        &options,
+        None::<&[(&str, Option<&str>)]>,
+        None,
+    )?;
+    match compiler.create_shader(input) {
+        Ok(_) => Ok(()),
+        Err(glslang::error::GlslangError::ParseError(log)) => Err(ugli::ShaderCompilationError {
+            log: source.remap_log(log.log.trim()),
+        }
+        .into()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Validate every program in `dir` (recursively) for all [Dialect]s,
+/// meant to be called from a `#[test]`.
+///
+/// `.glsl` files with a `main` function are considered programs,
+/// other files are only validated through includes.
+/// `setup` can register additional library files, same as done at runtime.
+/// All failures are reported together
+pub fn validate_dir(dir: impl AsRef<Path>, setup: impl Fn(&mut Library)) -> anyhow::Result<()> {
+    let mut files = HashMap::new();
+    let mut queue = vec![dir.as_ref().to_owned()];
+    while let Some(dir) = queue.pop() {
+        for entry in std::fs::read_dir(&dir).with_context(|| format!("Failed to read {dir:?}"))? {
+            let path = entry?.path();
+            if path.is_dir() {
+                queue.push(path);
+            } else if path.extension().is_some_and(|ext| ext == "glsl") {
+                let source = std::fs::read_to_string(&path)?;
+                files.insert(path, source);
+            }
+        }
+    }
+    let mut programs: Vec<&PathBuf> = files
+        .iter()
+        .filter(|(_, source)| source.contains("void main"))
+        .map(|(path, _)| path)
+        .collect();
+    programs.sort();
+    let mut errors = Vec::new();
+    for dialect in Dialect::ALL {
+        let mut library = Library::offline(dialect, true);
+        setup(&mut library);
+        for path in &programs {
+            if let Err(e) = library.validate_file(path, &files) {
+                errors.push(format!("{dialect:?}: {e:#}"));
+            }
+        }
+    }
+    if !errors.is_empty() {
+        anyhow::bail!("{} shader errors:\n{}", errors.len(), errors.join("\n\n"));
+    }
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use geng_shader::{Dialect, Library};
+use ugli::ShaderType;
+
+const PROGRAM: &str = "#version 330 core
+uniform mat3 u_projection;
+uniform sampler2D u_texture;
+
+#ifdef VERTEX_SHADER
+in vec2 a_pos;
+out vec2 v_uv;
+void main() {
+    v_uv = (inverse(u_projection) * vec3(a_pos, 1.0)).xy;
+    vec3 pos = u_projection * vec3(a_pos, 1.0);
+    gl_Position = vec4(pos.xy, 0.0, pos.z);
+}
+#endif
+
+#ifdef FRAGMENT_SHADER
+in vec2 v_uv;
+out vec4 frag_color;
+void main() {
+    frag_color = premultiply_alpha(texture(u_texture, v_uv));
+}
+#endif
+";
+
+fn process(dialect: Dialect, shader_type: ShaderType, source: &str) -> anyhow::Result<String> {
+    Library::offline(dialect, true).process(shader_type, source)
+}
+
+#[test]
+fn modern_program() {
+    for dialect in Dialect::ALL {
+        let vertex = process(dialect, ShaderType::Vertex, PROGRAM).unwrap();
+        let fragment = process(dialect, ShaderType::Fragment, PROGRAM).unwrap();
+        let version = match dialect {
+            Dialect::Gl33 => "#version 330 core",
+            Dialect::Gles30 => "#version 300 es",
+            Dialect::WebGl1 => "#version 100",
+        };
+        for source in [&vertex, &fragment] {
+            assert_eq!(source.lines().next(), Some(version), "{dialect:?}");
+            assert_eq!(source.matches("#version").count(), 1, "{dialect:?}");
+        }
+        if dialect == Dialect::WebGl1 {
+            assert!(vertex.contains("attribute vec2 a_pos;"));
+            assert!(vertex.contains("varying vec2 v_uv;"));
+            assert!(fragment.contains("varying vec2 v_uv;"));
+            assert!(fragment.contains("#define frag_color gl_FragColor"));
+            assert!(fragment.contains("texture2D(u_texture, v_uv)"));
+        } else {
+            assert!(vertex.contains("in vec2 a_pos;"));
+            assert!(fragment.contains("out vec4 frag_color;"));
+            assert!(fragment.contains("texture(u_texture, v_uv)"));
+        }
+    }
+}
+
+#[test]
+fn unsupported_features() {
+    let source = PROGRAM.replace(
+        "texture(u_texture, v_uv)",
+        "texelFetch(u_texture, ivec2(v_uv), 0)",
+    );
+    let error = process(Dialect::WebGl1, ShaderType::Fragment, &source).unwrap_err();
+    let error = format!("{error:#}");
+    assert!(
+        error.contains("<source>:19: `texelFetch` is not supported"),
+        "{error}"
+    );
+    // Not part of the vertex shader
+    process(Dialect::WebGl1, ShaderType::Vertex, &source).unwrap();
+    process(Dialect::Gl33, ShaderType::Fragment, &source).unwrap();
+
+    let source = PROGRAM.replace("vec2 v_uv;", "dvec2 v_uv;");
+    assert!(process(Dialect::Gles30, ShaderType::Vertex, &source).is_err());
+    process(Dialect::Gl33, ShaderType::Vertex, &source).unwrap();
+}
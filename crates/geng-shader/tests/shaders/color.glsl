@@ -0,0 +1,20 @@
+#version 330 core
+#include "common.glsl"
+#include <noise>
+
+#ifdef VERTEX_SHADER
+in vec2 a_pos;
+out vec2 v_pos;
+void main() {
+    v_pos = (inverse(u_projection_matrix) * vec3(a_pos, 1.0)).xy;
+    gl_Position = project(a_pos);
+}
+#endif
+
+#ifdef FRAGMENT_SHADER
+in vec2 v_pos;
+layout(location = 0) out vec4 frag_color;
+void main() {
+    frag_color = u_color * (0.5 + 0.5 * snoise(v_pos));
+}
+#endif
@@ -0,0 +1,10 @@
+#pragma once
+
+uniform mat3 u_projection_matrix;
+uniform mat3 u_view_matrix;
+uniform vec4 u_color;
+
+vec4 project(vec2 pos) {
+    vec3 result = u_projection_matrix * u_view_matrix * vec3(pos, 1.0);
+    return vec4(result.xy, 0.0, result.z);
+}
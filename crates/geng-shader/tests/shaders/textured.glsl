@@ -0,0 +1,24 @@
+#version 330 core
+#include "common.glsl"
+
+uniform sampler2D u_texture;
+uniform ivec2 u_texture_size;
+
+#ifdef VERTEX_SHADER
+in vec2 a_pos;
+in vec2 a_uv;
+out vec2 v_uv;
+void main() {
+    v_uv = a_uv;
+    gl_Position = project(a_pos);
+}
+#endif
+
+#ifdef FRAGMENT_SHADER
+in vec2 v_uv;
+out vec4 frag_color;
+void main() {
+    vec4 color = texture(u_texture, v_uv) * u_color;
+    frag_color = premultiply_alpha(color) + smoothTexture2D(v_uv, u_texture, u_texture_size) * 0.0;
+}
+#endif
@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+fn validate(dir: &Path) {
+    if let Err(e) = geng_shader::validate_dir(dir, |_| {}) {
+        panic!("{e:#}");
+    }
+}
+
+#[test]
+fn builtin_shaders() {
+    let crates = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    for dir in ["geng-draw2d/src/shaders", "geng-font/src"] {
+        validate(&crates.join(dir));
+    }
+}
+
+/// Both stages of modern shaders for every dialect
+#[test]
+fn modern_shaders() {
+    validate(&Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/shaders"));
+}
+
+/// Errors point at the original file and line
+#[test]
+fn invalid_shader() {
+    let path = PathBuf::from("broken.glsl");
+    let source = "#version 330 core
+#ifdef VERTEX_SHADER
+in vec2 a_pos;
+void main() {
+    gl_Position = vec4(a_pos, 0.0, 1.0);
+}
+#endif
+#ifdef FRAGMENT_SHADER
+out vec4 frag_color;
+void main() {
+    frag_color = u_missing;
+}
+#endif
+";
+    let files = HashMap::from([(path.clone(), source.to_owned())]);
+    for dialect in geng_shader::Dialect::ALL {
+        let library = geng_shader::Library::offline(dialect, true);
+        let error = library.validate_file(&path, &files).unwrap_err();
+        let message = format!("{error:#}");
+        assert!(message.contains("Fragment"), "{dialect:?}: {message}");
+        assert!(message.contains("broken.glsl:11"), "{dialect:?}: {message}");
+        assert!(message.contains("u_missing"), "{dialect:?}: {message}");
+    }
+}
@@ -0,0 +1,106 @@
+use super::*;
+
+/// Plays the frames of a [SpriteSheet] tag according to their durations
+#[derive(Debug, Clone)]
+pub struct AnimationPlayer {
+    tag: Option<String>,
+    frames: Vec<usize>,
+    position: usize,
+    time: f64,
+    finished: bool,
+    pub looped: bool,
+    /// Playback speed multiplier
+    pub speed: f64,
+}
+
+impl AnimationPlayer {
+    /// Looping player going through all the frames of the sheet
+    pub fn new(sheet: &SpriteSheet) -> Self {
+        Self::with_frames(None, (0..sheet.frames().len()).collect())
+    }
+
+    /// Looping player of the tag named `tag`
+    pub fn from_tag(sheet: &SpriteSheet, tag: &str) -> anyhow::Result<Self> {
+        let frames = find_tag(sheet, tag)?.frames.clone();
+        Ok(Self::with_frames(Some(tag.to_owned()), frames))
+    }
+
+    fn with_frames(tag: Option<String>, frames: Vec<usize>) -> Self {
+        Self {
+            tag,
+            frames,
+            position: 0,
+            time: 0.0,
+            finished: false,
+            looped: true,
+            speed: 1.0,
+        }
+    }
+
+    /// Switch to the tag named `tag`, playing it from the start
+    /// unless it is already playing
+    pub fn play(&mut self, sheet: &SpriteSheet, tag: &str) -> anyhow::Result<()> {
+        if self.tag.as_deref() == Some(tag) {
+            return Ok(());
+        }
+        self.frames = find_tag(sheet, tag)?.frames.clone();
+        self.tag = Some(tag.to_owned());
+        self.restart();
+        Ok(())
+    }
+
+    pub fn restart(&mut self) {
+        self.position = 0;
+        self.time = 0.0;
+        self.finished = false;
+    }
+
+    /// Name of the tag being played, `None` when playing all frames
+    pub fn tag(&self) -> Option<&str> {
+        self.tag.as_deref()
+    }
+
+    pub fn update(&mut self, sheet: &SpriteSheet, delta_time: f64) {
+        if self.finished {
+            return;
+        }
+        let duration = |index: usize| sheet.frame(self.frames[index]).duration;
+        let total: f64 = (0..self.frames.len()).map(duration).sum();
+        if total <= 0.0 {
+            return;
+        }
+        self.time += delta_time * self.speed;
+        if self.looped {
+            self.time %= total;
+        }
+        while self.time >= duration(self.position) {
+            if self.position + 1 < self.frames.len() {
+                self.time -= duration(self.position);
+                self.position += 1;
+            } else if self.looped {
+                self.time -= duration(self.position);
+                self.position = 0;
+            } else {
+                self.time = duration(self.position);
+                self.finished = true;
+                break;
+            }
+        }
+    }
+
+    /// Index of the current frame in the sheet
+    pub fn frame(&self) -> usize {
+        self.frames[self.position]
+    }
+
+    /// Whether a non looped animation has reached its last frame
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+}
+
+fn find_tag<'a>(sheet: &'a SpriteSheet, tag: &str) -> anyhow::Result<&'a Tag> {
+    sheet
+        .tag(tag)
+        .ok_or_else(|| anyhow::anyhow!("Sprite sheet has no tag {tag:?}"))
+}
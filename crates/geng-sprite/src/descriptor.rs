@@ -0,0 +1,337 @@
+//! Sprite sheet descriptor formats: Aseprite and TexturePacker JSON exports and grid specs
+use super::*;
+
+use std::collections::BTreeMap;
+
+/// Parsed sprite sheet descriptor, turned into a [SpriteSheet] together with its image.
+///
+/// Can be deserialized from any self-describing format,
+/// grid specs are recognized by the `frame_size` field
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(try_from = "DescriptorFile")]
+pub struct Descriptor {
+    /// Image file relative to the descriptor, `None` if not specified
+    pub image: Option<String>,
+    pub layout: Layout,
+    pub tags: Vec<Tag>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Layout {
+    /// Explicit frame rects, as exported by Aseprite or TexturePacker
+    Frames(Vec<Frame>),
+    /// Frames of the same size laid out in rows, depends on the image size
+    Grid(Grid),
+}
+
+#[derive(Debug, Clone)]
+pub struct Grid {
+    pub frame_size: vec2<usize>,
+    /// Space around the whole grid
+    pub margin: usize,
+    /// Space between frames
+    pub spacing: usize,
+    /// Number of frames, all cells are used if `None`
+    pub count: Option<usize>,
+    /// Duration of every frame in seconds
+    pub duration: f64,
+    /// Frame names, frames without a name are named by their index
+    pub names: Vec<String>,
+}
+
+impl Descriptor {
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Frames of the sheet for an image of `image_size` pixels
+    pub fn frames(&self, image_size: vec2<usize>) -> anyhow::Result<Vec<Frame>> {
+        let frames = match &self.layout {
+            Layout::Frames(frames) => frames.clone(),
+            Layout::Grid(grid) => grid.frames(image_size)?,
+        };
+        if frames.is_empty() {
+            anyhow::bail!("Sprite sheet has no frames");
+        }
+        for frame in &frames {
+            if frame.rect.max.x > image_size.x || frame.rect.max.y > image_size.y {
+                anyhow::bail!(
+                    "Frame {:?} is outside of the {}x{} image",
+                    frame.name,
+                    image_size.x,
+                    image_size.y,
+                );
+            }
+        }
+        for tag in &self.tags {
+            if tag.frames.is_empty() {
+                anyhow::bail!("Tag {:?} has no frames", tag.name);
+            }
+            if let Some(&index) = tag.frames.iter().find(|&&index| index >= frames.len()) {
+                anyhow::bail!(
+                    "Tag {:?} refers to frame {index}, but there are only {} frames",
+                    tag.name,
+                    frames.len(),
+                );
+            }
+        }
+        Ok(frames)
+    }
+}
+
+impl Grid {
+    pub fn frames(&self, image_size: vec2<usize>) -> anyhow::Result<Vec<Frame>> {
+        let cells = |image: usize, frame: usize| {
+            (image + self.spacing).saturating_sub(2 * self.margin) / (frame + self.spacing)
+        };
+        if self.frame_size.x == 0 || self.frame_size.y == 0 {
+            anyhow::bail!("Grid frame size must not be zero");
+        }
+        let columns = cells(image_size.x, self.frame_size.x);
+        let rows = cells(image_size.y, self.frame_size.y);
+        let count = self.count.unwrap_or(columns * rows);
+        if count > columns * rows {
+            anyhow::bail!(
+                "Grid has {count} frames, but only {columns}x{rows} fit in the {}x{} image",
+                image_size.x,
+                image_size.y,
+            );
+        }
+        Ok((0..count)
+            .map(|index| {
+                let cell = vec2(index % columns, index / columns);
+                let pos = vec2(
+                    self.margin + cell.x * (self.frame_size.x + self.spacing),
+                    self.margin + cell.y * (self.frame_size.y + self.spacing),
+                );
+                Frame {
+                    name: self
+                        .names
+                        .get(index)
+                        .cloned()
+                        .unwrap_or_else(|| index.to_string()),
+                    rect: Aabb2::point(pos).extend_positive(self.frame_size),
+                    duration: self.duration,
+                }
+            })
+            .collect())
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(
+    untagged,
+    expecting = "an Aseprite or TexturePacker JSON export or a grid spec with `frame_size`"
+)]
+enum DescriptorFile {
+    Grid(GridFile),
+    Sheet(JsonSheet),
+}
+
+impl TryFrom<DescriptorFile> for Descriptor {
+    type Error = anyhow::Error;
+    fn try_from(file: DescriptorFile) -> anyhow::Result<Self> {
+        match file {
+            DescriptorFile::Grid(grid) => Ok(grid.into()),
+            DescriptorFile::Sheet(sheet) => sheet.try_into(),
+        }
+    }
+}
+
+/// Map that keeps the order of entries, frame order matters for tags
+struct OrderedMap<T>(Vec<(String, T)>);
+
+impl<T> Default for OrderedMap<T> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for OrderedMap<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor<T>(std::marker::PhantomData<T>);
+        impl<'de, T: serde::Deserialize<'de>> serde::de::Visitor<'de> for Visitor<T> {
+            type Value = OrderedMap<T>;
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a map")
+            }
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut entries = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(OrderedMap(entries))
+            }
+        }
+        deserializer.deserialize_map(Visitor(std::marker::PhantomData))
+    }
+}
+
+/// Aseprite and TexturePacker JSON (hash or array), with Pixi style `animations`
+#[derive(serde::Deserialize)]
+struct JsonSheet {
+    frames: JsonFrames,
+    #[serde(default)]
+    meta: JsonMeta,
+    #[serde(default)]
+    animations: OrderedMap<Vec<String>>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum JsonFrames {
+    Array(Vec<JsonFrame>),
+    Hash(OrderedMap<JsonFrame>),
+}
+
+#[derive(serde::Deserialize)]
+struct JsonFrame {
+    filename: Option<String>,
+    frame: JsonRect,
+    #[serde(default)]
+    rotated: bool,
+    /// Milliseconds
+    duration: Option<f64>,
+}
+
+#[derive(serde::Deserialize)]
+struct JsonRect {
+    x: usize,
+    y: usize,
+    w: usize,
+    h: usize,
+}
+
+#[derive(Default, serde::Deserialize)]
+struct JsonMeta {
+    image: Option<String>,
+    #[serde(default, rename = "frameTags")]
+    frame_tags: Vec<JsonTag>,
+}
+
+#[derive(serde::Deserialize)]
+struct JsonTag {
+    name: String,
+    from: usize,
+    to: usize,
+    #[serde(default)]
+    direction: String,
+}
+
+impl TryFrom<JsonSheet> for Descriptor {
+    type Error = anyhow::Error;
+    fn try_from(sheet: JsonSheet) -> anyhow::Result<Self> {
+        let frames: Vec<(Option<String>, JsonFrame)> = match sheet.frames {
+            JsonFrames::Array(frames) => frames.into_iter().map(|frame| (None, frame)).collect(),
+            JsonFrames::Hash(OrderedMap(frames)) => frames
+                .into_iter()
+                .map(|(name, frame)| (Some(name), frame))
+                .collect(),
+        };
+        let frames = frames
+            .into_iter()
+            .enumerate()
+            .map(|(index, (name, frame))| {
+                let name = name.or(frame.filename).unwrap_or_else(|| index.to_string());
+                if frame.rotated {
+                    anyhow::bail!("Frame {name:?} is rotated, rotated frames are not supported");
+                }
+                let rect = frame.frame;
+                Ok(Frame {
+                    name,
+                    rect: Aabb2::point(vec2(rect.x, rect.y)).extend_positive(vec2(rect.w, rect.h)),
+                    duration: frame.duration.map_or(DEFAULT_DURATION, |ms| ms / 1000.0),
+                })
+            })
+            .collect::<anyhow::Result<Vec<Frame>>>()?;
+        let mut tags = Vec::new();
+        for tag in sheet.meta.frame_tags {
+            if tag.from > tag.to {
+                anyhow::bail!("Tag {:?} ends before it starts", tag.name);
+            }
+            let forward = tag.from..=tag.to;
+            // Ping-pong does not repeat the end frames
+            let inner = (tag.to - tag.from).saturating_sub(1);
+            let frames = match tag.direction.as_str() {
+                "" | "forward" => forward.collect(),
+                "reverse" => forward.rev().collect(),
+                "pingpong" => forward
+                    .clone()
+                    .chain(forward.rev().skip(1).take(inner))
+                    .collect(),
+                "pingpong_reverse" => forward
+                    .clone()
+                    .rev()
+                    .chain(forward.skip(1).take(inner))
+                    .collect(),
+                direction => anyhow::bail!("Unknown direction {direction:?} of tag {:?}", tag.name),
+            };
+            tags.push(Tag {
+                name: tag.name,
+                frames,
+            });
+        }
+        for (name, names) in sheet.animations.0 {
+            let frames = names
+                .iter()
+                .map(|frame| {
+                    frames.iter().position(|f| f.name == *frame).ok_or_else(|| {
+                        anyhow::anyhow!("Animation {name:?} refers to unknown frame {frame:?}")
+                    })
+                })
+                .collect::<anyhow::Result<_>>()?;
+            tags.push(Tag { name, frames });
+        }
+        Ok(Self {
+            image: sheet.meta.image,
+            layout: Layout::Frames(frames),
+            tags,
+        })
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct GridFile {
+    image: Option<String>,
+    frame_size: [usize; 2],
+    #[serde(default)]
+    margin: usize,
+    #[serde(default)]
+    spacing: usize,
+    count: Option<usize>,
+    #[serde(default = "default_duration")]
+    duration: f64,
+    #[serde(default)]
+    names: Vec<String>,
+    /// Frame indices of each tag
+    #[serde(default)]
+    tags: BTreeMap<String, Vec<usize>>,
+}
+
+fn default_duration() -> f64 {
+    DEFAULT_DURATION
+}
+
+impl From<GridFile> for Descriptor {
+    fn from(file: GridFile) -> Self {
+        Self {
+            image: file.image,
+            layout: Layout::Grid(Grid {
+                frame_size: vec2(file.frame_size[0], file.frame_size[1]),
+                margin: file.margin,
+                spacing: file.spacing,
+                count: file.count,
+                duration: file.duration,
+                names: file.names,
+            }),
+            tags: file
+                .tags
+                .into_iter()
+                .map(|(name, frames)| Tag { name, frames })
+                .collect(),
+        }
+    }
+}
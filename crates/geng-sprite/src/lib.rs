@@ -0,0 +1,109 @@
+//! Sprite sheets with named frames and tags, and an [AnimationPlayer] to play them
+use batbox_color::*;
+use batbox_la::*;
+
+mod animation;
+mod descriptor;
+
+pub use animation::*;
+pub use descriptor::*;
+
+/// Default frame duration in seconds when the descriptor has none
+pub const DEFAULT_DURATION: f64 = 0.1;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub name: String,
+    /// Pixel rect in the image, `y` pointing down like in the descriptor files
+    pub rect: Aabb2<usize>,
+    /// Seconds
+    pub duration: f64,
+}
+
+/// Named sequence of frames, like an animation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tag {
+    pub name: String,
+    /// Frame indices in playback order
+    pub frames: Vec<usize>,
+}
+
+pub struct SpriteSheet {
+    texture: ugli::Texture,
+    frames: Vec<Frame>,
+    uvs: Vec<Aabb2<f32>>,
+    tags: Vec<Tag>,
+}
+
+impl SpriteSheet {
+    pub fn new(texture: ugli::Texture, descriptor: &Descriptor) -> anyhow::Result<Self> {
+        let size = texture.size();
+        let frames = descriptor.frames(size)?;
+        let size = size.map(|x| x as f32);
+        // Textures are stored bottom to top
+        let uvs = frames
+            .iter()
+            .map(|frame| Aabb2 {
+                min: vec2(
+                    frame.rect.min.x as f32 / size.x,
+                    1.0 - frame.rect.max.y as f32 / size.y,
+                ),
+                max: vec2(
+                    frame.rect.max.x as f32 / size.x,
+                    1.0 - frame.rect.min.y as f32 / size.y,
+                ),
+            })
+            .collect();
+        Ok(Self {
+            texture,
+            frames,
+            uvs,
+            tags: descriptor.tags.clone(),
+        })
+    }
+    pub fn texture(&self) -> &ugli::Texture {
+        &self.texture
+    }
+    pub fn set_filter(&mut self, filter: ugli::Filter) {
+        self.texture.set_filter(filter);
+    }
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+    pub fn frame(&self, index: usize) -> &Frame {
+        &self.frames[index]
+    }
+    /// Index of the frame named `name`
+    pub fn find_frame(&self, name: &str) -> Option<usize> {
+        self.frames.iter().position(|frame| frame.name == name)
+    }
+    pub fn tags(&self) -> &[Tag] {
+        &self.tags
+    }
+    pub fn tag(&self, name: &str) -> Option<&Tag> {
+        self.tags.iter().find(|tag| tag.name == name)
+    }
+    pub fn uv(&self, frame: usize) -> Aabb2<f32> {
+        self.uvs[frame]
+    }
+    /// Frame `frame` drawn into `aabb`
+    pub fn quad(
+        &self,
+        frame: usize,
+        aabb: Aabb2<f32>,
+    ) -> geng_draw2d::TexturedQuad<&ugli::Texture> {
+        self.colored_quad(frame, aabb, Rgba::WHITE)
+    }
+    pub fn colored_quad(
+        &self,
+        frame: usize,
+        aabb: Aabb2<f32>,
+        color: Rgba<f32>,
+    ) -> geng_draw2d::TexturedQuad<&ugli::Texture> {
+        geng_draw2d::TexturedQuad::colored(aabb, &self.texture, color).sub_texture(self.uv(frame))
+    }
+    /// Frame `frame` in the `[-1, 1]` square, to be transformed
+    pub fn unit_quad(&self, frame: usize) -> geng_draw2d::TexturedQuad<&ugli::Texture> {
+        geng_draw2d::TexturedQuad::unit(&self.texture).sub_texture(self.uv(frame))
+    }
+}
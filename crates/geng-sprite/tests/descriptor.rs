@@ -0,0 +1,68 @@
+use batbox_la::*;
+use geng_sprite::*;
+
+#[test]
+fn aseprite_hash() {
+    let descriptor = Descriptor::from_json(
+        r#"{
+            "frames": {
+                "hero 10.aseprite": { "frame": { "x": 0, "y": 0, "w": 16, "h": 16 }, "rotated": false, "duration": 100 },
+                "hero 2.aseprite": { "frame": { "x": 16, "y": 0, "w": 16, "h": 16 }, "rotated": false, "duration": 150 },
+                "hero 3.aseprite": { "frame": { "x": 32, "y": 0, "w": 16, "h": 16 }, "rotated": false, "duration": 50 }
+            },
+            "meta": {
+                "image": "hero.png",
+                "frameTags": [
+                    { "name": "walk", "from": 0, "to": 2, "direction": "pingpong" },
+                    { "name": "back", "from": 1, "to": 2, "direction": "reverse" }
+                ]
+            }
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(descriptor.image.as_deref(), Some("hero.png"));
+    let frames = descriptor.frames(vec2(48, 16)).unwrap();
+    let names: Vec<&str> = frames.iter().map(|frame| frame.name.as_str()).collect();
+    assert_eq!(
+        names,
+        ["hero 10.aseprite", "hero 2.aseprite", "hero 3.aseprite"]
+    );
+    assert_eq!(frames[1].duration, 0.15);
+    assert_eq!(descriptor.tags[0].frames, [0, 1, 2, 1]);
+    assert_eq!(descriptor.tags[1].frames, [2, 1]);
+    assert!(descriptor.frames(vec2(40, 16)).is_err());
+}
+
+#[test]
+fn texture_packer_array() {
+    let descriptor = Descriptor::from_json(
+        r#"{
+            "frames": [
+                { "filename": "idle_0.png", "frame": { "x": 0, "y": 0, "w": 8, "h": 8 } },
+                { "filename": "idle_1.png", "frame": { "x": 8, "y": 0, "w": 8, "h": 8 } }
+            ],
+            "animations": { "idle": ["idle_1.png", "idle_0.png"] }
+        }"#,
+    )
+    .unwrap();
+    let frames = descriptor.frames(vec2(16, 8)).unwrap();
+    assert_eq!(frames[0].name, "idle_0.png");
+    assert_eq!(frames[0].duration, DEFAULT_DURATION);
+    assert_eq!(descriptor.tags[0].frames, [1, 0]);
+}
+
+#[test]
+fn grid() {
+    let descriptor = Descriptor::from_json(
+        r#"{ "frame_size": [16, 16], "margin": 1, "spacing": 2, "count": 5, "tags": { "run": [3, 4] } }"#,
+    )
+    .unwrap();
+    let frames = descriptor.frames(vec2(54, 36)).unwrap();
+    assert_eq!(frames.len(), 5);
+    assert_eq!(frames[4].name, "4");
+    assert_eq!(
+        frames[4].rect,
+        Aabb2::point(vec2(19, 19)).extend_positive(vec2(16, 16))
+    );
+    assert!(descriptor.frames(vec2(53, 36)).is_err());
+}
@@ -0,0 +1,86 @@
+use super::*;
+
+/// Text of a translation key, resolved again when the locale or translations change
+pub struct LocalizedText<F: AsRef<Font>> {
+    localization: geng_i18n::Localization,
+    key: String,
+    args: Vec<(String, String)>,
+    font: F,
+    size: f32,
+    color: Rgba<f32>,
+    /// Resolved text and the [revision](geng_i18n::Localization::revision) it was resolved at
+    text: Option<(u64, String)>,
+}
+
+impl<F: AsRef<Font>> LocalizedText<F> {
+    pub fn new(
+        localization: &geng_i18n::Localization,
+        key: impl Into<String>,
+        font: F,
+        size: f32,
+        color: Rgba<f32>,
+    ) -> Self {
+        Self {
+            localization: localization.clone(),
+            key: key.into(),
+            args: Vec::new(),
+            font,
+            size,
+            color,
+            text: None,
+        }
+    }
+
+    /// Set argument referred to as `{ $name }` in the message
+    pub fn arg(mut self, name: &str, value: impl std::fmt::Display) -> Self {
+        self.set_arg(name, value);
+        self
+    }
+
+    pub fn set_arg(&mut self, name: &str, value: impl std::fmt::Display) {
+        let value = value.to_string();
+        match self.args.iter_mut().find(|(arg, _)| arg == name) {
+            Some((_, old)) if *old == value => return,
+            Some((_, old)) => *old = value,
+            None => self.args.push((name.to_owned(), value)),
+        }
+        self.text = None;
+    }
+
+    pub fn set_key(&mut self, key: impl Into<String>) {
+        self.key = key.into();
+        self.text = None;
+    }
+
+    /// Resolved text in the current locale
+    pub fn text(&mut self) -> &str {
+        self.resolve();
+        &self.text.as_ref().unwrap().1
+    }
+
+    fn resolve(&mut self) {
+        let revision = self.localization.revision();
+        if self.text.as_ref().map(|(cached, _)| *cached) != Some(revision) {
+            let args: Vec<(&str, &dyn std::fmt::Display)> = self
+                .args
+                .iter()
+                .map(|(name, value)| (name.as_str(), value as &dyn std::fmt::Display))
+                .collect();
+            let text = self.localization.format(&self.key, &args);
+            self.text = Some((revision, text));
+        }
+    }
+}
+
+impl<F: AsRef<Font>> Widget for LocalizedText<F> {
+    fn calc_constraints(&mut self, cx: &ConstraintsContext) -> Constraints {
+        self.resolve();
+        let text = &self.text.as_ref().unwrap().1;
+        calc_text_constraints(text, self.font.as_ref(), self.size, cx)
+    }
+    fn draw(&mut self, cx: &mut DrawContext) {
+        self.resolve();
+        let text = &self.text.as_ref().unwrap().1;
+        draw_text(text, self.font.as_ref(), self.color, cx);
+    }
+}
@@ -3,6 +3,7 @@ use super::*;
 mod button;
 mod color_box;
 // mod event_handler;
+mod localized_text;
 mod slider;
 mod string;
 mod text;
@@ -13,6 +14,7 @@ pub use button::*;
 pub use color_box::*;
 pub(crate) use string::*;
 // pub use event_handler::*;
+pub use localized_text::*;
 pub use slider::*;
 pub use text::*;
 pub use void::*;
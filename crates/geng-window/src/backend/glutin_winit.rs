@@ -177,6 +177,7 @@ where
                     .unwrap();
                 let gl_display = glutin::display::GetGlDisplay::display(&gl_config);
                 let context_attributes = glutin::context::ContextAttributesBuilder::new()
+                    .with_debug(self.options.gl_debug)
                     .build(window_handle.map(|handle| handle.as_raw()));
 
                 let gl_ctx = unsafe {
@@ -208,6 +209,9 @@ where
                         &std::ffi::CString::new(symbol).unwrap(),
                     )
                 });
+                if self.options.gl_debug && !ugli.enable_debug_output() {
+                    log::warn!("GL debug output is not supported");
+                }
                 let context = Rc::new(Context {
                     options: self.options.clone(),
                     window: RefCell::new(window),
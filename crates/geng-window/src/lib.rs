@@ -32,6 +32,9 @@ pub struct CliArgs {
     /// Start in fullscreen
     #[clap(long, value_name = "BOOL")]
     pub fullscreen: Option<bool>,
+    /// Create a debug GL context and log driver debug messages
+    #[clap(long, value_name = "BOOL")]
+    pub gl_debug: Option<bool>,
 }
 
 #[derive(Debug, Clone)]
@@ -45,6 +48,7 @@ pub struct Options {
     pub size: Option<vec2<usize>>,
     pub auto_close: bool,
     pub start_hidden: bool,
+    pub gl_debug: bool,
 }
 
 impl Options {
@@ -59,6 +63,7 @@ impl Options {
             size: None,
             auto_close: true,
             start_hidden: false,
+            gl_debug: false,
         }
     }
 
@@ -75,6 +80,9 @@ impl Options {
         if let Some(fullscreen) = args.fullscreen {
             self.fullscreen = fullscreen;
         }
+        if let Some(gl_debug) = args.gl_debug {
+            self.gl_debug = gl_debug;
+        }
     }
 }
 
@@ -261,6 +269,9 @@ where
                         return std::ops::ControlFlow::Break(());
                     }
                 }
+                Event::Draw => {
+                    window.ugli().begin_frame();
+                }
                 _ => {}
             }
             window
@@ -11,22 +11,22 @@ use proc_macro2::TokenStream;
 
 mod uniforms;
 
-#[proc_macro_derive(Uniforms)]
+#[proc_macro_derive(Uniforms, attributes(uniform))]
 pub fn derive_uniforms(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input: syn::DeriveInput = syn::parse_macro_input!(input);
-    match uniforms::DeriveInput::from_derive_input(&input) {
-        Ok(input) => input.derive().into(),
+    match uniforms::DeriveInput::from_derive_input(&input).and_then(|input| input.derive()) {
+        Ok(output) => output.into(),
         Err(e) => e.write_errors().into(),
     }
 }
 
 mod vertex;
 
-#[proc_macro_derive(Vertex)]
+#[proc_macro_derive(Vertex, attributes(attr))]
 pub fn derive_vertex(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input: syn::DeriveInput = syn::parse_macro_input!(input);
-    match vertex::DeriveInput::from_derive_input(&input) {
-        Ok(input) => input.derive().into(),
+    match vertex::DeriveInput::from_derive_input(&input).and_then(|input| input.derive()) {
+        Ok(output) => output.into(),
         Err(e) => e.write_errors().into(),
     }
 }
@@ -12,13 +12,155 @@ pub struct DeriveInput {
 }
 
 #[derive(FromField)]
+#[darling(attributes(uniform))]
 struct Field {
     ident: Option<syn::Ident>,
     ty: syn::Type,
+    /// Name of the uniform, defaults to field name
+    #[darling(default)]
+    rename: Option<String>,
+    /// Field is a `[T; N]` or `Vec<T>` mapped to a uniform array
+    #[darling(default)]
+    array: bool,
+    /// Field implements `ugli::Uniforms` instead of `ugli::Uniform`
+    #[darling(default)]
+    nested: bool,
+    /// Prefix for nested uniform names instead of `"{name}."`. Implies nested.
+    /// Array elements are always prefixed with `"{name}[{index}]."`
+    #[darling(default)]
+    prefix: Option<String>,
+}
+
+impl Field {
+    fn is_nested(&self) -> bool {
+        self.nested || self.prefix.is_some()
+    }
+
+    /// Type used for the uniform values, array element type for arrays
+    fn item_ty(&self) -> TokenStream {
+        let ty = &self.ty;
+        match self.array {
+            false => quote! { #ty },
+            true => quote! { <#ty as ugli::UniformArray>::Item },
+        }
+    }
+
+    fn cache_key_ty(&self) -> TokenStream {
+        let item_ty = self.item_ty();
+        match self.is_nested() {
+            false => quote! { <#item_ty as ugli::Uniform>::LifetimeErased },
+            true => quote! { <#item_ty as ugli::Uniforms>::ProgramInfoCacheKey },
+        }
+    }
+
+    fn program_info_ty(&self) -> TokenStream {
+        let item_ty = self.item_ty();
+        let item_info_ty = match self.is_nested() {
+            false => quote! { Option<ugli::UniformInfo> },
+            true => quote! { <#item_ty as ugli::Uniforms>::ProgramInfo },
+        };
+        match self.array {
+            false => item_info_ty,
+            true => quote! { Vec<#item_info_ty> },
+        }
+    }
+
+    /// Expression evaluating to the item's program info.
+    /// `name` is the full uniform name of the item (`u_light` or `u_lights[2]`)
+    fn get_item_info(&self, name: TokenStream) -> TokenStream {
+        let item_ty = self.item_ty();
+        match self.is_nested() {
+            false => quote! { program.uniform_info(&#name) },
+            true => quote! {
+                <#item_ty as ugli::Uniforms>::get_program_info_prefixed(
+                    program,
+                    &format!("{}.", #name),
+                )
+            },
+        }
+    }
+
+    fn get_program_info(&self, name: &str) -> TokenStream {
+        if let (Some(nested_prefix), false) = (&self.prefix, self.array) {
+            let item_ty = self.item_ty();
+            return quote! {
+                <#item_ty as ugli::Uniforms>::get_program_info_prefixed(
+                    program,
+                    &format!("{prefix}{}", #nested_prefix),
+                )
+            };
+        }
+        match self.array {
+            false => self.get_item_info(quote! { format!("{prefix}{}", #name) }),
+            true => {
+                let item_info = self.get_item_info(quote! { name });
+                quote! {{
+                    let name = format!("{prefix}{}", #name);
+                    (0..program.uniform_array_len(&name))
+                        .map(|index| {
+                            let name = format!("{name}[{index}]");
+                            #item_info
+                        })
+                        .collect()
+                }}
+            }
+        }
+    }
+
+    fn uniform_names(&self, value: TokenStream, name: &str) -> TokenStream {
+        if let (Some(nested_prefix), false) = (&self.prefix, self.array) {
+            return quote! {
+                complete &= ugli::Uniforms::uniform_names(&#value, &format!("{prefix}{}", #nested_prefix), names);
+            };
+        }
+        let item_names = |value: TokenStream, name: TokenStream| match self.is_nested() {
+            false => quote! { names.push(#name); },
+            true => quote! {
+                complete &= ugli::Uniforms::uniform_names(#value, &format!("{}.", #name), names);
+            },
+        };
+        match self.array {
+            false => item_names(quote! { &#value }, quote! { format!("{prefix}{}", #name) }),
+            true => {
+                let item_names =
+                    item_names(quote! { value }, quote! { format!("{name}[{index}]") });
+                quote! {{
+                    let name = format!("{prefix}{}", #name);
+                    for (index, value) in ugli::UniformArray::items(&#value).iter().enumerate() {
+                        #item_names
+                    }
+                }}
+            }
+        }
+    }
+
+    fn apply(&self, value: TokenStream, info: TokenStream) -> TokenStream {
+        let apply_item = |value: TokenStream, info: TokenStream| match self.is_nested() {
+            false => quote! {
+                if let Some(info) = #info {
+                    ugli::Uniform::apply(#value, program, info);
+                }
+            },
+            true => quote! {
+                ugli::Uniforms::apply_uniforms(#value, program, #info);
+            },
+        };
+        match self.array {
+            false => apply_item(quote! { &#value }, quote! { &#info }),
+            true => {
+                let apply_item = apply_item(quote! { value }, quote! { info });
+                quote! {
+                    for (value, info) in ugli::UniformArray::items(&#value).iter().zip(&#info) {
+                        #apply_item
+                    }
+                }
+            }
+        }
+    }
 }
 
 impl DeriveInput {
-    pub fn derive(self) -> TokenStream {
+    pub fn derive(self) -> darling::Result<TokenStream> {
         let Self {
             vis,
             ident,
@@ -27,6 +169,14 @@ impl DeriveInput {
         } = self;
         let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
         let data = data.take_struct().unwrap();
+        for field in &data.fields {
+            if field.array && field.prefix.is_some() {
+                return Err(darling::Error::custom(
+                    "prefix can not be used for arrays, elements are prefixed with \"{name}[{index}].\"",
+                )
+                .with_span(&field.ty));
+            }
+        }
         let field_names: Vec<_> = data
             .fields
             .iter()
@@ -42,35 +192,73 @@ impl DeriveInput {
                     })
             })
             .collect();
-        let field_tys = data.fields.iter().map(|field| &field.ty);
-        let field_count = data.fields.len();
+        let uniform_names: Vec<String> = data
+            .fields
+            .iter()
+            .zip(&field_names)
+            .map(|(field, field_name)| {
+                field
+                    .rename
+                    .clone()
+                    .unwrap_or_else(|| field_name.to_string())
+            })
+            .collect();
+        let field_indices: Vec<_> = (0..data.fields.len()).map(syn::Index::from).collect();
+        let cache_key_params: Vec<_> = (0..data.fields.len())
+            .map(|index| syn::Ident::new(&format!("F{index}"), Span::call_site()))
+            .collect();
+        let cache_key_tys = data.fields.iter().map(|field| field.cache_key_ty());
+        let program_info_tys = data.fields.iter().map(|field| field.program_info_ty());
+        let get_program_infos = data
+            .fields
+            .iter()
+            .zip(&uniform_names)
+            .map(|(field, name)| field.get_program_info(name));
+        let apply_fields = data
+            .fields
+            .iter()
+            .zip(&field_names)
+            .zip(&field_indices)
+            .map(|((field, field_name), index)| {
+                field.apply(quote! { self.#field_name }, quote! { info.#index })
+            });
+        let field_uniform_names = data
+            .fields
+            .iter()
+            .zip(&field_names)
+            .zip(&uniform_names)
+            .map(|((field, field_name), name)| {
+                field.uniform_names(quote! { self.#field_name }, name)
+            });
         let cache_key_type_name =
             syn::Ident::new(&format!("{ident}CacheKeyType"), Span::call_site());
-        quote! {
-            #[allow(non_camel_case_types)]
-            #vis struct #cache_key_type_name <#(#field_names: 'static,)*> {
-                #(
-                    #field_names: std::marker::PhantomData<#field_names>,
-                )*
+        Ok(quote! {
+            #vis struct #cache_key_type_name <#(#cache_key_params: 'static,)*> {
+                #[allow(clippy::type_complexity)]
+                phantom_data: std::marker::PhantomData<(#(#cache_key_params,)*)>,
             }
             #[allow(non_camel_case_types)]
             impl #impl_generics ugli::Uniforms for #ident #ty_generics #where_clause {
-                type ProgramInfoCacheKey = #cache_key_type_name <#(<#field_tys as ugli::Uniform>::LifetimeErased,)*>;
-                type ProgramInfo = [Option<ugli::UniformInfo>; #field_count];
+                type ProgramInfoCacheKey = #cache_key_type_name <#(#cache_key_tys,)*>;
+                type ProgramInfo = (#(#program_info_tys,)*);
                 fn get_program_info(program: &ugli::Program) -> Self::ProgramInfo {
-                    [#(
-                        program.uniform_info(stringify!(#field_names)),
-                    )*]
+                    Self::get_program_info_prefixed(program, "")
+                }
+                fn get_program_info_prefixed(program: &ugli::Program, prefix: &str) -> Self::ProgramInfo {
+                    #![allow(unused_variables)]
+                    (#(#get_program_infos,)*)
                 }
                 fn apply_uniforms(&self, program: &ugli::Program, info: &Self::ProgramInfo) {
-                    let [#(#field_names,)*] = info;
-                    #(
-                        if let Some(info) = #field_names {
-                            ugli::Uniform::apply(&self.#field_names, program, info);
-                        }
-                    )*
+                    #![allow(unused_variables)]
+                    #(#apply_fields)*
+                }
+                fn uniform_names(&self, prefix: &str, names: &mut Vec<String>) -> bool {
+                    #![allow(unused_variables, unused_mut)]
+                    let mut complete = true;
+                    #(#field_uniform_names)*
+                    complete
                 }
             }
-        }
+        })
     }
 }
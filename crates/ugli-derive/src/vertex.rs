@@ -1,4 +1,5 @@
 use super::*;
+use syn::spanned::Spanned;
 
 #[derive(FromDeriveInput)]
 #[darling(supports(struct_any))]
@@ -9,13 +10,34 @@ pub struct DeriveInput {
 }
 
 #[derive(FromField)]
+#[darling(attributes(attr))]
 struct Field {
     ident: Option<syn::Ident>,
     ty: syn::Type,
+    #[darling(default)]
+    normalized: bool,
+    #[darling(default)]
+    integer: bool,
+}
+
+impl Field {
+    fn mode(&self) -> darling::Result<TokenStream> {
+        Ok(match (self.normalized, self.integer) {
+            (false, false) => quote! { ugli::AttributeMode::Float },
+            (true, false) => quote! { ugli::AttributeMode::Normalized },
+            (false, true) => quote! { ugli::AttributeMode::Integer },
+            (true, true) => {
+                return Err(darling::Error::custom(
+                    "Attribute can't be both normalized and integer",
+                )
+                .with_span(&self.ty))
+            }
+        })
+    }
 }
 
 impl DeriveInput {
-    pub fn derive(self) -> TokenStream {
+    pub fn derive(self) -> darling::Result<TokenStream> {
         let Self {
             ident,
             generics,
@@ -24,6 +46,19 @@ impl DeriveInput {
         let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
         let data = data.take_struct().unwrap();
         let field_tys = data.fields.iter().map(|field| &field.ty);
+        let field_modes = data
+            .fields
+            .iter()
+            .map(|field| field.mode())
+            .collect::<darling::Result<Vec<_>>>()?;
+        let integer_checks = data
+            .fields
+            .iter()
+            .filter(|field| field.normalized || field.integer)
+            .map(|field| {
+                let ty = &field.ty;
+                quote_spanned! { ty.span() => ugli::assert_integer_attribute::<#ty>(); }
+            });
         let field_names = data.fields.iter().enumerate().map(|(index, field)| {
             field
                 .ident
@@ -34,15 +69,17 @@ impl DeriveInput {
                     quote! { #index }
                 })
         });
-        quote! {
+        Ok(quote! {
             unsafe impl #impl_generics ugli::Vertex for #ident #ty_generics #where_clause {
                 fn walk_attributes(mut visitor: impl ugli::VertexAttributeVisitor) {
-                    #(visitor.visit::<#field_tys>(
+                    #(#integer_checks)*
+                    #(visitor.visit_with_mode::<#field_tys>(
                         stringify!(#field_names),
                         ugli::field_offset!(Self.#field_names),
+                        #field_modes,
                     ));*
                 }
             }
-        }
+        })
     }
 }
@@ -0,0 +1,30 @@
+use super::*;
+
+use std::cell::{Cell, RefCell};
+
+/// Records issued GL calls while capturing
+#[derive(Default)]
+pub(crate) struct CallLog {
+    capturing: Cell<bool>,
+    calls: RefCell<Vec<String>>,
+}
+
+impl Context {
+    /// Start recording every GL call issued through this context
+    pub fn start_call_capture(&self) {
+        self.call_log.calls.borrow_mut().clear();
+        self.call_log.capturing.set(true);
+    }
+
+    /// Stop recording, returning the recorded calls
+    pub fn finish_call_capture(&self) -> Vec<String> {
+        self.call_log.capturing.set(false);
+        self.call_log.calls.take()
+    }
+
+    pub(crate) fn log_call(&self, call: impl FnOnce() -> String) {
+        if self.call_log.capturing.get() {
+            self.call_log.calls.borrow_mut().push(call());
+        }
+    }
+}
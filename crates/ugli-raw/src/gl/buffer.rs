@@ -3,13 +3,20 @@ use super::*;
 pub type Buffer = gl::types::GLuint;
 
 impl Context {
-    pub fn bind_buffer(&self, target: Enum, buffer: &Buffer) {
+    pub fn bind_buffer(&self, target: Enum, buffer: Option<&Buffer>) {
+        self.log_call(|| format!("bind_buffer({target:#x}, {buffer:?})"));
         unsafe {
-            gl::BindBuffer(target, *buffer);
+            gl::BindBuffer(target, *buffer.unwrap_or(&0));
         }
     }
 
     pub fn buffer_data<T>(&self, target: Enum, data: &[T], usage: Enum) {
+        self.log_call(|| {
+            format!(
+                "buffer_data({target:#x}, <{} items>, {usage:#x})",
+                data.len()
+            )
+        });
         unsafe {
             gl::BufferData(
                 target,
@@ -20,7 +27,20 @@ impl Context {
         }
     }
 
+    pub fn buffer_data_size(&self, target: Enum, size: SizeIPtr, usage: Enum) {
+        self.log_call(|| format!("buffer_data_size({target:#x}, {size:?}, {usage:#x})"));
+        unsafe {
+            gl::BufferData(target, size, std::ptr::null(), usage);
+        }
+    }
+
     pub fn buffer_sub_data<T>(&self, target: Enum, offset: IntPtr, data: &[T]) {
+        self.log_call(|| {
+            format!(
+                "buffer_sub_data({target:#x}, {offset:?}, <{} items>)",
+                data.len()
+            )
+        });
         unsafe {
             gl::BufferSubData(
                 target,
@@ -31,7 +51,28 @@ impl Context {
         }
     }
 
+    /// Returns `false` if the buffer could not be mapped, leaving `data` untouched
+    pub fn get_buffer_sub_data<T>(&self, target: Enum, offset: IntPtr, data: &mut [T]) -> bool {
+        self.log_call(|| {
+            format!(
+                "get_buffer_sub_data({target:#x}, {offset:?}, <{} items>)",
+                data.len()
+            )
+        });
+        // glGetBufferSubData is not available in GLES
+        let size = std::mem::size_of_val(data);
+        unsafe {
+            let ptr = gl::MapBufferRange(target, offset, size as SizeIPtr, gl::MAP_READ_BIT);
+            if ptr.is_null() {
+                return false;
+            }
+            std::ptr::copy_nonoverlapping(ptr as *const u8, data.as_mut_ptr() as *mut u8, size);
+            gl::UnmapBuffer(target) == gl::TRUE
+        }
+    }
+
     pub fn create_buffer(&self) -> Option<Buffer> {
+        self.log_call(|| "create_buffer()".to_owned());
         let mut handle = std::mem::MaybeUninit::uninit();
         unsafe {
             gl::GenBuffers(1, handle.as_mut_ptr());
@@ -45,6 +86,7 @@ impl Context {
     }
 
     pub fn delete_buffer(&self, buffer: &Buffer) {
+        self.log_call(|| format!("delete_buffer({buffer:?})"));
         unsafe {
             gl::DeleteBuffers(1, buffer);
         }
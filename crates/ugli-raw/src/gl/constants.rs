@@ -4,19 +4,38 @@ pub const TRUE: Bool = 1;
 pub const FALSE: Bool = 0;
 
 pub use gl::{
-    ACTIVE_ATTRIBUTES, ACTIVE_UNIFORMS, ALPHA, ALWAYS, ARRAY_BUFFER, BACK, BLEND, CLAMP_TO_EDGE,
-    COLOR_ATTACHMENT0, COLOR_BUFFER_BIT, COMPILE_STATUS, CULL_FACE, DECR, DECR_WRAP,
-    DEPTH24_STENCIL8, DEPTH_ATTACHMENT, DEPTH_BUFFER_BIT, DEPTH_COMPONENT, DEPTH_COMPONENT16,
-    DEPTH_STENCIL, DEPTH_STENCIL_ATTACHMENT, DEPTH_TEST, DST_ALPHA, DST_COLOR, DYNAMIC_DRAW, EQUAL,
-    FLOAT, FLOAT_MAT2, FLOAT_MAT3, FLOAT_MAT4, FLOAT_VEC2, FLOAT_VEC3, FLOAT_VEC4, FRAGMENT_SHADER,
-    FRAMEBUFFER, FRAMEBUFFER_COMPLETE, FRONT, FUNC_ADD, FUNC_REVERSE_SUBTRACT, FUNC_SUBTRACT,
-    GEQUAL, GREATER, INCR, INCR_WRAP, INT, INT_VEC2, INT_VEC3, INT_VEC4, INVALID_ENUM,
-    INVALID_FRAMEBUFFER_OPERATION, INVALID_OPERATION, INVALID_VALUE, INVERT, KEEP, LEQUAL, LESS,
-    LINEAR, LINEAR_MIPMAP_LINEAR, LINES, LINE_LOOP, LINE_STRIP, LINK_STATUS, MAX, MIN, NEAREST,
-    NEVER, NOTEQUAL, NO_ERROR, ONE, ONE_MINUS_DST_ALPHA, ONE_MINUS_DST_COLOR, ONE_MINUS_SRC_ALPHA,
-    ONE_MINUS_SRC_COLOR, OUT_OF_MEMORY, POINTS, PROGRAM_POINT_SIZE, RENDERBUFFER, REPEAT, REPLACE,
-    RGBA, RGBA4, SRC_ALPHA, SRC_ALPHA_SATURATE, SRC_COLOR, STATIC_DRAW, STENCIL_BUFFER_BIT,
-    STENCIL_TEST, TEXTURE0, TEXTURE_2D, TEXTURE_MAG_FILTER, TEXTURE_MIN_FILTER, TEXTURE_WRAP_S,
-    TEXTURE_WRAP_T, TRIANGLES, TRIANGLE_FAN, TRIANGLE_STRIP, UNPACK_ALIGNMENT, UNSIGNED_BYTE,
-    VERTEX_SHADER, ZERO,
+    ACTIVE_ATTRIBUTES, ACTIVE_UNIFORMS, ALPHA, ALREADY_SIGNALED, ALWAYS, ARRAY_BUFFER, BACK, BLEND,
+    BOOL, BOOL_VEC2, BOOL_VEC3, BOOL_VEC4, BUFFER, BYTE, CLAMP_TO_EDGE, COLOR_ATTACHMENT0,
+    COLOR_BUFFER_BIT, COMPILE_STATUS, CONDITION_SATISFIED, CULL_FACE, DEBUG_OUTPUT,
+    DEBUG_OUTPUT_SYNCHRONOUS, DEBUG_SEVERITY_HIGH, DEBUG_SEVERITY_LOW, DEBUG_SEVERITY_MEDIUM,
+    DEBUG_SEVERITY_NOTIFICATION, DECR, DECR_WRAP, DEPTH24_STENCIL8, DEPTH_ATTACHMENT,
+    DEPTH_BUFFER_BIT, DEPTH_COMPONENT, DEPTH_COMPONENT16, DEPTH_STENCIL, DEPTH_STENCIL_ATTACHMENT,
+    DEPTH_TEST, DST_ALPHA, DST_COLOR, DYNAMIC_DRAW, EQUAL, FLOAT, FLOAT_MAT2, FLOAT_MAT3,
+    FLOAT_MAT4, FLOAT_VEC2, FLOAT_VEC3, FLOAT_VEC4, FRAGMENT_SHADER, FRAMEBUFFER,
+    FRAMEBUFFER_COMPLETE, FRONT, FUNC_ADD, FUNC_REVERSE_SUBTRACT, FUNC_SUBTRACT, GEQUAL, GREATER,
+    HALF_FLOAT, INCR, INCR_WRAP, INT, INTERLEAVED_ATTRIBS, INT_VEC2, INT_VEC3, INT_VEC4,
+    INVALID_ENUM, INVALID_FRAMEBUFFER_OPERATION, INVALID_OPERATION, INVALID_VALUE, INVERT, KEEP,
+    LEQUAL, LESS, LINEAR, LINEAR_MIPMAP_LINEAR, LINES, LINE_LOOP, LINE_STRIP, LINK_STATUS, MAX,
+    MIN, NEAREST, NEVER, NOTEQUAL, NO_ERROR, ONE, ONE_MINUS_DST_ALPHA, ONE_MINUS_DST_COLOR,
+    ONE_MINUS_SRC_ALPHA, ONE_MINUS_SRC_COLOR, OUT_OF_MEMORY, PIXEL_PACK_BUFFER, POINTS, PROGRAM,
+    PROGRAM_POINT_SIZE, RASTERIZER_DISCARD, RENDERBUFFER, REPEAT, REPLACE, RGBA, RGBA4, SAMPLER_2D,
+    SAMPLER_CUBE, SHORT, SRC_ALPHA, SRC_ALPHA_SATURATE, SRC_COLOR, STATIC_DRAW, STENCIL_BUFFER_BIT,
+    STENCIL_TEST, STREAM_READ, SYNC_FLUSH_COMMANDS_BIT, SYNC_GPU_COMMANDS_COMPLETE, TEXTURE,
+    TEXTURE0, TEXTURE_2D, TEXTURE_MAG_FILTER, TEXTURE_MIN_FILTER, TEXTURE_WRAP_S, TEXTURE_WRAP_T,
+    TIMEOUT_EXPIRED, TRANSFORM_FEEDBACK_BUFFER, TRIANGLES, TRIANGLE_FAN, TRIANGLE_STRIP,
+    UNPACK_ALIGNMENT, UNSIGNED_BYTE, UNSIGNED_INT, UNSIGNED_INT_VEC2, UNSIGNED_INT_VEC3,
+    UNSIGNED_INT_VEC4, UNSIGNED_SHORT, VERTEX_SHADER, WAIT_FAILED, ZERO,
 };
+
+// Compressed texture formats, defined by the extensions providing them
+pub const COMPRESSED_RGB_S3TC_DXT1: Enum = 0x83F0;
+pub const COMPRESSED_RGBA_S3TC_DXT1: Enum = 0x83F1;
+pub const COMPRESSED_RGBA_S3TC_DXT3: Enum = 0x83F2;
+pub const COMPRESSED_RGBA_S3TC_DXT5: Enum = 0x83F3;
+pub const COMPRESSED_RED_RGTC1: Enum = 0x8DBB;
+pub const COMPRESSED_RG_RGTC2: Enum = 0x8DBD;
+pub const COMPRESSED_RGBA_BPTC_UNORM: Enum = 0x8E8C;
+pub const COMPRESSED_RGB_ETC1: Enum = 0x8D64;
+pub const COMPRESSED_RGB8_ETC2: Enum = 0x9274;
+pub const COMPRESSED_RGBA8_ETC2_EAC: Enum = 0x9278;
+pub const COMPRESSED_RGBA_ASTC_4X4: Enum = 0x93B0;
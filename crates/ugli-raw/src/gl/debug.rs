@@ -0,0 +1,55 @@
+use super::*;
+
+/// Called with source, type, id, severity and message
+pub type DebugCallback = Box<dyn Fn(Enum, Enum, UInt, Enum, &str)>;
+
+impl Context {
+    /// Returns `false` if debug output is not supported
+    pub fn debug_message_callback(
+        &self,
+        callback: impl Fn(Enum, Enum, UInt, Enum, &str) + 'static,
+    ) -> bool {
+        if !gl::DebugMessageCallback::is_loaded() {
+            return false;
+        }
+        extern "system" fn handler(
+            source: Enum,
+            typ: Enum,
+            id: UInt,
+            severity: Enum,
+            length: SizeI,
+            message: *const Char,
+            user_param: *mut std::ffi::c_void,
+        ) {
+            let callback = unsafe { &*(user_param as *const DebugCallback) };
+            let message =
+                unsafe { std::slice::from_raw_parts(message as *const u8, length.max(0) as usize) };
+            callback(source, typ, id, severity, &String::from_utf8_lossy(message));
+        }
+        let callback: Box<DebugCallback> = Box::new(Box::new(callback));
+        unsafe {
+            gl::DebugMessageCallback(
+                Some(handler),
+                &*callback as *const DebugCallback as *const std::ffi::c_void,
+            );
+        }
+        // Previous callback is no longer referenced by the driver
+        self.debug_callback.replace(Some(callback));
+        true
+    }
+
+    pub fn object_label(&self, identifier: Enum, name: &UInt, label: &str) {
+        self.log_call(|| format!("object_label({identifier:#x}, {name:?}, {label:?})"));
+        if !gl::ObjectLabel::is_loaded() {
+            return;
+        }
+        unsafe {
+            gl::ObjectLabel(
+                identifier,
+                *name,
+                label.len() as SizeI,
+                label.as_ptr() as *const Char,
+            );
+        }
+    }
+}
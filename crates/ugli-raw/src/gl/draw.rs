@@ -2,18 +2,23 @@ use super::*;
 
 impl Context {
     pub fn clear(&self, mask: BitField) {
+        self.log_call(|| format!("clear({mask:#x})"));
         unsafe {
             gl::Clear(mask);
         }
     }
 
     pub fn draw_arrays(&self, mode: Enum, first: Int, count: SizeI) {
+        self.log_call(|| format!("draw_arrays({mode:#x}, {first:?}, {count:?})"));
         unsafe {
             gl::DrawArrays(mode, first, count);
         }
     }
 
     pub fn draw_arrays_instanced(&self, mode: Enum, first: Int, count: SizeI, primcount: SizeI) {
+        self.log_call(|| {
+            format!("draw_arrays_instanced({mode:#x}, {first:?}, {count:?}, {primcount:?})")
+        });
         unsafe {
             gl::DrawArraysInstanced(mode, first, count, primcount);
         }
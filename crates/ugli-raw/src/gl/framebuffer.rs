@@ -4,16 +4,19 @@ pub type Framebuffer = gl::types::GLuint;
 
 impl Context {
     pub fn bind_framebuffer(&self, target: Enum, framebuffer: Option<&Framebuffer>) {
+        self.log_call(|| format!("bind_framebuffer({target:#x}, {framebuffer:?})"));
         unsafe {
             gl::BindFramebuffer(target, *framebuffer.unwrap_or(&0));
         }
     }
 
     pub fn check_framebuffer_status(&self, target: Enum) -> Enum {
+        self.log_call(|| format!("check_framebuffer_status({target:#x})"));
         unsafe { gl::CheckFramebufferStatus(target) }
     }
 
     pub fn create_framebuffer(&self) -> Option<Framebuffer> {
+        self.log_call(|| "create_framebuffer()".to_owned());
         let mut handle = std::mem::MaybeUninit::uninit();
         unsafe {
             gl::GenFramebuffers(1, handle.as_mut_ptr());
@@ -27,6 +30,7 @@ impl Context {
     }
 
     pub fn delete_framebuffer(&self, framebuffer: &Framebuffer) {
+        self.log_call(|| format!("delete_framebuffer({framebuffer:?})"));
         unsafe {
             gl::DeleteFramebuffers(1, framebuffer);
         }
@@ -39,6 +43,7 @@ impl Context {
         renderbuffer_target: Enum,
         renderbuffer: Option<&Renderbuffer>,
     ) {
+        self.log_call(|| format!("framebuffer_renderbuffer({target:#x}, {attachment:#x}, {renderbuffer_target:#x}, {renderbuffer:?})"));
         unsafe {
             gl::FramebufferRenderbuffer(
                 target,
@@ -57,6 +62,7 @@ impl Context {
         texture: Option<&Texture>,
         level: Int,
     ) {
+        self.log_call(|| format!("framebuffer_texture_2d({target:#x}, {attachment:#x}, {texture_target:#x}, {texture:?}, {level:?})"));
         unsafe {
             gl::FramebufferTexture2D(
                 target,
@@ -79,8 +85,26 @@ impl Context {
         typ: Enum,
         pixels: &mut [T],
     ) {
+        self.log_call(|| format!("read_pixels({x:?}, {y:?}, {width:?}, {height:?}, {format:#x}, {typ:#x}, <{} items>)", pixels.len()));
         unsafe {
             gl::ReadPixels(x, y, width, height, format, typ, pixels.as_mut_ptr() as _);
         }
     }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn read_pixels_to_buffer(
+        &self,
+        x: Int,
+        y: Int,
+        width: SizeI,
+        height: SizeI,
+        format: Enum,
+        typ: Enum,
+        offset: IntPtr,
+    ) {
+        self.log_call(|| format!("read_pixels_to_buffer({x:?}, {y:?}, {width:?}, {height:?}, {format:#x}, {typ:#x}, {offset:?})"));
+        unsafe {
+            gl::ReadPixels(x, y, width, height, format, typ, offset as _);
+        }
+    }
 }
@@ -11,35 +11,47 @@ pub type UByte = gl::types::GLubyte;
 pub type UInt = gl::types::GLuint;
 pub type SizeIPtr = gl::types::GLsizeiptr;
 
-pub struct Context {}
+pub struct Context {
+    pub(crate) call_log: crate::CallLog,
+    debug_callback: std::cell::RefCell<Option<Box<DebugCallback>>>,
+}
 
 impl Context {
     pub fn new<F: Fn(&str) -> *const std::os::raw::c_void>(get_proc_address: F) -> Self {
         gl::load_with(get_proc_address);
-        Self {}
+        Self {
+            call_log: Default::default(),
+            debug_callback: Default::default(),
+        }
     }
 }
 
 mod buffer;
 mod constants;
+mod debug;
 mod draw;
 mod framebuffer;
 mod program_shader;
 mod renderbuffer;
 mod state;
+mod sync;
 mod texture;
+mod transform_feedback;
 mod uniform_attribute;
 mod vao;
 mod view;
 
 pub use buffer::*;
 pub use constants::*;
+pub use debug::*;
 pub use draw::*;
 pub use framebuffer::*;
 pub use program_shader::*;
 pub use renderbuffer::*;
 pub use state::*;
+pub use sync::*;
 pub use texture::*;
+pub use transform_feedback::*;
 pub use uniform_attribute::*;
 pub use vao::*;
 pub use view::*;
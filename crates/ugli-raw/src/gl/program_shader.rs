@@ -5,18 +5,21 @@ pub type Shader = gl::types::GLuint;
 
 impl Context {
     pub fn attach_shader(&self, program: &Program, shader: &Shader) {
+        self.log_call(|| format!("attach_shader({program:?}, {shader:?})"));
         unsafe {
             gl::AttachShader(*program, *shader);
         }
     }
 
     pub fn compile_shader(&self, shader: &Shader) {
+        self.log_call(|| format!("compile_shader({shader:?})"));
         unsafe {
             gl::CompileShader(*shader);
         }
     }
 
     pub fn create_program(&self) -> Option<Program> {
+        self.log_call(|| "create_program()".to_owned());
         let handle = unsafe { gl::CreateProgram() };
         if handle == 0 {
             None
@@ -26,6 +29,7 @@ impl Context {
     }
 
     pub fn create_shader(&self, typ: Enum) -> Option<Shader> {
+        self.log_call(|| format!("create_shader({typ:#x})"));
         let handle = unsafe { gl::CreateShader(typ) };
         if handle == 0 {
             None
@@ -35,24 +39,28 @@ impl Context {
     }
 
     pub fn delete_program(&self, program: &Program) {
+        self.log_call(|| format!("delete_program({program:?})"));
         unsafe {
             gl::DeleteProgram(*program);
         }
     }
 
     pub fn delete_shader(&self, shader: &Shader) {
+        self.log_call(|| format!("delete_shader({shader:?})"));
         unsafe {
             gl::DeleteShader(*shader);
         }
     }
 
     pub fn detach_shader(&self, program: &Program, shader: &Shader) {
+        self.log_call(|| format!("detach_shader({program:?}, {shader:?})"));
         unsafe {
             gl::DetachShader(*program, *shader);
         }
     }
 
     pub fn get_program_info_log(&self, program: &Program) -> String {
+        self.log_call(|| format!("get_program_info_log({program:?})"));
         let mut info_log_length = std::mem::MaybeUninit::uninit();
         unsafe {
             gl::GetProgramiv(*program, gl::INFO_LOG_LENGTH, info_log_length.as_mut_ptr());
@@ -76,10 +84,12 @@ impl Context {
     }
 
     pub fn get_program_parameter_bool(&self, program: &Program, pname: Enum) -> Bool {
+        self.log_call(|| format!("get_program_parameter_bool({program:?}, {pname:#x})"));
         self.get_program_parameter_int(program, pname) as Bool
     }
 
     pub fn get_program_parameter_int(&self, program: &Program, pname: Enum) -> Int {
+        self.log_call(|| format!("get_program_parameter_int({program:?}, {pname:#x})"));
         let mut result = std::mem::MaybeUninit::uninit();
         unsafe {
             gl::GetProgramiv(*program, pname, result.as_mut_ptr());
@@ -88,6 +98,7 @@ impl Context {
     }
 
     pub fn get_shader_info_log(&self, shader: &Shader) -> String {
+        self.log_call(|| format!("get_shader_info_log({shader:?})"));
         let mut info_log_length = std::mem::MaybeUninit::uninit();
         unsafe {
             gl::GetShaderiv(*shader, gl::INFO_LOG_LENGTH, info_log_length.as_mut_ptr());
@@ -111,10 +122,12 @@ impl Context {
     }
 
     pub fn get_shader_parameter_bool(&self, shader: &Shader, pname: Enum) -> Bool {
+        self.log_call(|| format!("get_shader_parameter_bool({shader:?}, {pname:#x})"));
         self.get_shader_parameter_int(shader, pname) as Bool
     }
 
     pub fn get_shader_parameter_int(&self, shader: &Shader, pname: Enum) -> Int {
+        self.log_call(|| format!("get_shader_parameter_int({shader:?}, {pname:#x})"));
         let mut result = std::mem::MaybeUninit::uninit();
         unsafe {
             gl::GetShaderiv(*shader, pname, result.as_mut_ptr());
@@ -123,12 +136,14 @@ impl Context {
     }
 
     pub fn link_program(&self, program: &Program) {
+        self.log_call(|| format!("link_program({program:?})"));
         unsafe {
             gl::LinkProgram(*program);
         }
     }
 
     pub fn shader_source(&self, shader: &Shader, source: &str) {
+        self.log_call(|| format!("shader_source({shader:?}, {source:?})"));
         unsafe {
             gl::ShaderSource(
                 *shader,
@@ -140,6 +155,7 @@ impl Context {
     }
 
     pub fn use_program(&self, program: &Program) {
+        self.log_call(|| format!("use_program({program:?})"));
         unsafe {
             gl::UseProgram(*program);
         }
@@ -4,12 +4,14 @@ pub type Renderbuffer = gl::types::GLuint;
 
 impl Context {
     pub fn bind_renderbuffer(&self, target: Enum, renderbuffer: &Renderbuffer) {
+        self.log_call(|| format!("bind_renderbuffer({target:#x}, {renderbuffer:?})"));
         unsafe {
             gl::BindRenderbuffer(target, *renderbuffer);
         }
     }
 
     pub fn create_renderbuffer(&self) -> Option<Renderbuffer> {
+        self.log_call(|| "create_renderbuffer()".to_owned());
         let mut handle = std::mem::MaybeUninit::uninit();
         unsafe {
             gl::GenRenderbuffers(1, handle.as_mut_ptr());
@@ -23,6 +25,7 @@ impl Context {
     }
 
     pub fn delete_renderbuffer(&self, renderbuffer: &Renderbuffer) {
+        self.log_call(|| format!("delete_renderbuffer({renderbuffer:?})"));
         unsafe {
             gl::DeleteRenderbuffers(1, renderbuffer);
         }
@@ -35,6 +38,11 @@ impl Context {
         width: SizeI,
         height: SizeI,
     ) {
+        self.log_call(|| {
+            format!(
+                "renderbuffer_storage({target:#x}, {internal_format:#x}, {width:?}, {height:?})"
+            )
+        });
         unsafe {
             gl::RenderbufferStorage(target, internal_format, width, height);
         }
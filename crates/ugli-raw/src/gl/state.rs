@@ -2,6 +2,7 @@ use super::*;
 
 impl Context {
     pub fn blend_func(&self, src_factor: Enum, dst_factor: Enum) {
+        self.log_call(|| format!("blend_func({src_factor:#x}, {dst_factor:#x})"));
         unsafe {
             gl::BlendFunc(src_factor, dst_factor);
         }
@@ -14,6 +15,7 @@ impl Context {
         src_factor_alpha: Enum,
         dst_factor_alpha: Enum,
     ) {
+        self.log_call(|| format!("blend_func_separate({src_factor_rgb:#x}, {dst_factor_rgb:#x}, {src_factor_alpha:#x}, {dst_factor_alpha:#x})"));
         unsafe {
             gl::BlendFuncSeparate(
                 src_factor_rgb,
@@ -25,6 +27,7 @@ impl Context {
     }
 
     pub fn blend_equation_separate(&self, mode_rgb: Enum, mode_alpha: Enum) {
+        self.log_call(|| format!("blend_equation_separate({mode_rgb:#x}, {mode_alpha:#x})"));
         unsafe {
             gl::BlendEquationSeparate(mode_rgb, mode_alpha);
         }
@@ -37,12 +40,14 @@ impl Context {
         blue: ClampedFloat,
         alpha: ClampedFloat,
     ) {
+        self.log_call(|| format!("clear_color({red:?}, {green:?}, {blue:?}, {alpha:?})"));
         unsafe {
             gl::ClearColor(red, green, blue, alpha);
         }
     }
 
     pub fn clear_depth(&self, depth: ClampedFloat) {
+        self.log_call(|| format!("clear_depth({depth:?})"));
         unsafe {
             #[cfg(target_os = "android")]
             gl::ClearDepthf(depth.into());
@@ -52,58 +57,68 @@ impl Context {
     }
 
     pub fn clear_stencil(&self, stencil: Int) {
+        self.log_call(|| format!("clear_stencil({stencil:?})"));
         unsafe {
             gl::ClearStencil(stencil);
         }
     }
 
     pub fn color_mask(&self, red: Bool, green: Bool, blue: Bool, alpha: Bool) {
+        self.log_call(|| format!("color_mask({red:?}, {green:?}, {blue:?}, {alpha:?})"));
         unsafe {
             gl::ColorMask(red, green, blue, alpha);
         }
     }
 
     pub fn cull_face(&self, mode: Enum) {
+        self.log_call(|| format!("cull_face({mode:#x})"));
         unsafe {
             gl::CullFace(mode);
         }
     }
 
     pub fn depth_func(&self, func: Enum) {
+        self.log_call(|| format!("depth_func({func:#x})"));
         unsafe {
             gl::DepthFunc(func);
         }
     }
 
     pub fn depth_mask(&self, flag: Bool) {
+        self.log_call(|| format!("depth_mask({flag:?})"));
         unsafe {
             gl::DepthMask(flag);
         }
     }
 
     pub fn disable(&self, cap: Enum) {
+        self.log_call(|| format!("disable({cap:#x})"));
         unsafe {
             gl::Disable(cap);
         }
     }
 
     pub fn enable(&self, cap: Enum) {
+        self.log_call(|| format!("enable({cap:#x})"));
         unsafe {
             gl::Enable(cap);
         }
     }
 
     pub fn get_error(&self) -> Enum {
+        self.log_call(|| "get_error()".to_owned());
         unsafe { gl::GetError() }
     }
 
     pub fn line_width(&self, width: Float) {
+        self.log_call(|| format!("line_width({width:?})"));
         unsafe {
             gl::LineWidth(width);
         }
     }
 
     pub fn get_version_string(&self) -> String {
+        self.log_call(|| "get_version_string()".to_owned());
         unsafe {
             std::ffi::CStr::from_ptr(gl::GetString(gl::VERSION) as _)
                 .to_str()
@@ -112,25 +127,58 @@ impl Context {
         }
     }
 
+    pub fn get_supported_extensions(&self) -> Vec<String> {
+        self.log_call(|| "get_supported_extensions()".to_owned());
+        unsafe {
+            let mut count = 0;
+            gl::GetIntegerv(gl::NUM_EXTENSIONS, &mut count);
+            (0..count as UInt)
+                .map(|index| {
+                    std::ffi::CStr::from_ptr(gl::GetStringi(gl::EXTENSIONS, index) as _)
+                        .to_string_lossy()
+                        .into_owned()
+                })
+                .collect()
+        }
+    }
+
+    /// Extensions are always enabled in desktop GL
+    pub fn enable_extension(&self, name: &str) -> bool {
+        self.get_supported_extensions()
+            .iter()
+            .any(|extension| extension == name)
+    }
+
     pub fn pixel_store(&self, pname: Enum, param: Int) {
+        self.log_call(|| format!("pixel_store({pname:#x}, {param:?})"));
         unsafe {
             gl::PixelStorei(pname, param);
         }
     }
 
     pub fn stencil_func_separate(&self, face: Enum, func: Enum, r#ref: Int, mask: UInt) {
+        self.log_call(|| {
+            format!(
+                "stencil_func_separate({face:#x}, {func:#x}, {:?}, {mask:?})",
+                r#ref
+            )
+        });
         unsafe {
             gl::StencilFuncSeparate(face, func, r#ref, mask);
         }
     }
 
     pub fn stencil_mask_separate(&self, face: Enum, mask: UInt) {
+        self.log_call(|| format!("stencil_mask_separate({face:#x}, {mask:?})"));
         unsafe {
             gl::StencilMaskSeparate(face, mask);
         }
     }
 
     pub fn stencil_op_separate(&self, face: Enum, fail: Enum, zfail: Enum, pass: Enum) {
+        self.log_call(|| {
+            format!("stencil_op_separate({face:#x}, {fail:#x}, {zfail:#x}, {pass:#x})")
+        });
         unsafe {
             gl::StencilOpSeparate(face, fail, zfail, pass);
         }
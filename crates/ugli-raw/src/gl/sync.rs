@@ -0,0 +1,34 @@
+use super::*;
+
+pub type Fence = gl::types::GLsync;
+
+impl Context {
+    pub fn fence_sync(&self, condition: Enum, flags: BitField) -> Option<Fence> {
+        self.log_call(|| format!("fence_sync({condition:#x}, {flags:#x})"));
+        let handle = unsafe { gl::FenceSync(condition, flags) };
+        if handle.is_null() {
+            None
+        } else {
+            Some(handle)
+        }
+    }
+
+    pub fn client_wait_sync(&self, fence: &Fence, flags: BitField, timeout: u64) -> Enum {
+        self.log_call(|| format!("client_wait_sync({fence:?}, {flags:#x}, {timeout:?})"));
+        unsafe { gl::ClientWaitSync(*fence, flags, timeout) }
+    }
+
+    pub fn delete_sync(&self, fence: &Fence) {
+        self.log_call(|| format!("delete_sync({fence:?})"));
+        unsafe {
+            gl::DeleteSync(*fence);
+        }
+    }
+
+    pub fn flush(&self) {
+        self.log_call(|| "flush()".to_owned());
+        unsafe {
+            gl::Flush();
+        }
+    }
+}
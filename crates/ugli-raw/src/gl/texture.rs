@@ -6,18 +6,21 @@ impl Context {
     pub fn pixel_store_flip_y(&self, _flip: bool) {}
 
     pub fn active_texture(&self, texture: Enum) {
+        self.log_call(|| format!("active_texture({texture:#x})"));
         unsafe {
             gl::ActiveTexture(texture);
         }
     }
 
     pub fn bind_texture(&self, target: Enum, texture: &Texture) {
+        self.log_call(|| format!("bind_texture({target:#x}, {texture:?})"));
         unsafe {
             gl::BindTexture(target, *texture);
         }
     }
 
     pub fn create_texture(&self) -> Option<Texture> {
+        self.log_call(|| "create_texture()".to_owned());
         let mut handle = std::mem::MaybeUninit::uninit();
         unsafe {
             gl::GenTextures(1, handle.as_mut_ptr());
@@ -31,17 +34,45 @@ impl Context {
     }
 
     pub fn delete_texture(&self, texture: &Texture) {
+        self.log_call(|| format!("delete_texture({texture:?})"));
         unsafe {
             gl::DeleteTextures(1, texture);
         }
     }
 
     pub fn generate_mipmap(&self, target: Enum) {
+        self.log_call(|| format!("generate_mipmap({target:#x})"));
         unsafe {
             gl::GenerateMipmap(target);
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn compressed_tex_image_2d(
+        &self,
+        target: Enum,
+        level: Int,
+        internal_format: Enum,
+        width: SizeI,
+        height: SizeI,
+        border: Int,
+        data: &[u8],
+    ) {
+        self.log_call(|| format!("compressed_tex_image_2d({target:#x}, {level:?}, {internal_format:#x}, {width:?}, {height:?}, {border:?}, <{} bytes>)", data.len()));
+        unsafe {
+            gl::CompressedTexImage2D(
+                target,
+                level,
+                internal_format,
+                width,
+                height,
+                border,
+                data.len() as SizeI,
+                data.as_ptr() as _,
+            );
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn tex_image_2d<T>(
         &self,
@@ -55,6 +86,7 @@ impl Context {
         typ: Enum,
         pixels: Option<&[T]>,
     ) {
+        self.log_call(|| format!("tex_image_2d({target:#x}, {level:?}, {internal_format:?}, {width:?}, {height:?}, {border:?}, {format:#x}, {typ:#x}, <{:?} items>)", pixels.map(|pixels| pixels.len())));
         unsafe {
             gl::TexImage2D(
                 target,
@@ -74,6 +106,7 @@ impl Context {
     }
 
     pub fn tex_parameteri(&self, target: Enum, pname: Enum, param: Int) {
+        self.log_call(|| format!("tex_parameteri({target:#x}, {pname:#x}, {param:?})"));
         unsafe {
             gl::TexParameteri(target, pname, param);
         }
@@ -92,6 +125,7 @@ impl Context {
         typ: Enum,
         pixels: &[T],
     ) {
+        self.log_call(|| format!("tex_sub_image_2d({target:#x}, {level:?}, {x_offset:?}, {y_offset:?}, {width:?}, {height:?}, {format:#x}, {typ:#x}, <{} items>)", pixels.len()));
         unsafe {
             gl::TexSubImage2D(
                 target,
@@ -119,6 +153,7 @@ impl Context {
         width: SizeI,
         height: SizeI,
     ) {
+        self.log_call(|| format!("copy_tex_sub_image_2d({target:#x}, {level:?}, {x_offset:?}, {y_offset:?}, {x:?}, {y:?}, {width:?}, {height:?})"));
         unsafe {
             gl::CopyTexSubImage2D(target, level, x_offset, y_offset, x, y, width, height);
         }
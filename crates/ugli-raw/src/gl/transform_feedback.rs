@@ -0,0 +1,48 @@
+use super::*;
+
+impl Context {
+    pub fn transform_feedback_varyings(
+        &self,
+        program: &Program,
+        varyings: &[&str],
+        buffer_mode: Enum,
+    ) {
+        self.log_call(|| {
+            format!("transform_feedback_varyings({program:?}, {varyings:?}, {buffer_mode:#x})")
+        });
+        let varyings: Vec<std::ffi::CString> = varyings
+            .iter()
+            .map(|name| std::ffi::CString::new(*name).unwrap())
+            .collect();
+        let varyings: Vec<*const Char> = varyings.iter().map(|name| name.as_ptr()).collect();
+        unsafe {
+            gl::TransformFeedbackVaryings(
+                *program,
+                varyings.len() as SizeI,
+                varyings.as_ptr(),
+                buffer_mode,
+            );
+        }
+    }
+
+    pub fn bind_buffer_base(&self, target: Enum, index: UInt, buffer: Option<&Buffer>) {
+        self.log_call(|| format!("bind_buffer_base({target:#x}, {index:?}, {buffer:?})"));
+        unsafe {
+            gl::BindBufferBase(target, index, *buffer.unwrap_or(&0));
+        }
+    }
+
+    pub fn begin_transform_feedback(&self, primitive_mode: Enum) {
+        self.log_call(|| format!("begin_transform_feedback({primitive_mode:#x})"));
+        unsafe {
+            gl::BeginTransformFeedback(primitive_mode);
+        }
+    }
+
+    pub fn end_transform_feedback(&self) {
+        self.log_call(|| "end_transform_feedback()".to_owned());
+        unsafe {
+            gl::EndTransformFeedback();
+        }
+    }
+}
@@ -11,18 +11,21 @@ pub struct ActiveInfo {
 
 impl Context {
     pub fn disable_vertex_attrib_array(&self, index: UInt) {
+        self.log_call(|| format!("disable_vertex_attrib_array({index:?})"));
         unsafe {
             gl::DisableVertexAttribArray(index);
         }
     }
 
     pub fn enable_vertex_attrib_array(&self, index: UInt) {
+        self.log_call(|| format!("enable_vertex_attrib_array({index:?})"));
         unsafe {
             gl::EnableVertexAttribArray(index);
         }
     }
 
     pub fn get_active_attrib(&self, program: &Program, index: UInt) -> ActiveInfo {
+        self.log_call(|| format!("get_active_attrib({program:?}, {index:?})"));
         let mut max_length = std::mem::MaybeUninit::uninit();
         unsafe {
             gl::GetProgramiv(
@@ -60,6 +63,7 @@ impl Context {
     }
 
     pub fn get_active_uniform(&self, program: &Program, index: UInt) -> ActiveInfo {
+        self.log_call(|| format!("get_active_uniform({program:?}, {index:?})"));
         let mut max_length = std::mem::MaybeUninit::uninit();
         unsafe {
             gl::GetProgramiv(
@@ -97,6 +101,7 @@ impl Context {
     }
 
     pub fn get_attrib_location(&self, program: &Program, name: &str) -> Int {
+        self.log_call(|| format!("get_attrib_location({program:?}, {name:?})"));
         let name = std::ffi::CString::new(name).unwrap();
         unsafe { gl::GetAttribLocation(*program, name.as_ptr()) }
     }
@@ -107,6 +112,12 @@ impl Context {
         location: &UniformLocation,
         params: &mut [Int],
     ) {
+        self.log_call(|| {
+            format!(
+                "get_uniform_int({program:?}, {location:?}, <{} items>)",
+                params.len()
+            )
+        });
         unsafe {
             gl::GetUniformiv(*program, *location, params.as_mut_ptr());
         }
@@ -118,12 +129,19 @@ impl Context {
         location: &UniformLocation,
         params: &mut [Float],
     ) {
+        self.log_call(|| {
+            format!(
+                "get_uniform_float({program:?}, {location:?}, <{} items>)",
+                params.len()
+            )
+        });
         unsafe {
             gl::GetUniformfv(*program, *location, params.as_mut_ptr());
         }
     }
 
     pub fn get_uniform_location(&self, program: &Program, name: &str) -> Option<UniformLocation> {
+        self.log_call(|| format!("get_uniform_location({program:?}, {name:?})"));
         let name = std::ffi::CString::new(name).unwrap();
         unsafe {
             let location = gl::GetUniformLocation(*program, name.as_ptr());
@@ -136,42 +154,49 @@ impl Context {
     }
 
     pub fn uniform_1i(&self, location: &UniformLocation, v: Int) {
+        self.log_call(|| format!("uniform_1i({location:?}, {v:?})"));
         unsafe {
             gl::Uniform1i(*location, v);
         }
     }
 
     pub fn uniform_1f(&self, location: &UniformLocation, v: Float) {
+        self.log_call(|| format!("uniform_1f({location:?}, {v:?})"));
         unsafe {
             gl::Uniform1f(*location, v);
         }
     }
 
     pub fn uniform_2i(&self, location: &UniformLocation, v0: Int, v1: Int) {
+        self.log_call(|| format!("uniform_2i({location:?}, {v0:?}, {v1:?})"));
         unsafe {
             gl::Uniform2i(*location, v0, v1);
         }
     }
 
     pub fn uniform_2f(&self, location: &UniformLocation, v0: Float, v1: Float) {
+        self.log_call(|| format!("uniform_2f({location:?}, {v0:?}, {v1:?})"));
         unsafe {
             gl::Uniform2f(*location, v0, v1);
         }
     }
 
     pub fn uniform_3i(&self, location: &UniformLocation, v0: Int, v1: Int, v2: Int) {
+        self.log_call(|| format!("uniform_3i({location:?}, {v0:?}, {v1:?}, {v2:?})"));
         unsafe {
             gl::Uniform3i(*location, v0, v1, v2);
         }
     }
 
     pub fn uniform_3f(&self, location: &UniformLocation, v0: Float, v1: Float, v2: Float) {
+        self.log_call(|| format!("uniform_3f({location:?}, {v0:?}, {v1:?}, {v2:?})"));
         unsafe {
             gl::Uniform3f(*location, v0, v1, v2);
         }
     }
 
     pub fn uniform_4i(&self, location: &UniformLocation, v0: Int, v1: Int, v2: Int, v3: Int) {
+        self.log_call(|| format!("uniform_4i({location:?}, {v0:?}, {v1:?}, {v2:?}, {v3:?})"));
         unsafe {
             gl::Uniform4i(*location, v0, v1, v2, v3);
         }
@@ -185,6 +210,7 @@ impl Context {
         v2: Float,
         v3: Float,
     ) {
+        self.log_call(|| format!("uniform_4f({location:?}, {v0:?}, {v1:?}, {v2:?}, {v3:?})"));
         unsafe {
             gl::Uniform4f(*location, v0, v1, v2, v3);
         }
@@ -197,6 +223,9 @@ impl Context {
         transpose: Bool,
         v: &[Float],
     ) {
+        self.log_call(|| {
+            format!("uniform_matrix2fv({location:?}, {count:?}, {transpose:?}, {v:?})")
+        });
         debug_assert_eq!(v.len(), count as usize * 2 * 2);
         unsafe {
             gl::UniformMatrix2fv(*location, count, transpose, v.as_ptr());
@@ -210,6 +239,9 @@ impl Context {
         transpose: Bool,
         v: &[Float],
     ) {
+        self.log_call(|| {
+            format!("uniform_matrix3fv({location:?}, {count:?}, {transpose:?}, {v:?})")
+        });
         debug_assert_eq!(v.len(), count as usize * 3 * 3);
         unsafe {
             gl::UniformMatrix3fv(*location, count, transpose, v.as_ptr());
@@ -223,6 +255,9 @@ impl Context {
         transpose: Bool,
         v: &[Float],
     ) {
+        self.log_call(|| {
+            format!("uniform_matrix4fv({location:?}, {count:?}, {transpose:?}, {v:?})")
+        });
         debug_assert_eq!(v.len(), count as usize * 4 * 4);
         unsafe {
             gl::UniformMatrix4fv(*location, count, transpose, v.as_ptr());
@@ -230,6 +265,7 @@ impl Context {
     }
 
     pub fn vertex_attrib_divisor(&self, index: UInt, divisor: UInt) {
+        self.log_call(|| format!("vertex_attrib_divisor({index:?}, {divisor:?})"));
         unsafe {
             gl::VertexAttribDivisor(index, divisor);
         }
@@ -244,8 +280,27 @@ impl Context {
         stride: SizeI,
         offset: IntPtr,
     ) {
+        self.log_call(|| format!("vertex_attrib_pointer({index:?}, {size:?}, {typ:#x}, {normalized:?}, {stride:?}, {offset:?})"));
         unsafe {
             gl::VertexAttribPointer(index, size, typ, normalized, stride, offset as _);
         }
     }
+
+    pub fn vertex_attrib_i_pointer(
+        &self,
+        index: UInt,
+        size: Int,
+        typ: Enum,
+        stride: SizeI,
+        offset: IntPtr,
+    ) {
+        self.log_call(|| {
+            format!(
+                "vertex_attrib_i_pointer({index:?}, {size:?}, {typ:#x}, {stride:?}, {offset:?})"
+            )
+        });
+        unsafe {
+            gl::VertexAttribIPointer(index, size, typ, stride, offset as _);
+        }
+    }
 }
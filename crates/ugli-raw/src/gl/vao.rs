@@ -4,12 +4,14 @@ pub type VertexArrayObject = gl::types::GLuint;
 
 impl Context {
     pub fn bind_vertex_array(&self, vao: &VertexArrayObject) {
+        self.log_call(|| format!("bind_vertex_array({vao:?})"));
         unsafe {
             gl::BindVertexArray(*vao);
         }
     }
 
     pub fn create_vertex_array(&self) -> Option<VertexArrayObject> {
+        self.log_call(|| "create_vertex_array()".to_owned());
         let mut handle = std::mem::MaybeUninit::uninit();
         unsafe {
             gl::GenVertexArrays(1, handle.as_mut_ptr());
@@ -23,6 +25,7 @@ impl Context {
     }
 
     pub fn delete_vertex_array(&self, vao: &VertexArrayObject) {
+        self.log_call(|| format!("delete_vertex_array({vao:?})"));
         unsafe {
             gl::DeleteVertexArrays(1, vao);
         }
@@ -7,3 +7,7 @@ mod implementation;
 mod implementation;
 
 pub use implementation::*;
+
+mod call_log;
+
+use call_log::CallLog;
@@ -3,11 +3,18 @@ use super::*;
 pub type Buffer = web_sys::WebGlBuffer;
 
 impl Context {
-    pub fn bind_buffer(&self, target: Enum, buffer: &Buffer) {
-        self.inner.bind_buffer(target, Some(buffer));
+    pub fn bind_buffer(&self, target: Enum, buffer: Option<&Buffer>) {
+        self.log_call(|| format!("bind_buffer({target:#x}, {buffer:?})"));
+        self.inner.bind_buffer(target, buffer);
     }
 
     pub fn buffer_data<T>(&self, target: Enum, data: &[T], usage: Enum) {
+        self.log_call(|| {
+            format!(
+                "buffer_data({target:#x}, <{} items>, {usage:#x})",
+                data.len()
+            )
+        });
         self.inner.buffer_data_with_u8_array(
             target,
             unsafe {
@@ -17,18 +24,49 @@ impl Context {
         );
     }
 
+    pub fn buffer_data_size(&self, target: Enum, size: SizeIPtr, usage: Enum) {
+        self.log_call(|| format!("buffer_data_size({target:#x}, {size:?}, {usage:#x})"));
+        self.inner.buffer_data_with_i32(target, size, usage);
+    }
+
     pub fn buffer_sub_data<T>(&self, target: Enum, offset: IntPtr, data: &[T]) {
+        self.log_call(|| {
+            format!(
+                "buffer_sub_data({target:#x}, {offset:?}, <{} items>)",
+                data.len()
+            )
+        });
         self.inner
             .buffer_sub_data_with_i32_and_u8_array(target, offset, unsafe {
                 std::slice::from_raw_parts(data.as_ptr() as *const u8, std::mem::size_of_val(data))
             });
     }
 
+    /// Always succeeds, returns `bool` to match the native backend
+    pub fn get_buffer_sub_data<T>(&self, target: Enum, offset: IntPtr, data: &mut [T]) -> bool {
+        self.log_call(|| {
+            format!(
+                "get_buffer_sub_data({target:#x}, {offset:?}, <{} items>)",
+                data.len()
+            )
+        });
+        self.webgl2()
+            .get_buffer_sub_data_with_i32_and_u8_array(target, offset, unsafe {
+                std::slice::from_raw_parts_mut(
+                    data.as_mut_ptr() as *mut u8,
+                    std::mem::size_of_val(data),
+                )
+            });
+        true
+    }
+
     pub fn create_buffer(&self) -> Option<Buffer> {
+        self.log_call(|| "create_buffer()".to_owned());
         self.inner.create_buffer()
     }
 
     pub fn delete_buffer(&self, buffer: &Buffer) {
+        self.log_call(|| format!("delete_buffer({buffer:?})"));
         self.inner.delete_buffer(Some(buffer));
     }
 }
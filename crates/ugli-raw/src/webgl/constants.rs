@@ -39,6 +39,12 @@ pub const INT_VEC4: Enum = web_sys::WebGlRenderingContext::INT_VEC4;
 pub const FLOAT_MAT2: Enum = web_sys::WebGlRenderingContext::FLOAT_MAT2;
 pub const FLOAT_MAT3: Enum = web_sys::WebGlRenderingContext::FLOAT_MAT3;
 pub const FLOAT_MAT4: Enum = web_sys::WebGlRenderingContext::FLOAT_MAT4;
+pub const BOOL: Enum = web_sys::WebGlRenderingContext::BOOL;
+pub const BOOL_VEC2: Enum = web_sys::WebGlRenderingContext::BOOL_VEC2;
+pub const BOOL_VEC3: Enum = web_sys::WebGlRenderingContext::BOOL_VEC3;
+pub const BOOL_VEC4: Enum = web_sys::WebGlRenderingContext::BOOL_VEC4;
+pub const SAMPLER_2D: Enum = web_sys::WebGlRenderingContext::SAMPLER_2D;
+pub const SAMPLER_CUBE: Enum = web_sys::WebGlRenderingContext::SAMPLER_CUBE;
 pub const FRAMEBUFFER: Enum = web_sys::WebGlRenderingContext::FRAMEBUFFER;
 pub const FRAMEBUFFER_COMPLETE: Enum = web_sys::WebGlRenderingContext::FRAMEBUFFER_COMPLETE;
 pub const FRONT: Enum = web_sys::WebGlRenderingContext::FRONT;
@@ -63,6 +69,11 @@ pub const TRIANGLES: Enum = web_sys::WebGlRenderingContext::TRIANGLES;
 pub const TRIANGLE_FAN: Enum = web_sys::WebGlRenderingContext::TRIANGLE_FAN;
 pub const TRIANGLE_STRIP: Enum = web_sys::WebGlRenderingContext::TRIANGLE_STRIP;
 pub const UNSIGNED_BYTE: Enum = web_sys::WebGlRenderingContext::UNSIGNED_BYTE;
+pub const BYTE: Enum = web_sys::WebGlRenderingContext::BYTE;
+pub const SHORT: Enum = web_sys::WebGlRenderingContext::SHORT;
+pub const UNSIGNED_SHORT: Enum = web_sys::WebGlRenderingContext::UNSIGNED_SHORT;
+pub const UNSIGNED_INT: Enum = web_sys::WebGlRenderingContext::UNSIGNED_INT;
+pub const HALF_FLOAT: Enum = web_sys::WebGl2RenderingContext::HALF_FLOAT;
 // pub const PROGRAM_POINT_SIZE: Enum = web_sys::WebGlRenderingContext::PROGRAM_POINT_SIZE;
 pub const DEPTH_TEST: Enum = web_sys::WebGlRenderingContext::DEPTH_TEST;
 pub const ALPHA: Enum = web_sys::WebGlRenderingContext::ALPHA;
@@ -103,3 +114,38 @@ pub const FUNC_SUBTRACT: Enum = web_sys::WebGlRenderingContext::FUNC_SUBTRACT;
 pub const FUNC_REVERSE_SUBTRACT: Enum = web_sys::WebGlRenderingContext::FUNC_REVERSE_SUBTRACT;
 pub const MIN: Enum = web_sys::ExtBlendMinmax::MIN_EXT;
 pub const MAX: Enum = web_sys::ExtBlendMinmax::MAX_EXT;
+
+pub const PIXEL_PACK_BUFFER: Enum = web_sys::WebGl2RenderingContext::PIXEL_PACK_BUFFER;
+pub const STREAM_READ: Enum = web_sys::WebGl2RenderingContext::STREAM_READ;
+pub const SYNC_GPU_COMMANDS_COMPLETE: Enum =
+    web_sys::WebGl2RenderingContext::SYNC_GPU_COMMANDS_COMPLETE;
+pub const SYNC_FLUSH_COMMANDS_BIT: Enum = web_sys::WebGl2RenderingContext::SYNC_FLUSH_COMMANDS_BIT;
+pub const ALREADY_SIGNALED: Enum = web_sys::WebGl2RenderingContext::ALREADY_SIGNALED;
+pub const CONDITION_SATISFIED: Enum = web_sys::WebGl2RenderingContext::CONDITION_SATISFIED;
+pub const TIMEOUT_EXPIRED: Enum = web_sys::WebGl2RenderingContext::TIMEOUT_EXPIRED;
+pub const WAIT_FAILED: Enum = web_sys::WebGl2RenderingContext::WAIT_FAILED;
+pub const UNSIGNED_INT_VEC2: Enum = web_sys::WebGl2RenderingContext::UNSIGNED_INT_VEC2;
+pub const UNSIGNED_INT_VEC3: Enum = web_sys::WebGl2RenderingContext::UNSIGNED_INT_VEC3;
+pub const UNSIGNED_INT_VEC4: Enum = web_sys::WebGl2RenderingContext::UNSIGNED_INT_VEC4;
+pub const INTERLEAVED_ATTRIBS: Enum = web_sys::WebGl2RenderingContext::INTERLEAVED_ATTRIBS;
+pub const RASTERIZER_DISCARD: Enum = web_sys::WebGl2RenderingContext::RASTERIZER_DISCARD;
+pub const TRANSFORM_FEEDBACK_BUFFER: Enum =
+    web_sys::WebGl2RenderingContext::TRANSFORM_FEEDBACK_BUFFER;
+
+// Object label identifiers, only used for the call log
+pub const BUFFER: Enum = 0x82E0;
+pub const PROGRAM: Enum = 0x82E2;
+pub const TEXTURE: Enum = 0x1702;
+
+// Compressed texture formats, defined by the extensions providing them
+pub const COMPRESSED_RGB_S3TC_DXT1: Enum = 0x83F0;
+pub const COMPRESSED_RGBA_S3TC_DXT1: Enum = 0x83F1;
+pub const COMPRESSED_RGBA_S3TC_DXT3: Enum = 0x83F2;
+pub const COMPRESSED_RGBA_S3TC_DXT5: Enum = 0x83F3;
+pub const COMPRESSED_RED_RGTC1: Enum = 0x8DBB;
+pub const COMPRESSED_RG_RGTC2: Enum = 0x8DBD;
+pub const COMPRESSED_RGBA_BPTC_UNORM: Enum = 0x8E8C;
+pub const COMPRESSED_RGB_ETC1: Enum = 0x8D64;
+pub const COMPRESSED_RGB8_ETC2: Enum = 0x9274;
+pub const COMPRESSED_RGBA8_ETC2_EAC: Enum = 0x9278;
+pub const COMPRESSED_RGBA_ASTC_4X4: Enum = 0x93B0;
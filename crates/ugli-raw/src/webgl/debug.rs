@@ -0,0 +1,22 @@
+use super::*;
+
+impl Context {
+    /// Debug output is not available in WebGL, always returns `false`
+    pub fn debug_message_callback(
+        &self,
+        _callback: impl Fn(Enum, Enum, UInt, Enum, &str) + 'static,
+    ) -> bool {
+        false
+    }
+
+    /// Object labels are not available in WebGL, only recorded in the call log
+    pub fn object_label(
+        &self,
+        identifier: Enum,
+        name: &impl AsRef<wasm_bindgen::JsValue>,
+        label: &str,
+    ) {
+        let name = name.as_ref();
+        self.log_call(|| format!("object_label({identifier:#x}, {name:?}, {label:?})"));
+    }
+}
@@ -2,15 +2,26 @@ use super::*;
 
 impl Context {
     pub fn clear(&self, mask: BitField) {
+        self.log_call(|| format!("clear({mask:#x})"));
         self.inner.clear(mask);
     }
 
     pub fn draw_arrays(&self, mode: Enum, first: Int, count: SizeI) {
+        self.log_call(|| format!("draw_arrays({mode:#x}, {first:?}, {count:?})"));
         self.inner.draw_arrays(mode, first, count);
     }
 
     pub fn draw_arrays_instanced(&self, mode: Enum, first: Int, count: SizeI, primcount: SizeI) {
-        self.angle_instanced_arrays
-            .draw_arrays_instanced_angle(mode, first, count, primcount);
+        self.log_call(|| {
+            format!("draw_arrays_instanced({mode:#x}, {first:?}, {count:?}, {primcount:?})")
+        });
+        match &self.webgl2 {
+            Some(webgl2) => webgl2.draw_arrays_instanced(mode, first, count, primcount),
+            None => self
+                .angle_instanced_arrays
+                .as_ref()
+                .unwrap()
+                .draw_arrays_instanced_angle(mode, first, count, primcount),
+        }
     }
 }
@@ -4,18 +4,22 @@ pub type Framebuffer = web_sys::WebGlFramebuffer;
 
 impl Context {
     pub fn bind_framebuffer(&self, target: Enum, framebuffer: Option<&Framebuffer>) {
+        self.log_call(|| format!("bind_framebuffer({target:#x}, {framebuffer:?})"));
         self.inner.bind_framebuffer(target, framebuffer);
     }
 
     pub fn check_framebuffer_status(&self, target: Enum) -> Enum {
+        self.log_call(|| format!("check_framebuffer_status({target:#x})"));
         self.inner.check_framebuffer_status(target)
     }
 
     pub fn create_framebuffer(&self) -> Option<Framebuffer> {
+        self.log_call(|| "create_framebuffer()".to_owned());
         self.inner.create_framebuffer()
     }
 
     pub fn delete_framebuffer(&self, framebuffer: &Framebuffer) {
+        self.log_call(|| format!("delete_framebuffer({framebuffer:?})"));
         self.inner.delete_framebuffer(Some(framebuffer));
     }
 
@@ -26,6 +30,7 @@ impl Context {
         renderbuffer_target: Enum,
         renderbuffer: Option<&Renderbuffer>,
     ) {
+        self.log_call(|| format!("framebuffer_renderbuffer({target:#x}, {attachment:#x}, {renderbuffer_target:#x}, {renderbuffer:?})"));
         self.inner
             .framebuffer_renderbuffer(target, attachment, renderbuffer_target, renderbuffer);
     }
@@ -38,6 +43,7 @@ impl Context {
         texture: Option<&Texture>,
         level: Int,
     ) {
+        self.log_call(|| format!("framebuffer_texture_2d({target:#x}, {attachment:#x}, {texture_target:#x}, {texture:?}, {level:?})"));
         self.inner
             .framebuffer_texture_2d(target, attachment, texture_target, texture, level);
     }
@@ -53,6 +59,7 @@ impl Context {
         typ: Enum,
         pixels: &mut [T],
     ) {
+        self.log_call(|| format!("read_pixels({x:?}, {y:?}, {width:?}, {height:?}, {format:#x}, {typ:#x}, <{} items>)", pixels.len()));
         self.inner
             .read_pixels_with_opt_u8_array(
                 x,
@@ -70,4 +77,21 @@ impl Context {
             )
             .unwrap();
     }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn read_pixels_to_buffer(
+        &self,
+        x: Int,
+        y: Int,
+        width: SizeI,
+        height: SizeI,
+        format: Enum,
+        typ: Enum,
+        offset: IntPtr,
+    ) {
+        self.log_call(|| format!("read_pixels_to_buffer({x:?}, {y:?}, {width:?}, {height:?}, {format:#x}, {typ:#x}, {offset:?})"));
+        self.webgl2()
+            .read_pixels_with_i32(x, y, width, height, format, typ, offset)
+            .unwrap();
+    }
 }
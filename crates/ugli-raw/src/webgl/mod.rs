@@ -12,16 +12,31 @@ pub type SizeIPtr = i32;
 
 pub struct Context {
     inner: web_sys::WebGlRenderingContext,
-    angle_instanced_arrays: web_sys::AngleInstancedArrays,
+    webgl2: Option<web_sys::WebGl2RenderingContext>,
+    angle_instanced_arrays: Option<web_sys::AngleInstancedArrays>,
     #[allow(dead_code)]
-    oes_standard_derivatives: web_sys::OesStandardDerivatives,
+    oes_standard_derivatives: Option<web_sys::OesStandardDerivatives>,
     #[allow(dead_code)]
-    blend_minmax: web_sys::ExtBlendMinmax,
+    blend_minmax: Option<web_sys::ExtBlendMinmax>,
+    pub(crate) call_log: crate::CallLog,
 }
 
 impl Context {
     pub fn new(webgl_rendering_context: web_sys::WebGlRenderingContext) -> Self {
         use wasm_bindgen::JsCast;
+        let webgl2: Option<web_sys::WebGl2RenderingContext> =
+            webgl_rendering_context.dyn_ref().cloned();
+        if webgl2.is_some() {
+            // Instancing, derivatives and min/max blending are core in WebGL2
+            return Self {
+                inner: webgl_rendering_context,
+                webgl2,
+                angle_instanced_arrays: None,
+                oes_standard_derivatives: None,
+                blend_minmax: None,
+                call_log: Default::default(),
+            };
+        }
         let angle_instanced_arrays = webgl_rendering_context
             .get_extension("ANGLE_instanced_arrays")
             .unwrap()
@@ -36,32 +51,48 @@ impl Context {
             .expect("EXT_blend_minmax not supported?");
         Self {
             inner: webgl_rendering_context,
+            webgl2: None,
             // Unchecked casts here because the type is different in different browsers
-            angle_instanced_arrays: angle_instanced_arrays.unchecked_into(),
-            oes_standard_derivatives: oes_standard_derivatives.unchecked_into(),
-            blend_minmax: blend_minmax.unchecked_into(),
+            angle_instanced_arrays: Some(angle_instanced_arrays.unchecked_into()),
+            oes_standard_derivatives: Some(oes_standard_derivatives.unchecked_into()),
+            blend_minmax: Some(blend_minmax.unchecked_into()),
+            call_log: Default::default(),
         }
     }
+
+    pub fn is_webgl2(&self) -> bool {
+        self.webgl2.is_some()
+    }
+
+    fn webgl2(&self) -> &web_sys::WebGl2RenderingContext {
+        self.webgl2.as_ref().expect("WebGL2 context required")
+    }
 }
 
 mod buffer;
 mod constants;
+mod debug;
 mod draw;
 mod framebuffer;
 mod program_shader;
 mod renderbuffer;
 mod state;
+mod sync;
 mod texture;
+mod transform_feedback;
 mod uniform_attribute;
 mod view;
 
 pub use buffer::*;
 pub use constants::*;
+pub use debug::*;
 pub use draw::*;
 pub use framebuffer::*;
 pub use program_shader::*;
 pub use renderbuffer::*;
 pub use state::*;
+pub use sync::*;
 pub use texture::*;
+pub use transform_feedback::*;
 pub use uniform_attribute::*;
 pub use view::*;
@@ -5,38 +5,47 @@ pub type Shader = web_sys::WebGlShader;
 
 impl Context {
     pub fn attach_shader(&self, program: &Program, shader: &Shader) {
+        self.log_call(|| format!("attach_shader({program:?}, {shader:?})"));
         self.inner.attach_shader(program, shader);
     }
 
     pub fn compile_shader(&self, shader: &Shader) {
+        self.log_call(|| format!("compile_shader({shader:?})"));
         self.inner.compile_shader(shader);
     }
 
     pub fn create_program(&self) -> Option<Program> {
+        self.log_call(|| "create_program()".to_owned());
         self.inner.create_program()
     }
 
     pub fn create_shader(&self, typ: Enum) -> Option<Shader> {
+        self.log_call(|| format!("create_shader({typ:#x})"));
         self.inner.create_shader(typ)
     }
 
     pub fn delete_program(&self, program: &Program) {
+        self.log_call(|| format!("delete_program({program:?})"));
         self.inner.delete_program(Some(program));
     }
 
     pub fn delete_shader(&self, shader: &Shader) {
+        self.log_call(|| format!("delete_shader({shader:?})"));
         self.inner.delete_shader(Some(shader));
     }
 
     pub fn detach_shader(&self, program: &Program, shader: &Shader) {
+        self.log_call(|| format!("detach_shader({program:?}, {shader:?})"));
         self.inner.detach_shader(program, shader);
     }
 
     pub fn get_program_info_log(&self, program: &Program) -> String {
+        self.log_call(|| format!("get_program_info_log({program:?})"));
         self.inner.get_program_info_log(program).unwrap()
     }
 
     pub fn get_program_parameter_bool(&self, program: &Program, pname: Enum) -> Bool {
+        self.log_call(|| format!("get_program_parameter_bool({program:?}, {pname:#x})"));
         self.inner
             .get_program_parameter(program, pname)
             .as_bool()
@@ -44,6 +53,7 @@ impl Context {
     }
 
     pub fn get_program_parameter_int(&self, program: &Program, pname: Enum) -> Int {
+        self.log_call(|| format!("get_program_parameter_int({program:?}, {pname:#x})"));
         self.inner
             .get_program_parameter(program, pname)
             .as_f64()
@@ -51,10 +61,12 @@ impl Context {
     }
 
     pub fn get_shader_info_log(&self, shader: &Shader) -> String {
+        self.log_call(|| format!("get_shader_info_log({shader:?})"));
         self.inner.get_shader_info_log(shader).unwrap()
     }
 
     pub fn get_shader_parameter_bool(&self, shader: &Shader, pname: Enum) -> Bool {
+        self.log_call(|| format!("get_shader_parameter_bool({shader:?}, {pname:#x})"));
         self.inner
             .get_shader_parameter(shader, pname)
             .as_bool()
@@ -62,6 +74,7 @@ impl Context {
     }
 
     pub fn get_shader_parameter_int(&self, shader: &Shader, pname: Enum) -> Int {
+        self.log_call(|| format!("get_shader_parameter_int({shader:?}, {pname:#x})"));
         self.inner
             .get_shader_parameter(shader, pname)
             .as_f64()
@@ -69,14 +82,17 @@ impl Context {
     }
 
     pub fn link_program(&self, program: &Program) {
+        self.log_call(|| format!("link_program({program:?})"));
         self.inner.link_program(program);
     }
 
     pub fn shader_source(&self, shader: &Shader, source: &str) {
+        self.log_call(|| format!("shader_source({shader:?}, {source:?})"));
         self.inner.shader_source(shader, source);
     }
 
     pub fn use_program(&self, program: &Program) {
+        self.log_call(|| format!("use_program({program:?})"));
         self.inner.use_program(Some(program));
     }
 }
@@ -4,14 +4,17 @@ pub type Renderbuffer = web_sys::WebGlRenderbuffer;
 
 impl Context {
     pub fn bind_renderbuffer(&self, target: Enum, renderbuffer: &Renderbuffer) {
+        self.log_call(|| format!("bind_renderbuffer({target:#x}, {renderbuffer:?})"));
         self.inner.bind_renderbuffer(target, Some(renderbuffer));
     }
 
     pub fn create_renderbuffer(&self) -> Option<Renderbuffer> {
+        self.log_call(|| "create_renderbuffer()".to_owned());
         self.inner.create_renderbuffer()
     }
 
     pub fn delete_renderbuffer(&self, renderbuffer: &Renderbuffer) {
+        self.log_call(|| format!("delete_renderbuffer({renderbuffer:?})"));
         self.inner.delete_renderbuffer(Some(renderbuffer));
     }
 
@@ -22,6 +25,11 @@ impl Context {
         width: SizeI,
         height: SizeI,
     ) {
+        self.log_call(|| {
+            format!(
+                "renderbuffer_storage({target:#x}, {internal_format:#x}, {width:?}, {height:?})"
+            )
+        });
         self.inner
             .renderbuffer_storage(target, internal_format, width, height);
     }
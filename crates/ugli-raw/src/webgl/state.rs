@@ -2,6 +2,7 @@ use super::*;
 
 impl Context {
     pub fn blend_func(&self, src_factor: Enum, dst_factor: Enum) {
+        self.log_call(|| format!("blend_func({src_factor:#x}, {dst_factor:#x})"));
         self.inner.blend_func(src_factor, dst_factor);
     }
 
@@ -12,6 +13,7 @@ impl Context {
         src_factor_alpha: Enum,
         dst_factor_alpha: Enum,
     ) {
+        self.log_call(|| format!("blend_func_separate({src_factor_rgb:#x}, {dst_factor_rgb:#x}, {src_factor_alpha:#x}, {dst_factor_alpha:#x})"));
         self.inner.blend_func_separate(
             src_factor_rgb,
             dst_factor_rgb,
@@ -21,6 +23,7 @@ impl Context {
     }
 
     pub fn blend_equation_separate(&self, mode_rgb: Enum, mode_alpha: Enum) {
+        self.log_call(|| format!("blend_equation_separate({mode_rgb:#x}, {mode_alpha:#x})"));
         self.inner.blend_equation_separate(mode_rgb, mode_alpha);
     }
 
@@ -31,50 +34,62 @@ impl Context {
         blue: ClampedFloat,
         alpha: ClampedFloat,
     ) {
+        self.log_call(|| format!("clear_color({red:?}, {green:?}, {blue:?}, {alpha:?})"));
         self.inner.clear_color(red, green, blue, alpha);
     }
 
     pub fn clear_depth(&self, depth: ClampedFloat) {
+        self.log_call(|| format!("clear_depth({depth:?})"));
         self.inner.clear_depth(depth);
     }
 
     pub fn clear_stencil(&self, stencil: Int) {
+        self.log_call(|| format!("clear_stencil({stencil:?})"));
         self.inner.clear_stencil(stencil);
     }
 
     pub fn color_mask(&self, red: Bool, green: Bool, blue: Bool, alpha: Bool) {
+        self.log_call(|| format!("color_mask({red:?}, {green:?}, {blue:?}, {alpha:?})"));
         self.inner.color_mask(red, green, blue, alpha);
     }
 
     pub fn cull_face(&self, mode: Enum) {
+        self.log_call(|| format!("cull_face({mode:#x})"));
         self.inner.cull_face(mode);
     }
 
     pub fn depth_func(&self, func: Enum) {
+        self.log_call(|| format!("depth_func({func:#x})"));
         self.inner.depth_func(func);
     }
 
     pub fn depth_mask(&self, flag: Bool) {
+        self.log_call(|| format!("depth_mask({flag:?})"));
         self.inner.depth_mask(flag);
     }
 
     pub fn disable(&self, cap: Enum) {
+        self.log_call(|| format!("disable({cap:#x})"));
         self.inner.disable(cap);
     }
 
     pub fn enable(&self, cap: Enum) {
+        self.log_call(|| format!("enable({cap:#x})"));
         self.inner.enable(cap);
     }
 
     pub fn get_error(&self) -> Enum {
+        self.log_call(|| "get_error()".to_owned());
         self.inner.get_error()
     }
 
     pub fn line_width(&self, width: Float) {
+        self.log_call(|| format!("line_width({width:?})"));
         self.inner.line_width(width);
     }
 
     pub fn get_version_string(&self) -> String {
+        self.log_call(|| "get_version_string()".to_owned());
         self.inner
             .get_parameter(VERSION)
             .unwrap()
@@ -82,19 +97,49 @@ impl Context {
             .unwrap()
     }
 
+    pub fn get_supported_extensions(&self) -> Vec<String> {
+        self.log_call(|| "get_supported_extensions()".to_owned());
+        self.inner
+            .get_supported_extensions()
+            .map(|extensions| {
+                extensions
+                    .iter()
+                    .filter_map(|extension| extension.as_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// WebGL extensions do nothing until they are requested
+    pub fn enable_extension(&self, name: &str) -> bool {
+        self.log_call(|| format!("enable_extension({name:?})"));
+        matches!(self.inner.get_extension(name), Ok(Some(_)))
+    }
+
     pub fn pixel_store(&self, pname: Enum, param: Int) {
+        self.log_call(|| format!("pixel_store({pname:#x}, {param:?})"));
         self.inner.pixel_storei(pname, param);
     }
 
     pub fn stencil_func_separate(&self, face: Enum, func: Enum, r#ref: Int, mask: UInt) {
+        self.log_call(|| {
+            format!(
+                "stencil_func_separate({face:#x}, {func:#x}, {:?}, {mask:?})",
+                r#ref
+            )
+        });
         self.inner.stencil_func_separate(face, func, r#ref, mask);
     }
 
     pub fn stencil_mask_separate(&self, face: Enum, mask: UInt) {
+        self.log_call(|| format!("stencil_mask_separate({face:#x}, {mask:?})"));
         self.inner.stencil_mask_separate(face, mask);
     }
 
     pub fn stencil_op_separate(&self, face: Enum, fail: Enum, zfail: Enum, pass: Enum) {
+        self.log_call(|| {
+            format!("stencil_op_separate({face:#x}, {fail:#x}, {zfail:#x}, {pass:#x})")
+        });
         self.inner.stencil_op_separate(face, fail, zfail, pass);
     }
 }
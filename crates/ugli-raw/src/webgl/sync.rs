@@ -0,0 +1,26 @@
+use super::*;
+
+pub type Fence = web_sys::WebGlSync;
+
+impl Context {
+    pub fn fence_sync(&self, condition: Enum, flags: BitField) -> Option<Fence> {
+        self.log_call(|| format!("fence_sync({condition:#x}, {flags:#x})"));
+        self.webgl2().fence_sync(condition, flags)
+    }
+
+    pub fn client_wait_sync(&self, fence: &Fence, flags: BitField, timeout: u64) -> Enum {
+        self.log_call(|| format!("client_wait_sync({fence:?}, {flags:#x}, {timeout:?})"));
+        self.webgl2()
+            .client_wait_sync_with_f64(fence, flags, timeout as f64)
+    }
+
+    pub fn delete_sync(&self, fence: &Fence) {
+        self.log_call(|| format!("delete_sync({fence:?})"));
+        self.webgl2().delete_sync(Some(fence));
+    }
+
+    pub fn flush(&self) {
+        self.log_call(|| "flush()".to_owned());
+        self.inner.flush();
+    }
+}
@@ -4,34 +4,41 @@ pub type Texture = web_sys::WebGlTexture;
 
 impl Context {
     pub fn pixel_store_flip_y(&self, flip: bool) {
+        self.log_call(|| format!("pixel_store_flip_y({flip:?})"));
         self.inner.pixel_storei(
             web_sys::WebGlRenderingContext::UNPACK_FLIP_Y_WEBGL,
             if flip { 1 } else { 0 },
         );
     }
     pub fn pixel_store_premultiply_alpha(&self, premultiply: bool) {
+        self.log_call(|| format!("pixel_store_premultiply_alpha({premultiply:?})"));
         self.inner.pixel_storei(
             web_sys::WebGlRenderingContext::UNPACK_PREMULTIPLY_ALPHA_WEBGL,
             if premultiply { 1 } else { 0 },
         );
     }
     pub fn active_texture(&self, texture: Enum) {
+        self.log_call(|| format!("active_texture({texture:#x})"));
         self.inner.active_texture(texture);
     }
 
     pub fn bind_texture(&self, target: Enum, texture: &Texture) {
+        self.log_call(|| format!("bind_texture({target:#x}, {texture:?})"));
         self.inner.bind_texture(target, Some(texture));
     }
 
     pub fn create_texture(&self) -> Option<Texture> {
+        self.log_call(|| "create_texture()".to_owned());
         self.inner.create_texture()
     }
 
     pub fn delete_texture(&self, texture: &Texture) {
+        self.log_call(|| format!("delete_texture({texture:?})"));
         self.inner.delete_texture(Some(texture));
     }
 
     pub fn generate_mipmap(&self, target: Enum) {
+        self.log_call(|| format!("generate_mipmap({target:#x})"));
         self.inner.generate_mipmap(target);
     }
 
@@ -48,6 +55,7 @@ impl Context {
         typ: Enum,
         pixels: Option<&[T]>,
     ) {
+        self.log_call(|| format!("tex_image_2d({target:#x}, {level:?}, {internal_format:?}, {width:?}, {height:?}, {border:?}, {format:#x}, {typ:#x}, <{:?} items>)", pixels.map(|pixels| pixels.len())));
         self.inner
             .tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
                 target,
@@ -68,6 +76,29 @@ impl Context {
             .unwrap();
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn compressed_tex_image_2d(
+        &self,
+        target: Enum,
+        level: Int,
+        internal_format: Enum,
+        width: SizeI,
+        height: SizeI,
+        border: Int,
+        data: &[u8],
+    ) {
+        self.log_call(|| format!("compressed_tex_image_2d({target:#x}, {level:?}, {internal_format:#x}, {width:?}, {height:?}, {border:?}, <{} bytes>)", data.len()));
+        self.inner.compressed_tex_image_2d_with_u8_array(
+            target,
+            level,
+            internal_format,
+            width,
+            height,
+            border,
+            data,
+        );
+    }
+
     pub fn tex_image_2d_image(
         &self,
         target: Enum,
@@ -77,6 +108,7 @@ impl Context {
         typ: Enum,
         source: &web_sys::HtmlImageElement,
     ) {
+        self.log_call(|| format!("tex_image_2d_image({target:#x}, {level:?}, {internal_format:?}, {format:#x}, {typ:#x}, {source:?})"));
         self.inner
             .tex_image_2d_with_u32_and_u32_and_image(
                 target,
@@ -89,7 +121,30 @@ impl Context {
             .unwrap();
     }
 
+    pub fn tex_image_2d_image_bitmap(
+        &self,
+        target: Enum,
+        level: Int,
+        internal_format: Int,
+        format: Enum,
+        typ: Enum,
+        source: &web_sys::ImageBitmap,
+    ) {
+        self.log_call(|| format!("tex_image_2d_image_bitmap({target:#x}, {level:?}, {internal_format:?}, {format:#x}, {typ:#x}, {source:?})"));
+        self.inner
+            .tex_image_2d_with_u32_and_u32_and_image_bitmap(
+                target,
+                level,
+                internal_format,
+                format,
+                typ,
+                source,
+            )
+            .unwrap();
+    }
+
     pub fn tex_parameteri(&self, target: Enum, pname: Enum, param: Int) {
+        self.log_call(|| format!("tex_parameteri({target:#x}, {pname:#x}, {param:?})"));
         self.inner.tex_parameteri(target, pname, param);
     }
 
@@ -106,6 +161,7 @@ impl Context {
         typ: Enum,
         pixels: &[T],
     ) {
+        self.log_call(|| format!("tex_sub_image_2d({target:#x}, {level:?}, {x_offset:?}, {y_offset:?}, {width:?}, {height:?}, {format:#x}, {typ:#x}, <{} items>)", pixels.len()));
         self.inner
             .tex_sub_image_2d_with_i32_and_i32_and_u32_and_type_and_opt_u8_array(
                 target,
@@ -138,6 +194,7 @@ impl Context {
         width: SizeI,
         height: SizeI,
     ) {
+        self.log_call(|| format!("copy_tex_sub_image_2d({target:#x}, {level:?}, {x_offset:?}, {y_offset:?}, {x:?}, {y:?}, {width:?}, {height:?})"));
         self.inner
             .copy_tex_sub_image_2d(target, level, x_offset, y_offset, x, y, width, height);
     }
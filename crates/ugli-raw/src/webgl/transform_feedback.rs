@@ -0,0 +1,35 @@
+use super::*;
+
+impl Context {
+    pub fn transform_feedback_varyings(
+        &self,
+        program: &Program,
+        varyings: &[&str],
+        buffer_mode: Enum,
+    ) {
+        self.log_call(|| {
+            format!("transform_feedback_varyings({program:?}, {varyings:?}, {buffer_mode:#x})")
+        });
+        let varyings: js_sys::Array = varyings
+            .iter()
+            .map(|name| wasm_bindgen::JsValue::from_str(name))
+            .collect();
+        self.webgl2()
+            .transform_feedback_varyings(program, &varyings, buffer_mode);
+    }
+
+    pub fn bind_buffer_base(&self, target: Enum, index: UInt, buffer: Option<&Buffer>) {
+        self.log_call(|| format!("bind_buffer_base({target:#x}, {index:?}, {buffer:?})"));
+        self.webgl2().bind_buffer_base(target, index, buffer);
+    }
+
+    pub fn begin_transform_feedback(&self, primitive_mode: Enum) {
+        self.log_call(|| format!("begin_transform_feedback({primitive_mode:#x})"));
+        self.webgl2().begin_transform_feedback(primitive_mode);
+    }
+
+    pub fn end_transform_feedback(&self) {
+        self.log_call(|| "end_transform_feedback()".to_owned());
+        self.webgl2().end_transform_feedback();
+    }
+}
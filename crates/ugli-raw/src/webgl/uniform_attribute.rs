@@ -21,22 +21,27 @@ impl ActiveInfo {
 
 impl Context {
     pub fn disable_vertex_attrib_array(&self, index: UInt) {
+        self.log_call(|| format!("disable_vertex_attrib_array({index:?})"));
         self.inner.disable_vertex_attrib_array(index);
     }
 
     pub fn enable_vertex_attrib_array(&self, index: UInt) {
+        self.log_call(|| format!("enable_vertex_attrib_array({index:?})"));
         self.inner.enable_vertex_attrib_array(index);
     }
 
     pub fn get_active_attrib(&self, program: &Program, index: UInt) -> ActiveInfo {
+        self.log_call(|| format!("get_active_attrib({program:?}, {index:?})"));
         ActiveInfo::from(self.inner.get_active_attrib(program, index).unwrap())
     }
 
     pub fn get_active_uniform(&self, program: &Program, index: UInt) -> ActiveInfo {
+        self.log_call(|| format!("get_active_uniform({program:?}, {index:?})"));
         ActiveInfo::from(self.inner.get_active_uniform(program, index).unwrap())
     }
 
     pub fn get_attrib_location(&self, program: &Program, name: &str) -> Int {
+        self.log_call(|| format!("get_attrib_location({program:?}, {name:?})"));
         self.inner.get_attrib_location(program, name)
     }
 
@@ -46,6 +51,12 @@ impl Context {
         location: &UniformLocation,
         params: &mut [Int],
     ) {
+        self.log_call(|| {
+            format!(
+                "get_uniform_int({program:?}, {location:?}, <{} items>)",
+                params.len()
+            )
+        });
         self.use_program(program); // This needs to happen but couldn't find docs
         use wasm_bindgen::JsCast;
         let value = self.inner.get_uniform(program, location);
@@ -64,6 +75,12 @@ impl Context {
         location: &UniformLocation,
         params: &mut [Float],
     ) {
+        self.log_call(|| {
+            format!(
+                "get_uniform_float({program:?}, {location:?}, <{} items>)",
+                params.len()
+            )
+        });
         self.use_program(program); // This needs to happen but couldn't find docs
         use wasm_bindgen::JsCast;
         let value = self.inner.get_uniform(program, location);
@@ -77,34 +94,42 @@ impl Context {
     }
 
     pub fn get_uniform_location(&self, program: &Program, name: &str) -> Option<UniformLocation> {
+        self.log_call(|| format!("get_uniform_location({program:?}, {name:?})"));
         self.inner.get_uniform_location(program, name)
     }
 
     pub fn uniform_1i(&self, location: &UniformLocation, v: Int) {
+        self.log_call(|| format!("uniform_1i({location:?}, {v:?})"));
         self.inner.uniform1i(Some(location), v);
     }
 
     pub fn uniform_1f(&self, location: &UniformLocation, v: Float) {
+        self.log_call(|| format!("uniform_1f({location:?}, {v:?})"));
         self.inner.uniform1f(Some(location), v);
     }
 
     pub fn uniform_2i(&self, location: &UniformLocation, v0: Int, v1: Int) {
+        self.log_call(|| format!("uniform_2i({location:?}, {v0:?}, {v1:?})"));
         self.inner.uniform2i(Some(location), v0, v1);
     }
 
     pub fn uniform_2f(&self, location: &UniformLocation, v0: Float, v1: Float) {
+        self.log_call(|| format!("uniform_2f({location:?}, {v0:?}, {v1:?})"));
         self.inner.uniform2f(Some(location), v0, v1);
     }
 
     pub fn uniform_3i(&self, location: &UniformLocation, v0: Int, v1: Int, v2: Int) {
+        self.log_call(|| format!("uniform_3i({location:?}, {v0:?}, {v1:?}, {v2:?})"));
         self.inner.uniform3i(Some(location), v0, v1, v2);
     }
 
     pub fn uniform_3f(&self, location: &UniformLocation, v0: Float, v1: Float, v2: Float) {
+        self.log_call(|| format!("uniform_3f({location:?}, {v0:?}, {v1:?}, {v2:?})"));
         self.inner.uniform3f(Some(location), v0, v1, v2);
     }
 
     pub fn uniform_4i(&self, location: &UniformLocation, v0: Int, v1: Int, v2: Int, v3: Int) {
+        self.log_call(|| format!("uniform_4i({location:?}, {v0:?}, {v1:?}, {v2:?}, {v3:?})"));
         self.inner.uniform4i(Some(location), v0, v1, v2, v3);
     }
 
@@ -116,6 +141,7 @@ impl Context {
         v2: Float,
         v3: Float,
     ) {
+        self.log_call(|| format!("uniform_4f({location:?}, {v0:?}, {v1:?}, {v2:?}, {v3:?})"));
         self.inner.uniform4f(Some(location), v0, v1, v2, v3);
     }
 
@@ -126,6 +152,9 @@ impl Context {
         transpose: Bool,
         v: &[Float],
     ) {
+        self.log_call(|| {
+            format!("uniform_matrix2fv({location:?}, {count:?}, {transpose:?}, {v:?})")
+        });
         debug_assert_eq!(v.len(), count as usize * 2 * 2);
         self.inner
             .uniform_matrix2fv_with_f32_array(Some(location), transpose, v);
@@ -138,6 +167,9 @@ impl Context {
         transpose: Bool,
         v: &[Float],
     ) {
+        self.log_call(|| {
+            format!("uniform_matrix3fv({location:?}, {count:?}, {transpose:?}, {v:?})")
+        });
         debug_assert_eq!(v.len(), count as usize * 3 * 3);
         self.inner
             .uniform_matrix3fv_with_f32_array(Some(location), transpose, v);
@@ -150,14 +182,24 @@ impl Context {
         transpose: Bool,
         v: &[Float],
     ) {
+        self.log_call(|| {
+            format!("uniform_matrix4fv({location:?}, {count:?}, {transpose:?}, {v:?})")
+        });
         debug_assert_eq!(v.len(), count as usize * 4 * 4);
         self.inner
             .uniform_matrix4fv_with_f32_array(Some(location), transpose, v);
     }
 
     pub fn vertex_attrib_divisor(&self, index: UInt, divisor: UInt) {
-        self.angle_instanced_arrays
-            .vertex_attrib_divisor_angle(index, divisor);
+        self.log_call(|| format!("vertex_attrib_divisor({index:?}, {divisor:?})"));
+        match &self.webgl2 {
+            Some(webgl2) => webgl2.vertex_attrib_divisor(index, divisor),
+            None => self
+                .angle_instanced_arrays
+                .as_ref()
+                .unwrap()
+                .vertex_attrib_divisor_angle(index, divisor),
+        }
     }
 
     pub fn vertex_attrib_pointer(
@@ -169,7 +211,25 @@ impl Context {
         stride: SizeI,
         offset: IntPtr,
     ) {
+        self.log_call(|| format!("vertex_attrib_pointer({index:?}, {size:?}, {typ:#x}, {normalized:?}, {stride:?}, {offset:?})"));
         self.inner
             .vertex_attrib_pointer_with_i32(index, size, typ, normalized, stride, offset);
     }
+
+    pub fn vertex_attrib_i_pointer(
+        &self,
+        index: UInt,
+        size: Int,
+        typ: Enum,
+        stride: SizeI,
+        offset: IntPtr,
+    ) {
+        self.log_call(|| {
+            format!(
+                "vertex_attrib_i_pointer({index:?}, {size:?}, {typ:#x}, {stride:?}, {offset:?})"
+            )
+        });
+        self.webgl2()
+            .vertex_attrib_i_pointer_with_i32(index, size, typ, stride, offset);
+    }
 }
@@ -2,6 +2,7 @@ use super::*;
 
 impl Context {
     pub fn viewport(&self, x: Int, y: Int, width: SizeI, height: SizeI) {
+        self.log_call(|| format!("viewport({x:?}, {y:?}, {width:?}, {height:?})"));
         self.inner.viewport(x, y, width, height);
     }
 }
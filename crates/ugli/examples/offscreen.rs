@@ -0,0 +1,85 @@
+//! Render to a texture without a window and save the result as `offscreen.png`.
+//!
+//! Also runs as a test, comparing the result with `examples/snapshots/offscreen.png`
+
+use batbox_color::*;
+use batbox_la::*;
+
+#[derive(ugli::Vertex)]
+struct Vertex {
+    a_pos: vec2<f32>,
+}
+
+const VERTEX_SHADER: &str = "
+attribute vec2 a_pos;
+void main() {
+    gl_Position = vec4(a_pos, 0.0, 1.0);
+}
+";
+
+const FRAGMENT_SHADER: &str = "
+uniform vec4 u_color;
+void main() {
+    gl_FragColor = u_color;
+}
+";
+
+/// Red rectangle in the bottom middle of a black 16x16 image
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn render(ugli: &ugli::Ugli) -> image::RgbaImage {
+    let program = ugli::Program::new(
+        ugli,
+        [
+            &ugli::Shader::new(ugli, ugli::ShaderType::Vertex, VERTEX_SHADER).unwrap(),
+            &ugli::Shader::new(ugli, ugli::ShaderType::Fragment, FRAGMENT_SHADER).unwrap(),
+        ],
+    )
+    .unwrap();
+    let vertices = ugli::VertexBuffer::new_static(
+        ugli,
+        [(-0.5, -0.5), (0.5, -0.5), (-0.5, 0.0), (0.5, 0.0)]
+            .into_iter()
+            .map(|(x, y)| Vertex { a_pos: vec2(x, y) })
+            .collect(),
+    );
+    let mut texture = ugli::Texture::new_uninitialized(ugli, vec2(16, 16));
+    let mut framebuffer =
+        ugli::Framebuffer::new_color(ugli, ugli::ColorAttachment::Texture(&mut texture));
+    ugli::clear(&mut framebuffer, Some(Rgba::BLACK), None, None);
+    ugli::draw(
+        &mut framebuffer,
+        &program,
+        ugli::DrawMode::TriangleStrip,
+        &vertices,
+        ugli::uniforms! {
+            u_color: Rgba::<f32>::RED,
+        },
+        ugli::DrawParameters::default(),
+    );
+    framebuffer.read_color().into_image()
+}
+
+#[cfg(target_os = "linux")]
+fn main() {
+    let ugli = ugli::Ugli::create_headless(ugli::HeadlessOptions::default())
+        .expect("Failed to create headless context");
+    render(&ugli).save("offscreen.png").unwrap();
+}
+
+#[cfg(not(target_os = "linux"))]
+fn main() {
+    eprintln!("Headless contexts are only supported on Linux");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn offscreen() {
+    let Some(ugli) = ugli::testing::create_headless() else {
+        return;
+    };
+    ugli::testing::assert_snapshot(
+        &render(&ugli),
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("examples/snapshots/offscreen.png"),
+        0,
+    );
+}
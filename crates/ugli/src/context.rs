@@ -4,6 +4,12 @@ pub(crate) struct UgliImpl {
     pub(crate) raw: raw::Context,
     // TODO this creates a cycling Rc so we will never GC
     vao: std::cell::RefCell<Option<Vao>>,
+    pub(crate) frame_wakers: std::cell::RefCell<Vec<std::task::Waker>>,
+    pub(crate) strict_validation: std::cell::Cell<bool>,
+    pub(crate) frame_capture: std::cell::RefCell<debug::FrameCapture>,
+    pub(crate) compressed_formats: std::cell::OnceCell<Vec<CompressedFormat>>,
+    /// GL context and display kept alive for contexts created by ugli itself
+    _owned_context: Option<Box<dyn std::any::Any>>,
     phantom_data: PhantomData<*mut ()>,
 }
 
@@ -23,6 +29,9 @@ pub struct WebGLContextOptions {
     pub power_preference: &'static str,
     pub depth: bool,
     pub antialias: bool,
+    /// Try to create a WebGL2 context, falling back to WebGL1
+    #[serde(skip)]
+    pub webgl2: bool,
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -36,6 +45,7 @@ impl Default for WebGLContextOptions {
             power_preference: "high-performance",
             depth: true,
             antialias: false,
+            webgl2: false,
         }
     }
 }
@@ -44,8 +54,16 @@ impl Default for WebGLContextOptions {
 impl Ugli {
     pub fn create_webgl(canvas: &web_sys::HtmlCanvasElement, options: WebGLContextOptions) -> Self {
         let context_options = serde_wasm_bindgen::to_value(&options).unwrap();
+        let webgl2 = match options.webgl2 {
+            true => canvas
+                .get_context_with_context_options("webgl2", &context_options)
+                .unwrap(),
+            false => None,
+        };
         let webgl;
-        if let Some(context) = canvas
+        if let Some(context) = webgl2 {
+            webgl = context;
+        } else if let Some(context) = canvas
             .get_context_with_context_options("webgl", &context_options)
             .unwrap()
         {
@@ -58,28 +76,51 @@ impl Ugli {
         } else {
             panic!("Could not get webgl context");
         }
-        let webgl: web_sys::WebGlRenderingContext = webgl.dyn_into().unwrap();
+        // WebGL2 context shares the WebGL1 api, so unchecked cast is fine here
+        let webgl: web_sys::WebGlRenderingContext = webgl.unchecked_into();
         let ugli = Ugli {
             inner: Rc::new(UgliImpl {
                 raw: raw::Context::new(webgl),
                 vao: Default::default(),
+                frame_wakers: Default::default(),
+                strict_validation: Default::default(),
+                frame_capture: Default::default(),
+                compressed_formats: Default::default(),
+                _owned_context: None,
                 phantom_data: PhantomData,
             }),
         };
         ugli.init();
         ugli
     }
+
+    pub fn is_webgl2(&self) -> bool {
+        self.inner.raw.is_webgl2()
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 impl Ugli {
     pub fn create_from_glutin<F: Fn(&str) -> *const std::os::raw::c_void>(
         get_proc_address: F,
+    ) -> Self {
+        Self::create_native(get_proc_address, None)
+    }
+
+    /// `owned_context` is dropped together with the last [Ugli] clone
+    pub(crate) fn create_native<F: Fn(&str) -> *const std::os::raw::c_void>(
+        get_proc_address: F,
+        owned_context: Option<Box<dyn std::any::Any>>,
     ) -> Self {
         let ugli = Ugli {
             inner: Rc::new(UgliImpl {
                 raw: raw::Context::new(get_proc_address),
                 vao: Default::default(),
+                frame_wakers: Default::default(),
+                strict_validation: Default::default(),
+                frame_capture: Default::default(),
+                compressed_formats: Default::default(),
+                _owned_context: owned_context,
                 phantom_data: PhantomData,
             }),
         };
@@ -102,4 +143,22 @@ impl Ugli {
         gl.pixel_store(raw::UNPACK_ALIGNMENT, 1);
         self.check();
     }
+
+    /// Report mismatches between programs and uniforms/vertex data passed to [draw].
+    /// Errors are logged once per program
+    pub fn set_strict_validation(&self, enabled: bool) {
+        self.inner.strict_validation.set(enabled);
+    }
+    pub fn strict_validation(&self) -> bool {
+        self.inner.strict_validation.get()
+    }
+
+    /// Should be called at the start of every frame.
+    /// Wakes up futures waiting for GPU work, like [FramebufferRead::read_color_async]
+    pub fn begin_frame(&self) {
+        self.update_frame_capture();
+        for waker in self.inner.frame_wakers.take() {
+            waker.wake();
+        }
+    }
 }
@@ -0,0 +1,78 @@
+use super::*;
+
+#[derive(Default)]
+pub(crate) enum FrameCapture {
+    #[default]
+    None,
+    Requested,
+    Capturing,
+    Done(String),
+}
+
+impl Ugli {
+    /// Route driver debug messages (`KHR_debug`) to [log].
+    /// Most drivers only report messages if the context was created with debug flag.
+    ///
+    /// Returns `false` if not supported
+    pub fn enable_debug_output(&self) -> bool {
+        #[cfg(target_arch = "wasm32")]
+        return false;
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let gl = &self.inner.raw;
+            let supported = gl.debug_message_callback(|source, typ, id, severity, message| {
+                let level = match severity {
+                    raw::DEBUG_SEVERITY_HIGH => log::Level::Error,
+                    raw::DEBUG_SEVERITY_MEDIUM => log::Level::Warn,
+                    raw::DEBUG_SEVERITY_LOW => log::Level::Info,
+                    _ => log::Level::Debug,
+                };
+                log::log!(
+                    level,
+                    "GL message {id} (source {source:#x}, type {typ:#x}): {message}",
+                );
+            });
+            if supported {
+                gl.enable(raw::DEBUG_OUTPUT);
+                // So that the callback is called on this thread, at the offending call
+                gl.enable(raw::DEBUG_OUTPUT_SYNCHRONOUS);
+            }
+            supported
+        }
+    }
+
+    /// Record every GL call issued during the next frame (between two [Ugli::begin_frame] calls).
+    /// Result is available with [Ugli::take_frame_capture] after the frame is done
+    pub fn capture_next_frame(&self) {
+        *self.inner.frame_capture.borrow_mut() = FrameCapture::Requested;
+    }
+
+    /// Take the log of the last captured frame, one GL call per line
+    pub fn take_frame_capture(&self) -> Option<String> {
+        let mut capture = self.inner.frame_capture.borrow_mut();
+        match std::mem::take(&mut *capture) {
+            FrameCapture::Done(log) => Some(log),
+            other => {
+                *capture = other;
+                None
+            }
+        }
+    }
+
+    pub(crate) fn update_frame_capture(&self) {
+        let gl = &self.inner.raw;
+        let mut capture = self.inner.frame_capture.borrow_mut();
+        match *capture {
+            FrameCapture::Requested => {
+                gl.start_call_capture();
+                *capture = FrameCapture::Capturing;
+            }
+            FrameCapture::Capturing => {
+                let calls = gl.finish_call_capture();
+                log::info!("Captured {} GL calls", calls.len());
+                *capture = FrameCapture::Done(calls.join("\n"));
+            }
+            FrameCapture::None | FrameCapture::Done(_) => {}
+        }
+    }
+}
@@ -1,9 +1,12 @@
 use super::*;
 
 mod parameters;
+mod validation;
 
 pub use parameters::*;
 
+use validation::Validator;
+
 #[derive(Debug, Copy, Clone)]
 pub enum DrawMode {
     Points,
@@ -58,6 +61,23 @@ fn apply_uniforms<U: Uniforms>(uniforms: U, program: &Program) {
     })
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum DrawError {
+    #[error("Attribute {name:?} of type {ty:?} can not be used as {mode:?}")]
+    InvalidAttributeMode {
+        name: String,
+        ty: AttributeType,
+        mode: AttributeMode,
+    },
+    #[error("Attribute {name:?} of type {ty:?} in {mode:?} mode requires WebGL2")]
+    RequiresWebGL2 {
+        name: String,
+        ty: AttributeType,
+        mode: AttributeMode,
+    },
+}
+
+/// Same as [try_draw], but logs the error instead of returning it
 pub fn draw<V, U, DP>(
     framebuffer: &mut Framebuffer,
     program: &Program,
@@ -69,14 +89,102 @@ pub fn draw<V, U, DP>(
     V: VertexDataSource,
     U: Uniforms,
     DP: std::borrow::Borrow<DrawParameters>,
+{
+    if let Err(error) = try_draw(
+        framebuffer,
+        program,
+        mode,
+        vertices,
+        uniforms,
+        draw_parameters,
+    ) {
+        log::error!("Draw skipped: {error}");
+    }
+}
+
+/// Draw, failing without drawing anything if the vertex attributes
+/// can not be passed to the program on this context
+pub fn try_draw<V, U, DP>(
+    framebuffer: &mut Framebuffer,
+    program: &Program,
+    mode: DrawMode,
+    vertices: V,
+    uniforms: U,
+    draw_parameters: DP,
+) -> Result<(), DrawError>
+where
+    V: VertexDataSource,
+    U: Uniforms,
+    DP: std::borrow::Borrow<DrawParameters>,
+{
+    draw_impl(
+        Some(framebuffer),
+        None,
+        program,
+        mode,
+        vertices,
+        uniforms,
+        draw_parameters.borrow(),
+    )
+}
+
+/// Draw with vertex shader outputs captured into `output`,
+/// program must be created with [Program::new_with_transform_feedback].
+///
+/// Rasterization only happens if `framebuffer` is given.
+/// Only [DrawMode::Points], [DrawMode::Lines] and [DrawMode::Triangles] are supported.
+/// Requires GL 3.0 / WebGL2
+pub fn draw_transform_feedback<T, V, U, DP>(
+    framebuffer: Option<&mut Framebuffer>,
+    output: &mut VertexBuffer<T>,
+    program: &Program,
+    mode: DrawMode,
+    vertices: V,
+    uniforms: U,
+    draw_parameters: DP,
+) where
+    T: Vertex,
+    V: VertexDataSource,
+    U: Uniforms,
+    DP: std::borrow::Borrow<DrawParameters>,
+{
+    let capacity = output.len();
+    if let Err(error) = draw_impl(
+        framebuffer,
+        Some((output.transform_feedback_handle(), capacity)),
+        program,
+        mode,
+        vertices,
+        uniforms,
+        draw_parameters.borrow(),
+    ) {
+        log::error!("Draw skipped: {error}");
+    }
+}
+
+/// `feedback` is the buffer to capture into and its capacity in vertices
+fn draw_impl<V, U>(
+    framebuffer: Option<&mut Framebuffer>,
+    feedback: Option<(&raw::Buffer, usize)>,
+    program: &Program,
+    mode: DrawMode,
+    vertices: V,
+    uniforms: U,
+    draw_parameters: &DrawParameters,
+) -> Result<(), DrawError>
+where
+    V: VertexDataSource,
+    U: Uniforms,
 {
     puffin::profile_function!();
     program.ugli.debug_check();
     let gl = &program.ugli.inner.raw;
 
-    framebuffer.fbo.bind();
-    let draw_parameters: &DrawParameters = draw_parameters.borrow();
-    draw_parameters.apply(gl, framebuffer.size());
+    let framebuffer_size = framebuffer.as_ref().map(|framebuffer| {
+        framebuffer.fbo.bind();
+        framebuffer.size()
+    });
+    draw_parameters.apply(gl, framebuffer_size);
     program.bind();
     unsafe {
         UNIFORM_TEXTURE_COUNT = 0;
@@ -90,11 +198,16 @@ pub fn draw<V, U, DP>(
         }
     }
 
+    let mut validator = Validator::new(program);
+    if let Some(validator) = &mut validator {
+        validator.uniforms(program, &uniforms);
+    }
     apply_uniforms(uniforms, program);
 
     let mut vertex_count = None;
     let mut instance_count = None;
     let mut attribute_locations = Vec::new();
+    let mut error = None;
     {
         puffin::profile_scope!("walk vertex data");
         vertices.walk_data(Vdc {
@@ -102,11 +215,22 @@ pub fn draw<V, U, DP>(
             attribute_locations: &mut attribute_locations,
             vertex_count: &mut vertex_count,
             instance_count: &mut instance_count,
+            validator: validator.as_mut(),
+            error: &mut error,
         });
     }
+    if let Some(validator) = validator {
+        validator.finish(program);
+    }
+    if let Some(error) = error {
+        for location in attribute_locations {
+            gl.disable_vertex_attrib_array(location);
+        }
+        return Err(error);
+    }
     let vertex_count = vertex_count.unwrap();
     if vertex_count == 0 {
-        return;
+        return Ok(());
     }
     let gl_mode = match mode {
         DrawMode::Points => raw::POINTS,
@@ -139,6 +263,23 @@ pub fn draw<V, U, DP>(
         }
     };
 
+    if let Some((buffer, capacity)) = feedback {
+        let primitive_mode = match mode {
+            DrawMode::Points | DrawMode::Lines { .. } | DrawMode::Triangles => gl_mode,
+            _ => panic!("{mode:?} can not be used with transform feedback"),
+        };
+        let captured = vertex_count * instance_count.unwrap_or(1);
+        assert!(
+            captured <= capacity,
+            "Transform feedback output has {capacity} vertices, but {captured} will be captured",
+        );
+        gl.bind_buffer_base(raw::TRANSFORM_FEEDBACK_BUFFER, 0, Some(buffer));
+        gl.begin_transform_feedback(primitive_mode);
+    }
+    if framebuffer.is_none() {
+        gl.enable(raw::RASTERIZER_DISCARD);
+    }
+
     if vertex_count != 0 {
         puffin::profile_scope!("draw call");
         if let Some(instance_count) = instance_count {
@@ -150,6 +291,14 @@ pub fn draw<V, U, DP>(
         }
     }
 
+    if framebuffer.is_none() {
+        gl.disable(raw::RASTERIZER_DISCARD);
+    }
+    if feedback.is_some() {
+        gl.end_transform_feedback();
+        gl.bind_buffer_base(raw::TRANSFORM_FEEDBACK_BUFFER, 0, None);
+    }
+
     {
         puffin::profile_scope!("disable");
         for location in attribute_locations {
@@ -164,6 +313,8 @@ pub fn draw<V, U, DP>(
         attribute_locations: &'a mut Vec<raw::UInt>,
         vertex_count: &'a mut Option<usize>,
         instance_count: &'a mut Option<usize>,
+        validator: Option<&'a mut Validator>,
+        error: &'a mut Option<DrawError>,
     }
     impl<'a> VertexDataVisitor for Vdc<'a> {
         fn visit<'b, D: Vertex + 'b, T: IntoVertexBufferSlice<'b, D>>(
@@ -189,6 +340,8 @@ pub fn draw<V, U, DP>(
                 attribute_locations: self.attribute_locations,
                 divisor,
                 program: self.program,
+                validator: self.validator.as_deref_mut(),
+                error: &mut *self.error,
                 offset: data.range.start * mem::size_of::<D>(),
                 phantom_data: PhantomData,
             });
@@ -197,26 +350,83 @@ pub fn draw<V, U, DP>(
                 offset: usize,
                 divisor: Option<usize>,
                 program: &'a Program,
+                validator: Option<&'a mut Validator>,
+                error: &'a mut Option<DrawError>,
                 phantom_data: PhantomData<D>,
             }
+            impl<'a, D: Vertex> Vac<'a, D> {
+                fn check<A: VertexAttribute>(
+                    &self,
+                    name: &str,
+                    mode: AttributeMode,
+                ) -> Result<(), DrawError> {
+                    let ty = A::Primitive::TYPE;
+                    if mode != AttributeMode::Float && !ty.is_integer() {
+                        return Err(DrawError::InvalidAttributeMode {
+                            name: name.to_owned(),
+                            ty,
+                            mode,
+                        });
+                    }
+                    #[cfg(target_arch = "wasm32")]
+                    if !self.program.ugli.inner.raw.is_webgl2()
+                        && (ty == AttributeType::HalfFloat || mode == AttributeMode::Integer)
+                    {
+                        return Err(DrawError::RequiresWebGL2 {
+                            name: name.to_owned(),
+                            ty,
+                            mode,
+                        });
+                    }
+                    Ok(())
+                }
+            }
             impl<'a, D: Vertex> VertexAttributeVisitor for Vac<'a, D> {
                 fn visit<A: VertexAttribute>(&mut self, name: &str, offset: usize) {
+                    self.visit_with_mode::<A>(name, offset, AttributeMode::Float);
+                }
+                fn visit_with_mode<A: VertexAttribute>(
+                    &mut self,
+                    name: &str,
+                    offset: usize,
+                    mode: AttributeMode,
+                ) {
                     let gl = &self.program.ugli.inner.raw;
+                    if let Some(validator) = &mut self.validator {
+                        validator.attribute::<A>(self.program, name, mode);
+                    }
+                    if self.error.is_some() {
+                        return;
+                    }
                     if let Some(attribute_info) = self.program.attributes.get(name) {
+                        if let Err(error) = self.check::<A>(name, mode) {
+                            *self.error = Some(error);
+                            return;
+                        }
                         let offset = self.offset + offset + A::primitive_offset();
                         for row in 0..A::Primitive::ROWS {
                             let offset = offset + mem::size_of::<A>() * row / A::Primitive::ROWS;
                             let location = attribute_info.location + row as raw::UInt;
                             self.attribute_locations.push(location);
                             gl.enable_vertex_attrib_array(location);
-                            gl.vertex_attrib_pointer(
-                                location,
-                                A::Primitive::SIZE as raw::Int,
-                                A::Primitive::TYPE as raw::Enum,
-                                raw::FALSE,
-                                mem::size_of::<D>() as raw::SizeI,
-                                offset as raw::IntPtr,
-                            );
+                            match mode {
+                                AttributeMode::Integer => gl.vertex_attrib_i_pointer(
+                                    location,
+                                    A::Primitive::SIZE as raw::Int,
+                                    A::Primitive::TYPE as raw::Enum,
+                                    mem::size_of::<D>() as raw::SizeI,
+                                    offset as raw::IntPtr,
+                                ),
+                                AttributeMode::Float | AttributeMode::Normalized => gl
+                                    .vertex_attrib_pointer(
+                                        location,
+                                        A::Primitive::SIZE as raw::Int,
+                                        A::Primitive::TYPE as raw::Enum,
+                                        gl_bool(mode == AttributeMode::Normalized),
+                                        mem::size_of::<D>() as raw::SizeI,
+                                        offset as raw::IntPtr,
+                                    ),
+                            }
                             if let Some(divisor) = self.divisor {
                                 gl.vertex_attrib_divisor(location, divisor as raw::UInt);
                             } else {
@@ -228,4 +438,6 @@ pub fn draw<V, U, DP>(
             }
         }
     }
+
+    Ok(())
 }
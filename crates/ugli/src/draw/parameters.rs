@@ -249,7 +249,8 @@ impl Default for DrawParameters {
 }
 
 impl DrawParameters {
-    pub(crate) fn apply(&self, gl: &raw::Context, framebuffer_size: vec2<usize>) {
+    /// Viewport is left as is without a framebuffer (transform feedback only draws)
+    pub(crate) fn apply(&self, gl: &raw::Context, framebuffer_size: Option<vec2<usize>>) {
         puffin::profile_function!("apply draw params");
         match self.depth_func {
             Some(depth_test) => gl.depth_func(depth_test as _),
@@ -264,15 +265,17 @@ impl DrawParameters {
             }
             None => gl.disable(raw::CULL_FACE),
         }
-        if let Some(rect) = self.viewport {
-            gl.viewport(
-                rect.min.x as _,
-                rect.min.y as _,
-                rect.width() as _,
-                rect.height() as _,
-            );
-        } else {
-            gl.viewport(0, 0, framebuffer_size.x as _, framebuffer_size.y as _);
+        if let Some(framebuffer_size) = framebuffer_size {
+            if let Some(rect) = self.viewport {
+                gl.viewport(
+                    rect.min.x as _,
+                    rect.min.y as _,
+                    rect.width() as _,
+                    rect.height() as _,
+                );
+            } else {
+                gl.viewport(0, 0, framebuffer_size.x as _, framebuffer_size.y as _);
+            }
         }
         gl.color_mask(
             self.write_color as _,
@@ -0,0 +1,100 @@
+use super::*;
+
+/// Collects mismatches between a program and the data passed to [draw],
+/// enabled with [Ugli::set_strict_validation]
+#[derive(Default)]
+pub(crate) struct Validator {
+    attributes: Vec<String>,
+    errors: Vec<String>,
+}
+
+impl Validator {
+    pub fn new(program: &Program) -> Option<Self> {
+        program.ugli.strict_validation().then(Self::default)
+    }
+
+    pub fn uniforms<U: Uniforms>(&mut self, program: &Program, uniforms: &U) {
+        let mut names = Vec::new();
+        let complete = uniforms.uniform_names("", &mut names);
+        for name in &names {
+            if !program.uniforms.contains_key(name) {
+                self.errors.push(format!(
+                    "uniform {name:?} is set but not used by the program"
+                ));
+            }
+        }
+        if !complete {
+            return;
+        }
+        let names: std::collections::HashSet<String> = names.into_iter().collect();
+        for name in program.uniforms.keys() {
+            if !name.starts_with("gl_") && !names.contains(name) {
+                self.errors.push(format!("uniform {name:?} is not set"));
+            }
+        }
+    }
+
+    pub fn attribute<A: VertexAttribute>(
+        &mut self,
+        program: &Program,
+        name: &str,
+        mode: AttributeMode,
+    ) {
+        self.attributes.push(name.to_owned());
+        let Some(info) = program.attributes.get(name) else {
+            return;
+        };
+        let expected = GlslType::from_raw(info.info.typ);
+        let size = A::Primitive::SIZE;
+        let typ = A::Primitive::TYPE;
+        let matches = match A::Primitive::ROWS {
+            1 => {
+                let scalar = match (mode, typ) {
+                    (
+                        AttributeMode::Integer,
+                        AttributeType::I8 | AttributeType::I16 | AttributeType::I32,
+                    ) => GlslType::Int,
+                    (AttributeMode::Integer, _) => GlslType::UInt,
+                    _ => GlslType::Float,
+                };
+                expected.scalar_type() == Some(scalar)
+                    && expected
+                        .components()
+                        .is_some_and(|components| size <= components)
+            }
+            rows => {
+                expected
+                    == match rows {
+                        2 => GlslType::Mat2,
+                        3 => GlslType::Mat3,
+                        _ => GlslType::Mat4,
+                    }
+            }
+        };
+        if !matches {
+            self.errors.push(format!(
+                "attribute {name:?} of type {expected:?} is set from {size} x {typ:?} ({mode:?})",
+            ));
+        }
+    }
+
+    pub fn finish(mut self, program: &Program) {
+        for name in program.attributes.keys() {
+            if !name.starts_with("gl_") && !self.attributes.contains(name) {
+                self.errors.push(format!("attribute {name:?} is not set"));
+            }
+        }
+        thread_local! {
+            static REPORTED: std::cell::RefCell<std::collections::HashSet<(u64, String)>> =
+                Default::default();
+        }
+        REPORTED.with(|reported| {
+            let mut reported = reported.borrow_mut();
+            for error in self.errors {
+                if reported.insert((program.cache_key, error.clone())) {
+                    log::error!("Program {}: {error}", program.name().unwrap_or("<unnamed>"));
+                }
+            }
+        });
+    }
+}
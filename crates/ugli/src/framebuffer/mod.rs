@@ -83,6 +83,17 @@ impl<'a> FramebufferRead<'a> {
         self.size
     }
 
+    /// Name shown in GL debuggers and debug messages
+    pub fn set_name(&self, name: &str) {
+        if let Some(handle) = &self.fbo.handle {
+            self.fbo
+                .ugli
+                .inner
+                .raw
+                .object_label(raw::FRAMEBUFFER, handle, name);
+        }
+    }
+
     pub fn color_attachment(&self) -> &ColorAttachmentRead {
         &self.color
     }
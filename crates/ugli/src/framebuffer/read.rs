@@ -21,6 +21,14 @@ impl<'a> ColorData<'a> {
     pub fn data(&self) -> &[u8] {
         &self.buffer
     }
+    /// Convert to an image with the first row at the top, suitable for saving snapshots
+    #[cfg(feature = "image")]
+    pub fn into_image(self) -> image::RgbaImage {
+        let mut image =
+            image::RgbaImage::from_raw(self.width as u32, self.height as u32, self.buffer).unwrap();
+        image::imageops::flip_vertical_in_place(&mut image);
+        image
+    }
 }
 
 impl<'a> FramebufferRead<'a> {
@@ -61,6 +69,63 @@ impl<'a> FramebufferRead<'a> {
         result
     }
 
+    pub fn read_color_async(&self) -> ColorDataFuture {
+        self.read_color_at_async(Aabb2::ZERO.extend_positive(self.size()))
+    }
+
+    /// Start reading pixels into a pixel buffer object without stalling the pipeline.
+    /// The future resolves once the GPU is done, usually a few frames later.
+    /// Falls back to a blocking read if pixel buffers are not supported (WebGL1).
+    pub fn read_color_at_async(&self, rect: Aabb2<usize>) -> ColorDataFuture {
+        let ugli = &self.fbo.ugli;
+        let gl = &ugli.inner.raw;
+        #[cfg(target_arch = "wasm32")]
+        if !gl.is_webgl2() {
+            let data = self.read_color_at(rect);
+            return ColorDataFuture {
+                ugli: ugli.clone(),
+                state: Some(ColorDataFutureState::Ready(ColorData {
+                    width: data.width,
+                    height: data.height,
+                    buffer: data.buffer,
+                    phantom_data: PhantomData,
+                })),
+            };
+        }
+        self.fbo.bind();
+        let buffer = gl.create_buffer().unwrap();
+        gl.bind_buffer(raw::PIXEL_PACK_BUFFER, Some(&buffer));
+        gl.buffer_data_size(
+            raw::PIXEL_PACK_BUFFER,
+            (rect.width() * rect.height() * 4) as raw::SizeIPtr,
+            raw::STREAM_READ,
+        );
+        gl.read_pixels_to_buffer(
+            rect.min.x as raw::Int,
+            rect.min.y as raw::Int,
+            rect.width() as raw::SizeI,
+            rect.height() as raw::SizeI,
+            raw::RGBA,
+            raw::UNSIGNED_BYTE,
+            0,
+        );
+        gl.bind_buffer(raw::PIXEL_PACK_BUFFER, None);
+        let fence = gl
+            .fence_sync(raw::SYNC_GPU_COMMANDS_COMPLETE, 0)
+            .expect("Failed to create fence");
+        gl.flush();
+        ugli.debug_check();
+        ColorDataFuture {
+            ugli: ugli.clone(),
+            state: Some(ColorDataFutureState::Pending {
+                buffer,
+                fence,
+                width: rect.width(),
+                height: rect.height(),
+            }),
+        }
+    }
+
     pub fn copy_to_texture(
         &self,
         texture: &mut Texture,
@@ -83,3 +148,102 @@ impl<'a> FramebufferRead<'a> {
         self.fbo.ugli.debug_check();
     }
 }
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReadColorError {
+    #[error("Waiting for the GPU to finish reading pixels failed")]
+    WaitFailed,
+    #[error("Failed to map the pixel buffer")]
+    MapFailed,
+}
+
+enum ColorDataFutureState {
+    Pending {
+        buffer: raw::Buffer,
+        fence: raw::Fence,
+        width: usize,
+        height: usize,
+    },
+    #[cfg(target_arch = "wasm32")]
+    Ready(ColorData<'static>),
+}
+
+/// Result of [FramebufferRead::read_color_async]
+pub struct ColorDataFuture {
+    ugli: Ugli,
+    state: Option<ColorDataFutureState>,
+}
+
+impl std::future::Future for ColorDataFuture {
+    type Output = Result<ColorData<'static>, ReadColorError>;
+    fn poll(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        let this = self.get_mut();
+        let gl = &this.ugli.inner.raw;
+        let state = this
+            .state
+            .take()
+            .expect("ColorDataFuture polled after completion");
+        match state {
+            #[cfg(target_arch = "wasm32")]
+            ColorDataFutureState::Ready(data) => std::task::Poll::Ready(Ok(data)),
+            ColorDataFutureState::Pending {
+                buffer,
+                fence,
+                width,
+                height,
+            } => {
+                match gl.client_wait_sync(&fence, 0, 0) {
+                    raw::ALREADY_SIGNALED | raw::CONDITION_SATISFIED => {}
+                    raw::TIMEOUT_EXPIRED => {
+                        this.ugli
+                            .inner
+                            .frame_wakers
+                            .borrow_mut()
+                            .push(cx.waker().clone());
+                        this.state = Some(ColorDataFutureState::Pending {
+                            buffer,
+                            fence,
+                            width,
+                            height,
+                        });
+                        return std::task::Poll::Pending;
+                    }
+                    _ => {
+                        gl.delete_sync(&fence);
+                        gl.delete_buffer(&buffer);
+                        return std::task::Poll::Ready(Err(ReadColorError::WaitFailed));
+                    }
+                }
+                let mut data = vec![0; width * height * 4];
+                gl.bind_buffer(raw::PIXEL_PACK_BUFFER, Some(&buffer));
+                let mapped = gl.get_buffer_sub_data(raw::PIXEL_PACK_BUFFER, 0, &mut data);
+                gl.bind_buffer(raw::PIXEL_PACK_BUFFER, None);
+                gl.delete_sync(&fence);
+                gl.delete_buffer(&buffer);
+                this.ugli.debug_check();
+                if !mapped {
+                    return std::task::Poll::Ready(Err(ReadColorError::MapFailed));
+                }
+                std::task::Poll::Ready(Ok(ColorData {
+                    width,
+                    height,
+                    buffer: data,
+                    phantom_data: PhantomData,
+                }))
+            }
+        }
+    }
+}
+
+impl Drop for ColorDataFuture {
+    fn drop(&mut self) {
+        if let Some(ColorDataFutureState::Pending { buffer, fence, .. }) = &self.state {
+            let gl = &self.ugli.inner.raw;
+            gl.delete_sync(fence);
+            gl.delete_buffer(buffer);
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use super::*;
+
+use glutin::api::egl;
+use glutin::config::{ConfigSurfaceTypes, ConfigTemplateBuilder};
+use glutin::context::{ContextAttributesBuilder, NotCurrentGlContext};
+use glutin::display::GlDisplay;
+
+#[derive(Debug, thiserror::Error)]
+pub enum HeadlessContextError {
+    #[error("No EGL devices found")]
+    NoDevice,
+    #[error("No suitable EGL config found")]
+    NoConfig,
+    #[error(transparent)]
+    Glutin(#[from] glutin::error::Error),
+}
+
+/// Options for [Ugli::create_headless]
+#[derive(Debug, Clone)]
+pub struct HeadlessOptions {
+    /// Prefer a software rasterizer (Mesa llvmpipe) over hardware devices,
+    /// so that results do not depend on the GPU
+    pub software: bool,
+}
+
+impl Default for HeadlessOptions {
+    fn default() -> Self {
+        Self { software: true }
+    }
+}
+
+impl Ugli {
+    /// Create a context not attached to any window using EGL,
+    /// rendering should go to textures through [Framebuffer]s.
+    ///
+    /// Mainly useful for pixel tests, works in CI with Mesa installed
+    /// (`LIBGL_ALWAYS_SOFTWARE` is not required)
+    pub fn create_headless(options: HeadlessOptions) -> Result<Self, HeadlessContextError> {
+        let mut devices: Vec<egl::device::Device> = egl::device::Device::query_devices()?.collect();
+        let software =
+            |device: &egl::device::Device| device.extensions().contains("EGL_MESA_device_software");
+        devices.sort_by_key(|device| software(device) != options.software);
+        let device = devices
+            .into_iter()
+            .next()
+            .ok_or(HeadlessContextError::NoDevice)?;
+        if let Some(name) = device.name() {
+            log::info!("Using EGL device {name:?}");
+        }
+        let display = unsafe { egl::display::Display::with_device(&device, None)? };
+        let template = ConfigTemplateBuilder::new()
+            .with_alpha_size(8)
+            .with_surface_type(ConfigSurfaceTypes::empty())
+            .build();
+        let config = unsafe { display.find_configs(template)? }
+            .next()
+            .ok_or(HeadlessContextError::NoConfig)?;
+        let context_attributes = ContextAttributesBuilder::new().build(None);
+        let context = unsafe { display.create_context(&config, &context_attributes)? }
+            .make_current_surfaceless()?;
+        let ugli = Self::create_native(
+            |symbol| {
+                let symbol = std::ffi::CString::new(symbol).unwrap();
+                display.get_proc_address(&symbol)
+            },
+            Some(Box::new((context, display.clone()))),
+        );
+        Ok(ugli)
+    }
+}
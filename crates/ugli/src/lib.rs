@@ -16,15 +16,21 @@ use std::rc::Rc;
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
 
+pub use half::f16;
 pub use ugli_derive::*;
 
 mod context;
+mod debug;
 mod draw;
 mod error;
 mod framebuffer;
+#[cfg(target_os = "linux")]
+mod headless;
 mod program;
 mod renderbuffer;
 mod shader;
+#[cfg(all(feature = "testing", target_os = "linux"))]
+pub mod testing;
 mod texture;
 mod uniform;
 mod vertex;
@@ -33,6 +39,8 @@ pub use context::*;
 pub use draw::*;
 pub use error::*;
 pub use framebuffer::*;
+#[cfg(target_os = "linux")]
+pub use headless::*;
 pub use program::*;
 pub use renderbuffer::*;
 pub use shader::*;
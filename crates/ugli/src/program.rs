@@ -6,16 +6,116 @@ pub struct Program {
     pub(crate) handle: raw::Program,
     pub(crate) attributes: HashMap<String, AttributeInfo>,
     pub(crate) uniforms: HashMap<String, UniformInfo>,
+    pub(crate) name: Option<String>,
+    active_attributes: Vec<ActiveVariable>,
+    active_uniforms: Vec<ActiveVariable>,
     phantom_data: PhantomData<*mut ()>,
 }
 
 #[derive(Debug)]
 pub struct AttributeInfo {
     pub(crate) location: raw::UInt,
-    #[allow(dead_code)]
     pub(crate) info: raw::ActiveInfo,
 }
 
+/// Type of an active program variable as declared in GLSL
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum GlslType {
+    Float,
+    Vec2,
+    Vec3,
+    Vec4,
+    Int,
+    IVec2,
+    IVec3,
+    IVec4,
+    UInt,
+    UVec2,
+    UVec3,
+    UVec4,
+    Bool,
+    BVec2,
+    BVec3,
+    BVec4,
+    Mat2,
+    Mat3,
+    Mat4,
+    Sampler2d,
+    SamplerCube,
+    Other(raw::Enum),
+}
+
+impl GlslType {
+    pub fn from_raw(typ: raw::Enum) -> Self {
+        match typ {
+            raw::FLOAT => Self::Float,
+            raw::FLOAT_VEC2 => Self::Vec2,
+            raw::FLOAT_VEC3 => Self::Vec3,
+            raw::FLOAT_VEC4 => Self::Vec4,
+            raw::INT => Self::Int,
+            raw::INT_VEC2 => Self::IVec2,
+            raw::INT_VEC3 => Self::IVec3,
+            raw::INT_VEC4 => Self::IVec4,
+            raw::UNSIGNED_INT => Self::UInt,
+            raw::UNSIGNED_INT_VEC2 => Self::UVec2,
+            raw::UNSIGNED_INT_VEC3 => Self::UVec3,
+            raw::UNSIGNED_INT_VEC4 => Self::UVec4,
+            raw::BOOL => Self::Bool,
+            raw::BOOL_VEC2 => Self::BVec2,
+            raw::BOOL_VEC3 => Self::BVec3,
+            raw::BOOL_VEC4 => Self::BVec4,
+            raw::FLOAT_MAT2 => Self::Mat2,
+            raw::FLOAT_MAT3 => Self::Mat3,
+            raw::FLOAT_MAT4 => Self::Mat4,
+            raw::SAMPLER_2D => Self::Sampler2d,
+            raw::SAMPLER_CUBE => Self::SamplerCube,
+            _ => Self::Other(typ),
+        }
+    }
+
+    /// Component type for scalar and vector types
+    pub fn scalar_type(&self) -> Option<Self> {
+        Some(match self {
+            Self::Float | Self::Vec2 | Self::Vec3 | Self::Vec4 => Self::Float,
+            Self::Int | Self::IVec2 | Self::IVec3 | Self::IVec4 => Self::Int,
+            Self::UInt | Self::UVec2 | Self::UVec3 | Self::UVec4 => Self::UInt,
+            Self::Bool | Self::BVec2 | Self::BVec3 | Self::BVec4 => Self::Bool,
+            _ => return None,
+        })
+    }
+
+    /// Number of components for scalar and vector types
+    pub fn components(&self) -> Option<usize> {
+        Some(match self {
+            Self::Float | Self::Int | Self::UInt | Self::Bool => 1,
+            Self::Vec2 | Self::IVec2 | Self::UVec2 | Self::BVec2 => 2,
+            Self::Vec3 | Self::IVec3 | Self::UVec3 | Self::BVec3 => 3,
+            Self::Vec4 | Self::IVec4 | Self::UVec4 | Self::BVec4 => 4,
+            _ => return None,
+        })
+    }
+}
+
+/// Active attribute or uniform of a linked program
+#[derive(Debug, Clone)]
+pub struct ActiveVariable {
+    /// Name as reported by the driver, arrays are named like `u_values[0]`
+    pub name: String,
+    /// Array length, 1 for non-array variables
+    pub size: usize,
+    pub typ: GlslType,
+}
+
+impl From<&raw::ActiveInfo> for ActiveVariable {
+    fn from(info: &raw::ActiveInfo) -> Self {
+        Self {
+            name: info.name.clone(),
+            size: info.size as usize,
+            typ: GlslType::from_raw(info.typ),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct UniformInfo {
     pub(crate) location: raw::UniformLocation,
@@ -40,6 +140,18 @@ impl Program {
     pub fn new<'a>(
         ugli: &Ugli,
         shaders: impl IntoIterator<Item = &'a Shader>,
+    ) -> Result<Self, ProgramLinkError> {
+        Self::new_with_transform_feedback(ugli, shaders, &[])
+    }
+
+    /// Create a program capturing given vertex shader outputs, see [draw_transform_feedback].
+    ///
+    /// Varyings are written interleaved in the given order,
+    /// so they should match the fields of the output [Vertex] type
+    pub fn new_with_transform_feedback<'a>(
+        ugli: &Ugli,
+        shaders: impl IntoIterator<Item = &'a Shader>,
+        varyings: &[&str],
     ) -> Result<Self, ProgramLinkError> {
         let shaders: Vec<&Shader> = shaders.into_iter().collect();
         let gl = &ugli.inner.raw;
@@ -53,11 +165,17 @@ impl Program {
             handle: gl.create_program().expect("Failed to create program"),
             uniforms: HashMap::new(),
             attributes: HashMap::new(),
+            name: None,
+            active_attributes: Vec::new(),
+            active_uniforms: Vec::new(),
             phantom_data: PhantomData,
         };
         for shader in &shaders {
             gl.attach_shader(&program.handle, &shader.handle);
         }
+        if !varyings.is_empty() {
+            gl.transform_feedback_varyings(&program.handle, varyings, raw::INTERLEAVED_ATTRIBS);
+        }
         gl.link_program(&program.handle);
         for shader in &shaders {
             gl.detach_shader(&program.handle, &shader.handle);
@@ -76,6 +194,7 @@ impl Program {
             gl.get_program_parameter_int(&program.handle, raw::ACTIVE_ATTRIBUTES) as usize;
         for index in 0..attribute_count {
             let info = gl.get_active_attrib(&program.handle, index as raw::UInt);
+            program.active_attributes.push(ActiveVariable::from(&info));
             let name = info.name.clone();
             let location = gl.get_attrib_location(&program.handle, &name);
             // TODO: why can't this be an assert?
@@ -95,6 +214,7 @@ impl Program {
             gl.get_program_parameter_int(&program.handle, raw::ACTIVE_UNIFORMS) as usize;
         for index in 0..uniform_count {
             let info = gl.get_active_uniform(&program.handle, index as raw::UInt);
+            program.active_uniforms.push(ActiveVariable::from(&info));
             for index in 0..info.size {
                 let name = match info.size {
                     1 => info.name.clone(),
@@ -119,9 +239,40 @@ impl Program {
         ugli.debug_check();
         Ok(program)
     }
+    /// Name used in diagnostics
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+    /// Set name used in diagnostics, also shown in GL debuggers
+    pub fn set_name(&mut self, name: impl Into<String>) {
+        let name = name.into();
+        self.ugli
+            .inner
+            .raw
+            .object_label(raw::PROGRAM, &self.handle, &name);
+        self.name = Some(name);
+    }
+    pub fn active_attributes(&self) -> &[ActiveVariable] {
+        &self.active_attributes
+    }
+    pub fn active_uniforms(&self) -> &[ActiveVariable] {
+        &self.active_uniforms
+    }
     pub fn uniform_info(&self, name: &str) -> Option<UniformInfo> {
         self.uniforms.get(name).cloned()
     }
+    /// Number of active elements in uniform array `name`, including arrays of structs
+    pub fn uniform_array_len(&self, name: &str) -> usize {
+        self.uniforms
+            .keys()
+            .filter_map(|uniform| {
+                let index = uniform.strip_prefix(name)?.strip_prefix('[')?;
+                index[..index.find(']')?].parse::<usize>().ok()
+            })
+            .map(|index| index + 1)
+            .max()
+            .unwrap_or(0)
+    }
     pub(crate) fn bind(&self) {
         self.ugli.inner.raw.use_program(&self.handle);
     }
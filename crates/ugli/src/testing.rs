@@ -0,0 +1,82 @@
+//! Helpers for pixel tests, enabled by the `testing` feature
+
+use super::*;
+
+use std::path::Path;
+
+/// Set to skip pixel tests on machines without EGL, see [create_headless]
+pub const SKIP_HEADLESS_VAR: &str = "GENG_SKIP_HEADLESS";
+
+/// Set to write snapshots instead of comparing against them, see [assert_snapshot]
+pub const UPDATE_SNAPSHOTS_VAR: &str = "UPDATE_SNAPSHOTS";
+
+/// Headless context for a pixel test, `None` if the test should be skipped.
+///
+/// Panics if no context can be created, unless [SKIP_HEADLESS_VAR] is set
+#[track_caller]
+pub fn create_headless() -> Option<Ugli> {
+    match Ugli::create_headless(HeadlessOptions::default()) {
+        Ok(ugli) => Some(ugli),
+        Err(e) if std::env::var_os(SKIP_HEADLESS_VAR).is_some() => {
+            eprintln!("Skipping, headless context not available: {e}");
+            None
+        }
+        Err(e) => panic!(
+            "Headless context not available: {e}, set {SKIP_HEADLESS_VAR}=1 to skip pixel tests"
+        ),
+    }
+}
+
+/// Compare a rendered image (see [ColorData::into_image]) with the PNG snapshot at `path`.
+///
+/// Panics if the sizes differ or any channel of a pixel differs by more than `tolerance`,
+/// saving the rendered image next to the snapshot as `*.actual.png`.
+/// With [UPDATE_SNAPSHOTS_VAR] set the snapshot is written instead
+#[track_caller]
+pub fn assert_snapshot(image: &image::RgbaImage, path: impl AsRef<Path>, tolerance: u8) {
+    let path = path.as_ref();
+    if std::env::var_os(UPDATE_SNAPSHOTS_VAR).is_some() {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        image
+            .save(path)
+            .unwrap_or_else(|e| panic!("Failed to write snapshot {path:?}: {e}"));
+        return;
+    }
+    let expected = match image::open(path) {
+        Ok(expected) => expected.into_rgba8(),
+        Err(e) => panic!(
+            "Failed to read snapshot {path:?}: {e}, run with {UPDATE_SNAPSHOTS_VAR}=1 to create it"
+        ),
+    };
+    let mismatch = if expected.dimensions() != image.dimensions() {
+        Some(format!(
+            "size is {:?}, expected {:?}",
+            image.dimensions(),
+            expected.dimensions(),
+        ))
+    } else {
+        let different = image
+            .pixels()
+            .zip(expected.pixels())
+            .filter(|(actual, expected)| {
+                actual
+                    .0
+                    .iter()
+                    .zip(expected.0)
+                    .any(|(&actual, expected)| actual.abs_diff(expected) > tolerance)
+            })
+            .count();
+        (different != 0).then(|| format!("{different} pixels differ by more than {tolerance}"))
+    };
+    if let Some(mismatch) = mismatch {
+        let actual_path = path.with_extension("actual.png");
+        if let Err(e) = image.save(&actual_path) {
+            log::error!("Failed to save {actual_path:?}: {e}");
+        }
+        panic!(
+            "Snapshot {path:?} does not match: {mismatch}, rendered image saved to {actual_path:?}"
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use super::*;
+
+/// Block compressed texture formats, all of them use 4x4 pixel blocks
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CompressedFormat {
+    /// BC1 (DXT1) without alpha
+    Bc1,
+    /// BC1 (DXT1) with 1-bit alpha
+    Bc1Alpha,
+    /// BC2 (DXT3)
+    Bc2,
+    /// BC3 (DXT5)
+    Bc3,
+    /// BC4, single channel
+    Bc4,
+    /// BC5, two channels
+    Bc5,
+    /// BC7 (BPTC)
+    Bc7,
+    Etc1,
+    Etc2,
+    Etc2Alpha,
+    Astc4x4,
+}
+
+impl CompressedFormat {
+    pub const ALL: [Self; 11] = [
+        Self::Bc1,
+        Self::Bc1Alpha,
+        Self::Bc2,
+        Self::Bc3,
+        Self::Bc4,
+        Self::Bc5,
+        Self::Bc7,
+        Self::Etc1,
+        Self::Etc2,
+        Self::Etc2Alpha,
+        Self::Astc4x4,
+    ];
+
+    pub fn block_size(&self) -> vec2<usize> {
+        vec2(4, 4)
+    }
+
+    pub fn block_bytes(&self) -> usize {
+        match self {
+            Self::Bc1 | Self::Bc1Alpha | Self::Bc4 | Self::Etc1 | Self::Etc2 => 8,
+            Self::Bc2 | Self::Bc3 | Self::Bc5 | Self::Bc7 | Self::Etc2Alpha | Self::Astc4x4 => 16,
+        }
+    }
+
+    /// Size in bytes of an image of given size, `None` if it does not fit in `usize`
+    pub fn data_size(&self, size: vec2<usize>) -> Option<usize> {
+        let block = self.block_size();
+        size.x
+            .div_ceil(block.x)
+            .checked_mul(size.y.div_ceil(block.y))?
+            .checked_mul(self.block_bytes())
+    }
+
+    pub fn has_alpha(&self) -> bool {
+        !matches!(
+            self,
+            Self::Bc1 | Self::Bc4 | Self::Bc5 | Self::Etc1 | Self::Etc2
+        )
+    }
+
+    fn raw(&self) -> raw::Enum {
+        match self {
+            Self::Bc1 => raw::COMPRESSED_RGB_S3TC_DXT1,
+            Self::Bc1Alpha => raw::COMPRESSED_RGBA_S3TC_DXT1,
+            Self::Bc2 => raw::COMPRESSED_RGBA_S3TC_DXT3,
+            Self::Bc3 => raw::COMPRESSED_RGBA_S3TC_DXT5,
+            Self::Bc4 => raw::COMPRESSED_RED_RGTC1,
+            Self::Bc5 => raw::COMPRESSED_RG_RGTC2,
+            Self::Bc7 => raw::COMPRESSED_RGBA_BPTC_UNORM,
+            Self::Etc1 => raw::COMPRESSED_RGB_ETC1,
+            Self::Etc2 => raw::COMPRESSED_RGB8_ETC2,
+            Self::Etc2Alpha => raw::COMPRESSED_RGBA8_ETC2_EAC,
+            Self::Astc4x4 => raw::COMPRESSED_RGBA_ASTC_4X4,
+        }
+    }
+
+    /// Any of these extensions provides the format
+    fn extensions(&self) -> &'static [&'static str] {
+        #[cfg(not(target_arch = "wasm32"))]
+        match self {
+            Self::Bc1 | Self::Bc1Alpha | Self::Bc2 | Self::Bc3 => {
+                &["GL_EXT_texture_compression_s3tc"]
+            }
+            Self::Bc4 | Self::Bc5 => &["GL_ARB_texture_compression_rgtc"],
+            Self::Bc7 => &["GL_ARB_texture_compression_bptc"],
+            Self::Etc1 => &["GL_OES_compressed_ETC1_RGB8_texture"],
+            Self::Etc2 | Self::Etc2Alpha => &["GL_ARB_ES3_compatibility"],
+            Self::Astc4x4 => &["GL_KHR_texture_compression_astc_ldr"],
+        }
+        #[cfg(target_arch = "wasm32")]
+        match self {
+            Self::Bc1 | Self::Bc1Alpha | Self::Bc2 | Self::Bc3 => &[
+                "WEBGL_compressed_texture_s3tc",
+                "WEBKIT_WEBGL_compressed_texture_s3tc",
+            ],
+            Self::Bc4 | Self::Bc5 => &["EXT_texture_compression_rgtc"],
+            Self::Bc7 => &["EXT_texture_compression_bptc"],
+            Self::Etc1 => &["WEBGL_compressed_texture_etc1"],
+            Self::Etc2 | Self::Etc2Alpha => &["WEBGL_compressed_texture_etc"],
+            Self::Astc4x4 => &["WEBGL_compressed_texture_astc"],
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CompressedTextureError {
+    #[error("{0:?} textures are not supported by this device")]
+    Unsupported(CompressedFormat),
+    #[error("Mip level {level} has {actual} bytes, expected {expected}")]
+    InvalidSize {
+        level: usize,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("No mip levels given")]
+    NoLevels,
+    #[error("Texture size must not be zero")]
+    ZeroSize,
+    #[error("Texture of size {0}x{1} is too large")]
+    TooLarge(usize, usize),
+    #[error("{count} mip levels given, a {size:?} texture has at most {max}")]
+    TooManyLevels {
+        size: vec2<usize>,
+        count: usize,
+        max: usize,
+    },
+}
+
+impl Ugli {
+    /// Compressed formats that can be used with [Texture::from_compressed].
+    ///
+    /// Detected once from the available extensions, enabling them on the web
+    pub fn compressed_formats(&self) -> &[CompressedFormat] {
+        self.inner.compressed_formats.get_or_init(|| {
+            let gl = &self.inner.raw;
+            let available = gl.get_supported_extensions();
+            // ETC2 is core in OpenGL ES 3.0, but WebGL2 still needs the extension
+            #[cfg(not(target_arch = "wasm32"))]
+            let etc2_core = gl.get_version_string().starts_with("OpenGL ES 3");
+            #[cfg(target_arch = "wasm32")]
+            let etc2_core = false;
+            let formats: Vec<CompressedFormat> = CompressedFormat::ALL
+                .into_iter()
+                .filter(|format| {
+                    let etc2 =
+                        matches!(format, CompressedFormat::Etc2 | CompressedFormat::Etc2Alpha);
+                    if etc2 && etc2_core {
+                        return true;
+                    }
+                    format.extensions().iter().any(|&extension| {
+                        available.iter().any(|name| name == extension)
+                            && gl.enable_extension(extension)
+                    })
+                })
+                .collect();
+            log::info!("Compressed texture formats: {formats:?}");
+            formats
+        })
+    }
+
+    pub fn supports_compressed_format(&self, format: CompressedFormat) -> bool {
+        self.compressed_formats().contains(&format)
+    }
+}
+
+impl Texture {
+    /// Upload block compressed data, `levels` are the mip levels starting from the full size.
+    ///
+    /// Unlike [Texture::from_image_image] the data is not flipped,
+    /// so the first row of blocks ends up at the bottom of the texture
+    pub fn from_compressed(
+        ugli: &Ugli,
+        format: CompressedFormat,
+        size: vec2<usize>,
+        levels: &[impl AsRef<[u8]>],
+    ) -> Result<Self, CompressedTextureError> {
+        if !ugli.supports_compressed_format(format) {
+            return Err(CompressedTextureError::Unsupported(format));
+        }
+        if levels.is_empty() {
+            return Err(CompressedTextureError::NoLevels);
+        }
+        if size.x == 0 || size.y == 0 {
+            return Err(CompressedTextureError::ZeroSize);
+        }
+        let full_chain = (size.x.max(size.y).ilog2() + 1) as usize;
+        if levels.len() > full_chain {
+            return Err(CompressedTextureError::TooManyLevels {
+                size,
+                count: levels.len(),
+                max: full_chain,
+            });
+        }
+        let mut level_size = size;
+        for (level, data) in levels.iter().enumerate() {
+            let expected = format
+                .data_size(level_size)
+                .ok_or(CompressedTextureError::TooLarge(size.x, size.y))?;
+            let actual = data.as_ref().len();
+            if actual != expected {
+                return Err(CompressedTextureError::InvalidSize {
+                    level,
+                    expected,
+                    actual,
+                });
+            }
+            level_size = level_size.map(|x| (x / 2).max(1));
+        }
+        let mut texture = Texture2d::new_raw(ugli, size);
+        let data_size = levels.iter().map(|data| data.as_ref().len()).sum();
+        texture.compressed = Some((format, data_size));
+        let gl = &ugli.inner.raw;
+        let mut level_size = size;
+        for (level, data) in levels.iter().enumerate() {
+            gl.compressed_tex_image_2d(
+                raw::TEXTURE_2D,
+                level as raw::Int,
+                format.raw(),
+                level_size.x as raw::SizeI,
+                level_size.y as raw::SizeI,
+                0,
+                data.as_ref(),
+            );
+            level_size = level_size.map(|x| (x / 2).max(1));
+        }
+        // Partial mip chains make the texture incomplete
+        if levels.len() == full_chain && full_chain > 1 {
+            gl.tex_parameteri(
+                raw::TEXTURE_2D,
+                raw::TEXTURE_MIN_FILTER,
+                raw::LINEAR_MIPMAP_LINEAR as raw::Int,
+            );
+        }
+        ugli.debug_check();
+        Ok(texture)
+    }
+}
@@ -1,5 +1,9 @@
 use super::*;
 
+mod compressed;
+
+pub use compressed::*;
+
 /// # Safety
 /// Don't implement yourself
 pub unsafe trait TexturePixel: 'static {
@@ -39,6 +43,8 @@ pub struct Texture2d<P: TexturePixel> {
     pub(crate) ugli: Ugli,
     pub(crate) handle: raw::Texture,
     size: Cell<vec2<usize>>,
+    /// Format and total size in bytes of all mip levels, for block compressed textures
+    compressed: Option<(CompressedFormat, usize)>,
     phantom_data: PhantomData<*mut P>,
 }
 
@@ -65,6 +71,7 @@ impl<P: TexturePixel> Texture2d<P> {
             ugli: ugli.clone(),
             handle,
             size: Cell::new(size),
+            compressed: None,
             phantom_data: PhantomData,
         };
         texture.set_filter(Filter::Linear);
@@ -134,6 +141,30 @@ impl<P: TexturePixel> Texture2d<P> {
         self.size.get()
     }
 
+    /// Format of textures created with [Texture::from_compressed]
+    pub fn compressed_format(&self) -> Option<CompressedFormat> {
+        self.compressed.map(|(format, _)| format)
+    }
+
+    /// Size of the texture data in bytes, not counting generated mipmaps
+    pub fn data_size(&self) -> usize {
+        match self.compressed {
+            Some((_, data_size)) => data_size,
+            None => {
+                let size = self.size.get();
+                size.x * size.y * 4
+            }
+        }
+    }
+
+    /// Name shown in GL debuggers and debug messages
+    pub fn set_name(&self, name: &str) {
+        self.ugli
+            .inner
+            .raw
+            .object_label(raw::TEXTURE, &self.handle, name);
+    }
+
     // TODO: use like Matrix<Color>?
     pub fn sub_image(&mut self, pos: vec2<usize>, size: vec2<usize>, data: &[u8]) {
         assert_eq!(
@@ -211,6 +242,7 @@ impl Texture {
         texture
     }
 
+    #[cfg(feature = "image")]
     pub fn from_image_image(ugli: &Ugli, mut image: image::RgbaImage) -> Self {
         let size = vec2(image.width() as usize, image.height() as usize);
         let mut texture = Texture2d::new_raw(ugli, size);
@@ -260,4 +292,26 @@ impl Texture {
         ugli.debug_check();
         texture
     }
+
+    /// Flipping and premultiplying alpha is ignored for bitmaps,
+    /// pass `imageOrientation: "flipY"` to `createImageBitmap` instead
+    #[cfg(target_arch = "wasm32")]
+    pub fn from_image_bitmap(ugli: &Ugli, image: &web_sys::ImageBitmap) -> Self {
+        let mut texture =
+            Texture2d::new_raw(ugli, vec2(image.width() as usize, image.height() as usize));
+        let gl = &ugli.inner.raw;
+        gl.tex_image_2d_image_bitmap(
+            raw::TEXTURE_2D,
+            0,
+            raw::RGBA as raw::Int,
+            raw::RGBA,
+            raw::UNSIGNED_BYTE,
+            image,
+        );
+        if texture.is_pot() {
+            texture.gen_mipmaps();
+        }
+        ugli.debug_check();
+        texture
+    }
 }
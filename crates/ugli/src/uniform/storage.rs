@@ -4,7 +4,28 @@ pub trait Uniforms {
     type ProgramInfoCacheKey: 'static;
     type ProgramInfo: 'static;
     fn get_program_info(program: &Program) -> Self::ProgramInfo;
+    /// Get info for uniforms named with given prefix, used for nested uniform structs.
+    ///
+    /// Defaults to [Uniforms::get_program_info], types that don't override it
+    /// can only be nested without a prefix
+    fn get_program_info_prefixed(program: &Program, prefix: &str) -> Self::ProgramInfo {
+        if !prefix.is_empty() {
+            log::warn!(
+                "{} does not support prefixed uniforms, ignoring prefix {prefix:?}",
+                std::any::type_name::<Self>(),
+            );
+        }
+        Self::get_program_info(program)
+    }
     fn apply_uniforms(&self, program: &Program, info: &Self::ProgramInfo);
+    /// Collect names of uniforms set by this value, used for strict validation.
+    ///
+    /// Returns whether the names are complete, uniforms of types that
+    /// don't list them are not reported as missing
+    fn uniform_names(&self, prefix: &str, names: &mut Vec<String>) -> bool {
+        #![allow(unused_variables)]
+        false
+    }
 }
 
 impl<'a, U: Uniforms> Uniforms for &'a U {
@@ -13,9 +34,15 @@ impl<'a, U: Uniforms> Uniforms for &'a U {
     fn get_program_info(program: &Program) -> Self::ProgramInfo {
         U::get_program_info(program)
     }
+    fn get_program_info_prefixed(program: &Program, prefix: &str) -> Self::ProgramInfo {
+        U::get_program_info_prefixed(program, prefix)
+    }
     fn apply_uniforms(&self, program: &Program, info: &Self::ProgramInfo) {
         U::apply_uniforms(self, program, info)
     }
+    fn uniform_names(&self, prefix: &str, names: &mut Vec<String>) -> bool {
+        U::uniform_names(self, prefix, names)
+    }
 }
 
 macro_rules! impl_for_tuple {
@@ -27,6 +54,10 @@ macro_rules! impl_for_tuple {
                 #![allow(clippy::unused_unit, unused_variables)]
                 ($($a::get_program_info(program),)*)
             }
+            fn get_program_info_prefixed(program: &Program, prefix: &str) -> Self::ProgramInfo {
+                #![allow(clippy::unused_unit, unused_variables)]
+                ($($a::get_program_info_prefixed(program, prefix),)*)
+            }
             fn apply_uniforms(&self, program: &Program, info: &Self::ProgramInfo) {
                 #![allow(unused_parens, unused_variables)]
                 let ($($a,)*) = self;
@@ -35,6 +66,15 @@ macro_rules! impl_for_tuple {
                     $a.apply_uniforms(program, $b);
                 )*
             }
+            fn uniform_names(&self, prefix: &str, names: &mut Vec<String>) -> bool {
+                #![allow(unused_variables, unused_mut)]
+                let ($($a,)*) = self;
+                let mut complete = true;
+                $(
+                    complete &= $a.uniform_names(prefix, names);
+                )*
+                complete
+            }
         }
     };
 }
@@ -47,10 +87,46 @@ impl<U: Uniforms> Uniforms for Option<U> {
     fn get_program_info(program: &Program) -> Self::ProgramInfo {
         U::get_program_info(program)
     }
+    fn get_program_info_prefixed(program: &Program, prefix: &str) -> Self::ProgramInfo {
+        U::get_program_info_prefixed(program, prefix)
+    }
     fn apply_uniforms(&self, program: &Program, info: &Self::ProgramInfo) {
         if let Some(value) = self {
             value.apply_uniforms(program, info);
         }
         // TODO else default???
     }
+    fn uniform_names(&self, prefix: &str, names: &mut Vec<String>) -> bool {
+        match self {
+            Some(value) => value.uniform_names(prefix, names),
+            None => true,
+        }
+    }
+}
+
+/// Types that can be used as uniform arrays, see `#[uniform(array)]` in `#[derive(Uniforms)]`
+pub trait UniformArray {
+    type Item;
+    fn items(&self) -> &[Self::Item];
+}
+
+impl<T, const N: usize> UniformArray for [T; N] {
+    type Item = T;
+    fn items(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T> UniformArray for Vec<T> {
+    type Item = T;
+    fn items(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T> UniformArray for &[T] {
+    type Item = T;
+    fn items(&self) -> &[T] {
+        self
+    }
 }
@@ -3,7 +3,33 @@ use super::*;
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
 pub enum AttributeType {
     Float = raw::FLOAT as _,
+    /// Requires GL 3.0 / WebGL2
+    HalfFloat = raw::HALF_FLOAT as _,
+    I8 = raw::BYTE as _,
     U8 = raw::UNSIGNED_BYTE as _,
+    I16 = raw::SHORT as _,
+    U16 = raw::UNSIGNED_SHORT as _,
+    I32 = raw::INT as _,
+    U32 = raw::UNSIGNED_INT as _,
+}
+
+impl AttributeType {
+    pub fn is_integer(&self) -> bool {
+        !matches!(self, Self::Float | Self::HalfFloat)
+    }
+}
+
+/// How attribute data is passed to the shader
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, Default)]
+pub enum AttributeMode {
+    /// Values are converted to floats as is
+    #[default]
+    Float,
+    /// Integer values are mapped to `[0, 1]` (unsigned) or `[-1, 1]` (signed) floats
+    Normalized,
+    /// Integer values are passed as integers, shader should use `int`/`ivec`/`uint`/`uvec`.
+    /// Requires GL 3.0 / WebGL2
+    Integer,
 }
 
 /// # Safety
@@ -26,34 +52,79 @@ impl<T: VertexAttributePrimitive> VertexAttribute for T {
     }
 }
 
-unsafe impl VertexAttributePrimitive for u8 {
-    const SIZE: usize = 1;
-    const ROWS: usize = 1;
-    const TYPE: AttributeType = AttributeType::U8;
-}
+macro_rules! impl_primitive {
+    ($t:ty: $typ:ident) => {
+        unsafe impl VertexAttributePrimitive for $t {
+            const SIZE: usize = 1;
+            const ROWS: usize = 1;
+            const TYPE: AttributeType = AttributeType::$typ;
+        }
 
-unsafe impl VertexAttributePrimitive for f32 {
-    const SIZE: usize = 1;
-    const ROWS: usize = 1;
-    const TYPE: AttributeType = AttributeType::Float;
-}
+        unsafe impl VertexAttributePrimitive for [$t; 2] {
+            const SIZE: usize = 2;
+            const ROWS: usize = 1;
+            const TYPE: AttributeType = AttributeType::$typ;
+        }
 
-unsafe impl VertexAttributePrimitive for [f32; 2] {
-    const SIZE: usize = 2;
-    const ROWS: usize = 1;
-    const TYPE: AttributeType = AttributeType::Float;
+        unsafe impl VertexAttributePrimitive for [$t; 3] {
+            const SIZE: usize = 3;
+            const ROWS: usize = 1;
+            const TYPE: AttributeType = AttributeType::$typ;
+        }
+
+        unsafe impl VertexAttributePrimitive for [$t; 4] {
+            const SIZE: usize = 4;
+            const ROWS: usize = 1;
+            const TYPE: AttributeType = AttributeType::$typ;
+        }
+    };
 }
 
-unsafe impl VertexAttributePrimitive for [f32; 3] {
-    const SIZE: usize = 3;
-    const ROWS: usize = 1;
-    const TYPE: AttributeType = AttributeType::Float;
+impl_primitive!(f32: Float);
+impl_primitive!(f16: HalfFloat);
+impl_primitive!(i8: I8);
+impl_primitive!(u8: U8);
+impl_primitive!(i16: I16);
+impl_primitive!(u16: U16);
+impl_primitive!(i32: I32);
+impl_primitive!(u32: U32);
+
+/// Primitives with integer components,
+/// the only ones that can be used in [AttributeMode::Normalized] or [AttributeMode::Integer]
+///
+/// ```compile_fail
+/// #[derive(ugli::Vertex)]
+/// struct Vertex {
+///     #[attr(normalized)]
+///     a_value: f32,
+/// }
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not an integer attribute",
+    label = "only integer attributes can be `normalized` or `integer`"
+)]
+pub trait IntegerAttributePrimitive: VertexAttributePrimitive {}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl IntegerAttributePrimitive for $t {}
+            impl IntegerAttributePrimitive for [$t; 2] {}
+            impl IntegerAttributePrimitive for [$t; 3] {}
+            impl IntegerAttributePrimitive for [$t; 4] {}
+        )*
+    };
 }
 
-unsafe impl VertexAttributePrimitive for [f32; 4] {
-    const SIZE: usize = 4;
-    const ROWS: usize = 1;
-    const TYPE: AttributeType = AttributeType::Float;
+impl_integer!(i8, u8, i16, u16, i32, u32);
+
+/// Used by the derive macro to check `normalized` and `integer` attributes
+#[doc(hidden)]
+pub fn assert_integer_attribute<A>()
+where
+    A: VertexAttribute,
+    A::Primitive: IntegerAttributePrimitive,
+{
 }
 
 unsafe impl VertexAttributePrimitive for [[f32; 3]; 3] {
@@ -71,29 +142,41 @@ unsafe impl VertexAttributePrimitive for [[f32; 4]; 4] {
 mod batbox {
     use super::*;
 
-    impl VertexAttribute for vec2<f32> {
-        type Primitive = [f32; 2];
+    impl<T> VertexAttribute for vec2<T>
+    where
+        [T; 2]: VertexAttributePrimitive,
+    {
+        type Primitive = [T; 2];
         fn primitive_offset() -> usize {
             0
         }
     }
 
-    impl VertexAttribute for vec3<f32> {
-        type Primitive = [f32; 3];
+    impl<T> VertexAttribute for vec3<T>
+    where
+        [T; 3]: VertexAttributePrimitive,
+    {
+        type Primitive = [T; 3];
         fn primitive_offset() -> usize {
             0
         }
     }
 
-    impl VertexAttribute for vec4<f32> {
-        type Primitive = [f32; 4];
+    impl<T> VertexAttribute for vec4<T>
+    where
+        [T; 4]: VertexAttributePrimitive,
+    {
+        type Primitive = [T; 4];
         fn primitive_offset() -> usize {
             0
         }
     }
 
-    impl VertexAttribute for Rgba<f32> {
-        type Primitive = [f32; 4];
+    impl<T> VertexAttribute for Rgba<T>
+    where
+        [T; 4]: VertexAttributePrimitive,
+    {
+        type Primitive = [T; 4];
         fn primitive_offset() -> usize {
             0
         }
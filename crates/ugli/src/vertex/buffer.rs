@@ -21,7 +21,7 @@ impl RawBuffer {
     }
     fn bind(&self) {
         let gl = &self.ugli.inner.raw;
-        gl.bind_buffer(raw::ARRAY_BUFFER, &self.handle);
+        gl.bind_buffer(raw::ARRAY_BUFFER, Some(&self.handle));
         self.ugli.debug_check();
     }
     fn set_data<T>(&self, data: &Vec<T>) {
@@ -98,6 +98,15 @@ impl<T: Vertex> VertexBuffer<T> {
         }
     }
 
+    /// Name shown in GL debuggers and debug messages
+    pub fn set_name(&self, name: &str) {
+        self.buffer
+            .ugli
+            .inner
+            .raw
+            .object_label(raw::BUFFER, &self.buffer.handle, name);
+    }
+
     pub(crate) fn bind(&self) {
         if self.need_update.get() {
             self.buffer.set_data(&self.data);
@@ -105,6 +114,27 @@ impl<T: Vertex> VertexBuffer<T> {
         }
         self.buffer.bind();
     }
+
+    /// Handle to capture transform feedback into, uploads pending changes first
+    pub(crate) fn transform_feedback_handle(&mut self) -> &raw::Buffer {
+        self.bind();
+        &self.buffer.handle
+    }
+
+    /// Replace local data with the buffer contents on the GPU,
+    /// e.g. after [draw_transform_feedback].
+    ///
+    /// Local data is not updated automatically after the GPU writes into the buffer,
+    /// and modifying it uploads it back overwriting GPU results.
+    /// Requires GL 3.0 / WebGL2
+    pub fn read_back(&mut self) {
+        let gl = &self.buffer.ugli.inner.raw;
+        self.bind();
+        if !gl.get_buffer_sub_data(raw::ARRAY_BUFFER, 0, &mut self.data) {
+            log::error!("Failed to map vertex buffer for reading");
+        }
+        self.buffer.ugli.debug_check();
+    }
 }
 
 pub struct VertexBufferSlice<'a, T: Vertex + 'a> {
@@ -2,14 +2,28 @@ use super::*;
 
 mod attribute;
 mod buffer;
+mod ping_pong;
 mod vao;
 
 pub use attribute::*;
 pub use buffer::*;
+pub use ping_pong::*;
 pub use vao::*;
 
 pub trait VertexAttributeVisitor {
     fn visit<A: VertexAttribute>(&mut self, name: &str, offset: usize);
+    /// Visit an attribute passed to the shader in given mode.
+    ///
+    /// Defaults to [visit](Self::visit), ignoring the mode
+    fn visit_with_mode<A: VertexAttribute>(
+        &mut self,
+        name: &str,
+        offset: usize,
+        mode: AttributeMode,
+    ) {
+        let _ = mode;
+        self.visit::<A>(name, offset);
+    }
 }
 
 /// # Safety
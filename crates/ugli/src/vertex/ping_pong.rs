@@ -0,0 +1,54 @@
+use super::*;
+
+/// Pair of vertex buffers for simulations on the GPU.
+///
+/// Each step reads the [current](Self::current) buffer and captures
+/// the results into the other one using [draw_transform_feedback],
+/// then [swaps](Self::swap) them
+pub struct PingPongBuffer<T: Vertex> {
+    buffers: [VertexBuffer<T>; 2],
+    current: usize,
+}
+
+impl<T: Vertex + Clone> PingPongBuffer<T> {
+    pub fn new(ugli: &Ugli, data: Vec<T>) -> Self {
+        Self {
+            buffers: [
+                VertexBuffer::new_dynamic(ugli, data.clone()),
+                VertexBuffer::new_dynamic(ugli, data),
+            ],
+            current: 0,
+        }
+    }
+}
+
+impl<T: Vertex> PingPongBuffer<T> {
+    /// Buffer with the latest data
+    pub fn current(&self) -> &VertexBuffer<T> {
+        &self.buffers[self.current]
+    }
+
+    /// Buffer with the latest data for modifying it on the CPU.
+    ///
+    /// Reads the data back from the GPU first (see [VertexBuffer::read_back]),
+    /// so that changes don't overwrite results of the previous steps
+    pub fn current_mut(&mut self) -> &mut VertexBuffer<T> {
+        let current = &mut self.buffers[self.current];
+        current.read_back();
+        current
+    }
+
+    /// Returns current buffer to read from and the next one to write into
+    pub fn split(&mut self) -> (&VertexBuffer<T>, &mut VertexBuffer<T>) {
+        let [first, second] = &mut self.buffers;
+        match self.current {
+            0 => (first, second),
+            _ => (second, first),
+        }
+    }
+
+    /// Make the buffer written into by the last step current
+    pub fn swap(&mut self) {
+        self.current = 1 - self.current;
+    }
+}
@@ -0,0 +1,165 @@
+#![cfg(target_os = "linux")]
+
+use batbox_color::Rgba;
+use batbox_la::vec2;
+
+const VERTEX_SHADER: &str = "#version 130
+in vec2 a_pos;
+in vec4 a_color;
+in int a_index;
+flat out vec4 v_color;
+void main() {
+    v_color = vec4(a_color.rgb, float(a_index) / 255.0);
+    gl_Position = vec4(a_pos, 0.0, 1.0);
+}
+";
+
+const FRAGMENT_SHADER: &str = "#version 130
+flat in vec4 v_color;
+void main() {
+    gl_FragColor = v_color;
+}
+";
+
+#[derive(ugli::Vertex, Debug, Clone)]
+struct Vertex {
+    a_pos: batbox_la::vec2<f32>,
+    #[attr(normalized)]
+    a_color: Rgba<u8>,
+    #[attr(integer)]
+    a_index: i32,
+}
+
+/// Passes `a_color` as normalized floats without saying so
+#[derive(Debug, Clone)]
+struct FloatColorVertex {
+    a_pos: batbox_la::vec2<f32>,
+    a_color: Rgba<f32>,
+    a_index: i32,
+}
+
+unsafe impl ugli::Vertex for FloatColorVertex {
+    fn walk_attributes(mut visitor: impl ugli::VertexAttributeVisitor) {
+        visitor.visit_with_mode::<batbox_la::vec2<f32>>(
+            "a_pos",
+            ugli::field_offset!(Self.a_pos),
+            ugli::AttributeMode::Float,
+        );
+        visitor.visit_with_mode::<Rgba<f32>>(
+            "a_color",
+            ugli::field_offset!(Self.a_color),
+            ugli::AttributeMode::Normalized,
+        );
+        visitor.visit_with_mode::<i32>(
+            "a_index",
+            ugli::field_offset!(Self.a_index),
+            ugli::AttributeMode::Integer,
+        );
+    }
+}
+
+fn program(ugli: &ugli::Ugli) -> ugli::Program {
+    ugli::Program::new(
+        ugli,
+        [
+            &ugli::Shader::new(ugli, ugli::ShaderType::Vertex, VERTEX_SHADER).unwrap(),
+            &ugli::Shader::new(ugli, ugli::ShaderType::Fragment, FRAGMENT_SHADER).unwrap(),
+        ],
+    )
+    .unwrap()
+}
+
+fn covering_triangle() -> [batbox_la::vec2<f32>; 3] {
+    [vec2(-1.0, -1.0), vec2(3.0, -1.0), vec2(-1.0, 3.0)]
+}
+
+#[test]
+fn normalized_and_integer() {
+    let Some(ugli) = ugli::testing::create_headless() else {
+        return;
+    };
+    let program = program(&ugli);
+    let mut texture = ugli::Texture::new_uninitialized(&ugli, vec2(2, 2));
+    let mut framebuffer =
+        ugli::Framebuffer::new_color(&ugli, ugli::ColorAttachment::Texture(&mut texture));
+    ugli::clear(&mut framebuffer, Some(Rgba::BLACK), None, None);
+    let vertices = ugli::VertexBuffer::new_static(
+        &ugli,
+        covering_triangle()
+            .into_iter()
+            .map(|a_pos| Vertex {
+                a_pos,
+                a_color: Rgba::new(255, 51, 0, 0),
+                a_index: 102,
+            })
+            .collect(),
+    );
+    ugli::try_draw(
+        &mut framebuffer,
+        &program,
+        ugli::DrawMode::Triangles,
+        &vertices,
+        (),
+        ugli::DrawParameters::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        framebuffer.read_color().get(1, 1),
+        Rgba::new(255, 51, 0, 102)
+    );
+}
+
+#[test]
+fn normalized_float_is_an_error() {
+    let Some(ugli) = ugli::testing::create_headless() else {
+        return;
+    };
+    let program = program(&ugli);
+    let mut texture = ugli::Texture::new_uninitialized(&ugli, vec2(2, 2));
+    let mut framebuffer =
+        ugli::Framebuffer::new_color(&ugli, ugli::ColorAttachment::Texture(&mut texture));
+    ugli::clear(&mut framebuffer, Some(Rgba::BLACK), None, None);
+    let vertices = ugli::VertexBuffer::new_static(
+        &ugli,
+        covering_triangle()
+            .into_iter()
+            .map(|a_pos| FloatColorVertex {
+                a_pos,
+                a_color: Rgba::WHITE,
+                a_index: 255,
+            })
+            .collect(),
+    );
+    let result = ugli::try_draw(
+        &mut framebuffer,
+        &program,
+        ugli::DrawMode::Triangles,
+        &vertices,
+        (),
+        ugli::DrawParameters::default(),
+    );
+    assert!(matches!(
+        result,
+        Err(ugli::DrawError::InvalidAttributeMode {
+            ty: ugli::AttributeType::Float,
+            mode: ugli::AttributeMode::Normalized,
+            ..
+        })
+    ));
+    ugli.check();
+    assert_eq!(framebuffer.read_color().get(1, 1), Rgba::new(0, 0, 0, 255));
+}
+
+#[test]
+fn visit_defaults_to_ignoring_mode() {
+    #[derive(Default)]
+    struct Names(Vec<String>);
+    impl ugli::VertexAttributeVisitor for &mut Names {
+        fn visit<A: ugli::VertexAttribute>(&mut self, name: &str, _offset: usize) {
+            self.0.push(name.to_owned());
+        }
+    }
+    let mut names = Names::default();
+    <Vertex as ugli::Vertex>::walk_attributes(&mut names);
+    assert_eq!(names.0, ["a_pos", "a_color", "a_index"]);
+}
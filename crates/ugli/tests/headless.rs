@@ -0,0 +1,42 @@
+#![cfg(target_os = "linux")]
+
+use batbox_color::Rgba;
+use batbox_la::vec2;
+
+#[test]
+fn clear_and_read_back() {
+    let Some(ugli) = ugli::testing::create_headless() else {
+        return;
+    };
+    let mut texture = ugli::Texture::new_uninitialized(&ugli, vec2(4, 4));
+    let mut framebuffer =
+        ugli::Framebuffer::new_color(&ugli, ugli::ColorAttachment::Texture(&mut texture));
+    ugli::clear(&mut framebuffer, Some(Rgba::RED), None, None);
+    let data = framebuffer.read_color();
+    for x in 0..4 {
+        for y in 0..4 {
+            assert_eq!(data.get(x, y), Rgba::new(255, 0, 0, 255));
+        }
+    }
+    let image = data.into_image();
+    assert_eq!(image.dimensions(), (4, 4));
+}
+
+#[test]
+fn snapshot_mismatch() {
+    if std::env::var_os(ugli::testing::UPDATE_SNAPSHOTS_VAR).is_some() {
+        return;
+    }
+    let path = std::env::temp_dir().join(format!("ugli-snapshot-{}.png", std::process::id()));
+    let mut image = image::RgbaImage::from_pixel(4, 4, image::Rgba([10, 20, 30, 255]));
+    image.save(&path).unwrap();
+    image.put_pixel(1, 2, image::Rgba([12, 20, 30, 255]));
+    ugli::testing::assert_snapshot(&image, &path, 2);
+    image.put_pixel(1, 2, image::Rgba([13, 20, 30, 255]));
+    let result = std::panic::catch_unwind(|| ugli::testing::assert_snapshot(&image, &path, 2));
+    let actual_path = path.with_extension("actual.png");
+    assert!(result.is_err());
+    assert_eq!(image::open(&actual_path).unwrap().into_rgba8(), image);
+    std::fs::remove_file(path).unwrap();
+    std::fs::remove_file(actual_path).unwrap();
+}
@@ -0,0 +1,83 @@
+#![cfg(target_os = "linux")]
+
+const VERTEX_SHADER: &str = "#version 130
+in float a_value;
+out float v_value;
+uniform float u_add;
+void main() {
+    v_value = a_value * 2.0 + u_add;
+    gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+}
+";
+
+const FRAGMENT_SHADER: &str = "#version 130
+void main() {
+    gl_FragColor = vec4(1.0);
+}
+";
+
+#[derive(ugli::Vertex, Debug, Clone, PartialEq)]
+struct Particle {
+    a_value: f32,
+}
+
+fn step(ugli: &ugli::Ugli, program: &ugli::Program, buffers: &mut ugli::PingPongBuffer<Particle>) {
+    let (current, next) = buffers.split();
+    ugli::draw_transform_feedback(
+        None,
+        next,
+        program,
+        ugli::DrawMode::Points,
+        current,
+        ugli::uniforms! {
+            u_add: 1.0,
+        },
+        ugli::DrawParameters::default(),
+    );
+    buffers.swap();
+    ugli.debug_check();
+}
+
+fn values(buffers: &mut ugli::PingPongBuffer<Particle>) -> Vec<f32> {
+    buffers
+        .current_mut()
+        .iter()
+        .map(|particle| particle.a_value)
+        .collect()
+}
+
+#[test]
+fn step_and_modify() {
+    let Some(ugli) = ugli::testing::create_headless() else {
+        return;
+    };
+    let program = ugli::Program::new_with_transform_feedback(
+        &ugli,
+        [
+            &ugli::Shader::new(&ugli, ugli::ShaderType::Vertex, VERTEX_SHADER).unwrap(),
+            &ugli::Shader::new(&ugli, ugli::ShaderType::Fragment, FRAGMENT_SHADER).unwrap(),
+        ],
+        &["v_value"],
+    )
+    .unwrap();
+    // Surfaceless contexts have no default framebuffer, drawing needs a complete one bound
+    let mut texture = ugli::Texture::new_uninitialized(&ugli, batbox_la::vec2(1, 1));
+    let mut framebuffer =
+        ugli::Framebuffer::new_color(&ugli, ugli::ColorAttachment::Texture(&mut texture));
+    ugli::clear(
+        &mut framebuffer,
+        Some(batbox_color::Rgba::BLACK),
+        None,
+        None,
+    );
+    let mut buffers = ugli::PingPongBuffer::new(
+        &ugli,
+        vec![Particle { a_value: 1.0 }, Particle { a_value: 2.0 }],
+    );
+    step(&ugli, &program, &mut buffers);
+    assert_eq!(values(&mut buffers), [3.0, 5.0]);
+    // Changes are made on top of the step results
+    buffers.current_mut()[0].a_value = 10.0;
+    step(&ugli, &program, &mut buffers);
+    assert_eq!(values(&mut buffers), [21.0, 11.0]);
+}
@@ -0,0 +1,82 @@
+use batbox_color::Rgba;
+use batbox_la::*;
+use ugli::Uniforms;
+
+#[derive(ugli::Uniforms)]
+struct Light {
+    u_pos: vec2<f32>,
+    #[uniform(rename = "u_color")]
+    color: Rgba<f32>,
+}
+
+#[derive(ugli::Uniforms)]
+struct Scene {
+    #[uniform(rename = "u_time")]
+    time: f32,
+    #[uniform(nested)]
+    u_sun: Light,
+    #[uniform(prefix = "u_moon_")]
+    moon: Light,
+    #[uniform(array, nested, rename = "u_lights")]
+    lights: Vec<Light>,
+    #[uniform(array)]
+    u_weights: [f32; 2],
+}
+
+fn light(x: f32) -> Light {
+    Light {
+        u_pos: vec2(x, 0.0),
+        color: Rgba::WHITE,
+    }
+}
+
+fn names(uniforms: &impl Uniforms) -> Vec<String> {
+    let mut names = Vec::new();
+    assert!(uniforms.uniform_names("", &mut names));
+    names
+}
+
+#[test]
+fn rename() {
+    assert_eq!(names(&light(0.0)), ["u_pos", "u_color"]);
+}
+
+#[test]
+fn nested_prefix_and_arrays() {
+    let scene = Scene {
+        time: 0.0,
+        u_sun: light(1.0),
+        moon: light(2.0),
+        lights: vec![light(3.0), light(4.0)],
+        u_weights: [0.5, 0.5],
+    };
+    assert_eq!(
+        names(&scene),
+        [
+            "u_time",
+            "u_sun.u_pos",
+            "u_sun.u_color",
+            "u_moon_u_pos",
+            "u_moon_u_color",
+            "u_lights[0].u_pos",
+            "u_lights[0].u_color",
+            "u_lights[1].u_pos",
+            "u_lights[1].u_color",
+            "u_weights[0]",
+            "u_weights[1]",
+        ]
+    );
+    let mut prefixed = Vec::new();
+    assert!(scene.u_sun.uniform_names("u_scene.", &mut prefixed));
+    assert_eq!(prefixed, ["u_scene.u_pos", "u_scene.u_color"]);
+}
+
+#[test]
+fn empty_array() {
+    #[derive(ugli::Uniforms)]
+    struct Lights<'a> {
+        #[uniform(array, nested)]
+        u_lights: &'a [Light],
+    }
+    assert!(names(&Lights { u_lights: &[] }).is_empty());
+}
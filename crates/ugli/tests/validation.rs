@@ -0,0 +1,216 @@
+#![cfg(target_os = "linux")]
+
+use batbox_color::Rgba;
+use batbox_la::*;
+use std::sync::Mutex;
+
+const VERTEX_SHADER: &str = "
+attribute vec2 a_pos;
+attribute float a_value;
+uniform vec2 u_offset;
+varying float v_value;
+void main() {
+    v_value = a_value;
+    gl_Position = vec4(a_pos + u_offset, 0.0, 1.0);
+}
+";
+
+const FRAGMENT_SHADER: &str = "
+uniform vec4 u_color;
+varying float v_value;
+void main() {
+    gl_FragColor = u_color * v_value;
+}
+";
+
+#[derive(ugli::Vertex)]
+struct Vertex {
+    a_pos: vec2<f32>,
+    a_value: f32,
+}
+
+#[derive(ugli::Vertex)]
+struct WrongVertex {
+    a_pos: vec2<f32>,
+    a_value: vec2<f32>,
+}
+
+#[derive(ugli::Vertex)]
+struct PartialVertex {
+    a_pos: vec2<f32>,
+}
+
+/// Validation errors are only reported through the log
+static ERRORS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct Logger;
+
+impl log::Log for Logger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() == log::Level::Error
+    }
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            ERRORS.lock().unwrap().push(record.args().to_string());
+        }
+    }
+    fn flush(&self) {}
+}
+
+/// Errors reported for the program with given name
+fn errors(program: &str) -> Vec<String> {
+    let prefix = format!("Program {program}: ");
+    ERRORS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|error| error.strip_prefix(&prefix))
+        .map(str::to_owned)
+        .collect()
+}
+
+struct Test {
+    ugli: ugli::Ugli,
+    program: ugli::Program,
+    texture: ugli::Texture,
+}
+
+impl Test {
+    fn new(name: &str) -> Option<Self> {
+        static INIT: std::sync::Once = std::sync::Once::new();
+        INIT.call_once(|| {
+            log::set_logger(&Logger).unwrap();
+            log::set_max_level(log::LevelFilter::Error);
+        });
+        let ugli = ugli::testing::create_headless()?;
+        ugli.set_strict_validation(true);
+        let mut program = ugli::Program::new(
+            &ugli,
+            [
+                &ugli::Shader::new(&ugli, ugli::ShaderType::Vertex, VERTEX_SHADER).unwrap(),
+                &ugli::Shader::new(&ugli, ugli::ShaderType::Fragment, FRAGMENT_SHADER).unwrap(),
+            ],
+        )
+        .unwrap();
+        program.set_name(name);
+        let texture = ugli::Texture::new_uninitialized(&ugli, vec2(1, 1));
+        Some(Self {
+            ugli,
+            program,
+            texture,
+        })
+    }
+
+    fn draw<V: ugli::Vertex, U: ugli::Uniforms>(&mut self, vertices: Vec<V>, uniforms: U) {
+        let vertices = ugli::VertexBuffer::new_static(&self.ugli, vertices);
+        let mut framebuffer = ugli::Framebuffer::new_color(
+            &self.ugli,
+            ugli::ColorAttachment::Texture(&mut self.texture),
+        );
+        ugli::draw(
+            &mut framebuffer,
+            &self.program,
+            ugli::DrawMode::Points,
+            &vertices,
+            uniforms,
+            ugli::DrawParameters::default(),
+        );
+    }
+}
+
+fn vertex() -> Vertex {
+    Vertex {
+        a_pos: vec2::ZERO,
+        a_value: 1.0,
+    }
+}
+
+#[test]
+fn valid() {
+    let Some(mut test) = Test::new("valid") else {
+        return;
+    };
+    test.draw(
+        vec![vertex()],
+        ugli::uniforms! {
+            u_offset: vec2(0.0, 0.0),
+            u_color: Rgba::<f32>::WHITE,
+        },
+    );
+    assert_eq!(errors("valid"), Vec::<String>::new());
+}
+
+#[test]
+fn missing_uniform() {
+    let Some(mut test) = Test::new("missing_uniform") else {
+        return;
+    };
+    test.draw(
+        vec![vertex()],
+        ugli::uniforms! {
+            u_color: Rgba::<f32>::WHITE,
+        },
+    );
+    assert_eq!(
+        errors("missing_uniform"),
+        [r#"uniform "u_offset" is not set"#]
+    );
+}
+
+#[test]
+fn unknown_uniform() {
+    let Some(mut test) = Test::new("unknown_uniform") else {
+        return;
+    };
+    // Reported once per program
+    for _ in 0..2 {
+        test.draw(
+            vec![vertex()],
+            ugli::uniforms! {
+                u_offset: vec2(0.0, 0.0),
+                u_color: Rgba::<f32>::WHITE,
+                u_colour: Rgba::<f32>::WHITE,
+            },
+        );
+    }
+    assert_eq!(
+        errors("unknown_uniform"),
+        [r#"uniform "u_colour" is set but not used by the program"#]
+    );
+}
+
+#[test]
+fn attribute_mismatch() {
+    let Some(mut test) = Test::new("attribute_mismatch") else {
+        return;
+    };
+    let uniforms = ugli::uniforms! {
+        u_offset: vec2(0.0, 0.0),
+        u_color: Rgba::<f32>::WHITE,
+    };
+    test.draw(
+        vec![WrongVertex {
+            a_pos: vec2::ZERO,
+            a_value: vec2::ZERO,
+        }],
+        &uniforms,
+    );
+    test.draw(vec![PartialVertex { a_pos: vec2::ZERO }], &uniforms);
+    assert_eq!(
+        errors("attribute_mismatch"),
+        [
+            r#"attribute "a_value" of type Float is set from 2 x Float (Float)"#,
+            r#"attribute "a_value" is not set"#,
+        ]
+    );
+}
+
+#[test]
+fn disabled() {
+    let Some(mut test) = Test::new("disabled") else {
+        return;
+    };
+    test.ugli.set_strict_validation(false);
+    test.draw(vec![PartialVertex { a_pos: vec2::ZERO }], ());
+    assert_eq!(errors("disabled"), Vec::<String>::new());
+}
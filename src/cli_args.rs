@@ -8,6 +8,9 @@ pub struct CliArgs {
     /// Enable/disable hot reloading of assets
     #[clap(long, value_name = "BOOL")]
     pub hot_reload: Option<bool>,
+    /// Locale to start with instead of the detected one
+    #[clap(long)]
+    pub locale: Option<String>,
 }
 
 impl ContextOptions {
@@ -16,5 +19,8 @@ impl ContextOptions {
         if let Some(hot_reload) = args.hot_reload {
             self.hot_reload = hot_reload;
         }
+        if let Some(locale) = &args.locale {
+            self.locale = Some(locale.clone());
+        }
     }
 }
@@ -11,8 +11,9 @@ pub(crate) struct GengImpl {
     fixed_delta_time: Cell<f64>,
     max_delta_time: Cell<f64>,
     ui_theme: RefCell<Option<ui::Theme>>,
+    localization: i18n::Localization,
+    storage: save::Storage,
     pub(crate) options: ContextOptions,
-    pub(crate) load_progress: RefCell<asset::LoadProgress>,
     // pub(crate) gilrs: Option<RefCell<gilrs::Gilrs>>,
 }
 
@@ -29,6 +30,8 @@ pub struct ContextOptions {
     pub shader_prefix: Option<(String, String)>,
     pub target_ui_resolution: Option<vec2<f64>>,
     pub hot_reload: bool,
+    /// Initial locale, detected from the system or browser if `None`
+    pub locale: Option<String>,
 }
 
 impl Default for ContextOptions {
@@ -44,12 +47,14 @@ impl Default for ContextOptions {
                 fullscreen: !cfg!(debug_assertions),
                 auto_close: true,
                 start_hidden: false,
+                gl_debug: false,
             },
             fixed_delta_time: 0.05,
             max_delta_time: 0.1,
             shader_prefix: None,
             target_ui_resolution: None,
             hot_reload: cfg!(debug_assertions),
+            locale: None,
         }
     }
 }
@@ -107,8 +112,12 @@ impl Geng {
                     fixed_delta_time: Cell::new(options.fixed_delta_time),
                     max_delta_time: Cell::new(options.max_delta_time),
                     ui_theme: RefCell::new(None),
+                    localization: match &options.locale {
+                        Some(locale) => i18n::Localization::new(locale),
+                        None => i18n::Localization::detect(),
+                    },
+                    storage: save::Storage::new(),
                     options,
-                    load_progress: RefCell::new(asset::LoadProgress::new()),
                     // gilrs: if cfg!(target_os = "android") {
                     //     None
                     // } else {
@@ -163,6 +172,25 @@ impl Geng {
         *self.inner.ui_theme.borrow_mut() = Some(theme);
     }
 
+    /// Translations and the current locale, shared with everything that resolves text
+    pub fn localization(&self) -> &i18n::Localization {
+        &self.inner.localization
+    }
+
+    pub fn locale(&self) -> String {
+        self.inner.localization.locale()
+    }
+
+    /// Switch language, [LocalizedText](ui::LocalizedText) widgets update on the next frame
+    pub fn set_locale(&self, locale: &str) {
+        self.inner.localization.set_locale(locale);
+    }
+
+    /// Storage for save games and settings, see [save::Slots]
+    pub fn storage(&self) -> &save::Storage {
+        &self.inner.storage
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     pub fn set_icon(&self, path: &std::path::Path) -> anyhow::Result<()> {
         self.window().set_icon(path)
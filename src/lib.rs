@@ -54,8 +54,11 @@ pub use geng_camera::{
 };
 pub use geng_draw2d::{self as draw2d, Draw2d};
 pub use geng_font::{self as font, Font, TextAlign};
+pub use geng_i18n as i18n;
 pub use geng_net as net;
+pub use geng_save as save;
 pub use geng_shader as shader;
+pub use geng_sprite::{self as sprite, AnimationPlayer, SpriteSheet};
 pub use geng_state::{self as state, State};
 pub use geng_texture_atlas::{self as texture_atlas, TextureAtlas};
 pub use geng_ui as ui;
@@ -17,7 +17,11 @@ impl EmptyLoadingScreen {
     }
 }
 
-impl ProgressScreen for EmptyLoadingScreen {}
+impl ProgressScreen for EmptyLoadingScreen {
+    fn update_progress(&mut self, progress: f64) {
+        self.geng.set_loading_progress(progress, Some(1.0));
+    }
+}
 
 impl Drop for EmptyLoadingScreen {
     fn drop(&mut self) {
@@ -26,10 +30,5 @@ impl Drop for EmptyLoadingScreen {
 }
 
 impl State for EmptyLoadingScreen {
-    fn update(&mut self, _delta_time: f64) {
-        let progress = self.geng.inner.load_progress.borrow();
-        self.geng
-            .set_loading_progress(progress.progress as f64, Some(progress.total as f64));
-    }
     fn draw(&mut self, _framebuffer: &mut ugli::Framebuffer) {}
 }
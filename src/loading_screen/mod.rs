@@ -19,6 +19,9 @@ where
 {
     future: Pin<Box<dyn Future<Output = G>>>,
     state: L,
+    asset_manager: asset::Manager,
+    /// Only loads started after the screen was created are counted
+    start: asset::LoadProgress,
 }
 
 impl<L, G> LoadingScreen<L, G>
@@ -26,14 +29,12 @@ where
     L: ProgressScreen,
     G: State,
 {
-    pub fn new<F: Future<Output = G> + 'static>(
-        #[allow(unused_variables)] geng: &Geng,
-        state: L,
-        future: F,
-    ) -> Self {
+    pub fn new<F: Future<Output = G> + 'static>(geng: &Geng, state: L, future: F) -> Self {
         LoadingScreen {
             future: future.boxed_local(),
             state,
+            asset_manager: geng.asset_manager().clone(),
+            start: geng.asset_manager().load_progress(),
         }
     }
 }
@@ -45,7 +46,8 @@ where
 {
     fn update(&mut self, delta_time: f64) {
         self.state.update(delta_time);
-        // TODO: state.update_progress(future.progress().unwrap());
+        let progress = self.asset_manager.load_progress().since(&self.start);
+        self.state.update_progress(progress.fraction());
     }
     fn fixed_update(&mut self, delta_time: f64) {
         self.state.fixed_update(delta_time);